@@ -0,0 +1,223 @@
+use crate::AppState;
+use crate::planner::breeding::{
+    BreedingPair, BreedingStep, INHERITED_PASSIVE_COUNT_PCT, PassiveInheritance,
+    RANDOM_PASSIVE_COUNT_PCT, pair_offspring, parent_pairs, passive_inheritance,
+    shortest_breeding_path,
+};
+use crate::save::normalize::normalize_guid;
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct OffspringQuery {
+    pub parent_a: String,
+    pub parent_b: String,
+}
+
+#[derive(Serialize)]
+pub struct ParentPairsResponse {
+    pub child: String,
+    pub pairs: Vec<BreedingStep>,
+}
+
+#[derive(Deserialize)]
+pub struct PassiveInheritanceRequest {
+    #[serde(default)]
+    pub parent_a_passive_ids: Vec<String>,
+    #[serde(default)]
+    pub parent_b_passive_ids: Vec<String>,
+    #[serde(default)]
+    pub desired_passive_ids: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct PassiveInheritanceResponse {
+    /// `[count, percent]` pairs the odds are computed from.
+    pub inherited_count_pct: Vec<(usize, f64)>,
+    pub random_count_pct: Vec<(usize, f64)>,
+    #[serde(flatten)]
+    pub inheritance: PassiveInheritance,
+}
+
+#[derive(Deserialize)]
+pub struct BreedingPathQuery {
+    pub target: String,
+}
+
+#[derive(Serialize)]
+pub struct BreedingPathResponse {
+    pub import_version_id: Uuid,
+    pub player_uid: String,
+    pub target: String,
+    /// Catalog species of the player's pals.
+    pub owned_species: Vec<String>,
+    pub reachable: bool,
+    /// Breedings in order; empty when the target is owned or unreachable.
+    pub steps: Vec<BreedingStep>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+enum BreedingError {
+    NotFound(String),
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for BreedingError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+/// Child species of a parent pair. Parents may be save `CharacterID`s.
+pub async fn get_breeding_offspring(
+    State(state): State<AppState>,
+    Query(query): Query<OffspringQuery>,
+) -> impl IntoResponse {
+    match run_get_breeding_offspring(&state, &query) {
+        Ok(pair) => (StatusCode::OK, Json(pair)).into_response(),
+        Err(error) => breeding_error(error),
+    }
+}
+
+pub async fn get_breeding_parents(
+    State(state): State<AppState>,
+    Path(species_id): Path<String>,
+) -> impl IntoResponse {
+    let Some(child) = catalog_species_id(&state, &species_id) else {
+        return breeding_error(unknown_species(&species_id));
+    };
+    let pairs = parent_pairs(&state.catalog, &child);
+    (StatusCode::OK, Json(ParentPairsResponse { child, pairs })).into_response()
+}
+
+pub async fn get_passive_inheritance(
+    Json(request): Json<PassiveInheritanceRequest>,
+) -> impl IntoResponse {
+    Json(PassiveInheritanceResponse {
+        inherited_count_pct: INHERITED_PASSIVE_COUNT_PCT.to_vec(),
+        random_count_pct: RANDOM_PASSIVE_COUNT_PCT.to_vec(),
+        inheritance: passive_inheritance(
+            &request.parent_a_passive_ids,
+            &request.parent_b_passive_ids,
+            &request.desired_passive_ids,
+        ),
+    })
+}
+
+pub async fn get_breeding_path(
+    State(state): State<AppState>,
+    Path((import_version_id, player_uid)): Path<(Uuid, String)>,
+    Query(query): Query<BreedingPathQuery>,
+) -> impl IntoResponse {
+    match run_get_breeding_path(&state, import_version_id, &player_uid, &query.target).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(error) => breeding_error(error),
+    }
+}
+
+fn run_get_breeding_offspring(
+    state: &AppState,
+    query: &OffspringQuery,
+) -> Result<BreedingPair, BreedingError> {
+    let parent_a = catalog_species_id(state, &query.parent_a)
+        .ok_or_else(|| unknown_species(&query.parent_a))?;
+    let parent_b = catalog_species_id(state, &query.parent_b)
+        .ok_or_else(|| unknown_species(&query.parent_b))?;
+    pair_offspring(&state.catalog, &parent_a, &parent_b).ok_or_else(|| {
+        BreedingError::NotFound(format!("{} and {} cannot breed", parent_a, parent_b))
+    })
+}
+
+async fn run_get_breeding_path(
+    state: &AppState,
+    import_version_id: Uuid,
+    player_uid: &str,
+    target: &str,
+) -> Result<BreedingPathResponse, BreedingError> {
+    let player_uid = normalize_guid(player_uid);
+    let target = catalog_species_id(state, target).ok_or_else(|| unknown_species(target))?;
+    let exists = sqlx::query("SELECT id FROM save_import_versions WHERE id = $1")
+        .bind(import_version_id)
+        .fetch_optional(&state.pool)
+        .await?;
+    if exists.is_none() {
+        return Err(BreedingError::NotFound(
+            "import version not found".to_string(),
+        ));
+    }
+    let player = sqlx::query(
+        "SELECT player_uid FROM planner_players WHERE import_version_id = $1 AND player_uid = $2",
+    )
+    .bind(import_version_id)
+    .bind(&player_uid)
+    .fetch_optional(&state.pool)
+    .await?;
+    if player.is_none() {
+        return Err(BreedingError::NotFound(format!(
+            "player {} not found in this import",
+            player_uid
+        )));
+    }
+
+    let rows = sqlx::query(
+        "SELECT DISTINCT species_id
+         FROM planner_pals
+         WHERE import_version_id = $1 AND owner_player_uid = $2 AND species_id IS NOT NULL",
+    )
+    .bind(import_version_id)
+    .bind(&player_uid)
+    .fetch_all(&state.pool)
+    .await?;
+    let owned: BTreeSet<String> = rows
+        .iter()
+        .filter_map(|row| catalog_species_id(state, row.get("species_id")))
+        .collect();
+
+    let path = shortest_breeding_path(&state.catalog, &owned, &target);
+    Ok(BreedingPathResponse {
+        import_version_id,
+        player_uid,
+        target,
+        owned_species: owned.into_iter().collect(),
+        reachable: path.is_some(),
+        steps: path.unwrap_or_default(),
+    })
+}
+
+/// Catalog species id for a save `CharacterID`; breeding ignores variant
+/// prefixes and save casing.
+fn catalog_species_id(state: &AppState, character_id: &str) -> Option<String> {
+    state
+        .catalog
+        .species(character_id)
+        .map(|species| species.pal.species_id.clone())
+}
+
+fn unknown_species(species_id: &str) -> BreedingError {
+    BreedingError::NotFound(format!("unknown pal species {}", species_id))
+}
+
+fn breeding_error(error: BreedingError) -> axum::response::Response {
+    match error {
+        BreedingError::NotFound(error) => {
+            (StatusCode::NOT_FOUND, Json(ErrorResponse { error })).into_response()
+        }
+        BreedingError::Database(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to plan breeding: {}", error),
+            }),
+        )
+            .into_response(),
+    }
+}
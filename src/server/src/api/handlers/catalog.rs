@@ -0,0 +1,92 @@
+use crate::AppState;
+use crate::catalog::pals::{CatalogPal, CharacterVariant};
+use crate::catalog::skills::{CatalogActiveSkill, CatalogPassiveSkill};
+use crate::catalog::{DatasetSource, SUPPORTED_SCHEMA_VERSION};
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct CatalogPalsResponse {
+    pub schema_version: u32,
+    pub sources: Vec<DatasetSource>,
+    pub pals: Vec<CatalogPal>,
+}
+
+#[derive(Serialize)]
+pub struct CatalogPalResponse {
+    pub character_id: String,
+    pub variant: Option<CharacterVariant>,
+    pub pal: CatalogPal,
+}
+
+#[derive(Serialize)]
+pub struct CatalogPassivesResponse {
+    pub schema_version: u32,
+    pub sources: Vec<DatasetSource>,
+    pub passives: Vec<CatalogPassiveSkill>,
+}
+
+#[derive(Serialize)]
+pub struct CatalogSkillsResponse {
+    pub schema_version: u32,
+    pub sources: Vec<DatasetSource>,
+    pub skills: Vec<CatalogActiveSkill>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+pub async fn list_catalog_pals(State(state): State<AppState>) -> impl IntoResponse {
+    Json(CatalogPalsResponse {
+        schema_version: SUPPORTED_SCHEMA_VERSION,
+        sources: state.catalog.pal_sources().to_vec(),
+        pals: state.catalog.pals().to_vec(),
+    })
+}
+
+/// Looks up a species by save `CharacterID`, so prefixed ids such as
+/// `BOSS_Anubis` resolve to their base species.
+pub async fn get_catalog_pal(
+    State(state): State<AppState>,
+    Path(character_id): Path<String>,
+) -> impl IntoResponse {
+    match state.catalog.species(&character_id) {
+        Some(species) => (
+            StatusCode::OK,
+            Json(CatalogPalResponse {
+                variant: species.variant,
+                pal: species.pal.clone(),
+                character_id,
+            }),
+        )
+            .into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("unknown pal species {}", character_id),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+pub async fn list_catalog_passives(State(state): State<AppState>) -> impl IntoResponse {
+    Json(CatalogPassivesResponse {
+        schema_version: SUPPORTED_SCHEMA_VERSION,
+        sources: state.catalog.passive_sources().to_vec(),
+        passives: state.catalog.passives().to_vec(),
+    })
+}
+
+pub async fn list_catalog_skills(State(state): State<AppState>) -> impl IntoResponse {
+    Json(CatalogSkillsResponse {
+        schema_version: SUPPORTED_SCHEMA_VERSION,
+        sources: state.catalog.active_skill_sources().to_vec(),
+        skills: state.catalog.active_skills().to_vec(),
+    })
+}
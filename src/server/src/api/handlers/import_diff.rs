@@ -0,0 +1,204 @@
+use crate::AppState;
+use crate::save::diff::{NormalizedDiff, PalSnapshot, PlayerSnapshot, diff_normalized};
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::Row;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+#[derive(Serialize)]
+pub struct ImportDiffResponse {
+    pub from_import_version_id: Uuid,
+    pub to_import_version_id: Uuid,
+    pub summary: ImportDiffSummary,
+    #[serde(flatten)]
+    pub diff: NormalizedDiff,
+}
+
+#[derive(Serialize)]
+pub struct ImportDiffSummary {
+    pub players_added: usize,
+    pub players_removed: usize,
+    pub guild_changes: usize,
+    pub pals_added: usize,
+    pub pals_removed: usize,
+    pub pals_changed: usize,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+enum DiffOutcome {
+    Diff(Box<ImportDiffResponse>),
+    NotFound(Uuid),
+    NotReady(Uuid, String),
+    DifferentWorlds,
+}
+
+pub async fn diff_import_versions(
+    State(state): State<AppState>,
+    Path((from_id, to_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match run_diff_import_versions(&state, from_id, to_id).await {
+        Ok(DiffOutcome::Diff(response)) => (StatusCode::OK, Json(*response)).into_response(),
+        Ok(DiffOutcome::NotFound(id)) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("import version not found: {}", id),
+            }),
+        )
+            .into_response(),
+        Ok(DiffOutcome::NotReady(id, status)) => (
+            StatusCode::CONFLICT,
+            Json(ErrorResponse {
+                error: format!(
+                    "import version {} is {}; only ready imports can be diffed",
+                    id, status
+                ),
+            }),
+        )
+            .into_response(),
+        Ok(DiffOutcome::DifferentWorlds) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ErrorResponse {
+                error: "import versions belong to different worlds".to_string(),
+            }),
+        )
+            .into_response(),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to diff import versions: {}", error),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+async fn run_diff_import_versions(
+    state: &AppState,
+    from_id: Uuid,
+    to_id: Uuid,
+) -> Result<DiffOutcome, sqlx::Error> {
+    let mut world_ids = Vec::with_capacity(2);
+    for import_version_id in [from_id, to_id] {
+        let row = sqlx::query("SELECT status, world_id FROM save_import_versions WHERE id = $1")
+            .bind(import_version_id)
+            .fetch_optional(&state.pool)
+            .await?;
+        let Some(row) = row else {
+            return Ok(DiffOutcome::NotFound(import_version_id));
+        };
+        let status: String = row.get("status");
+        if status != "ready" {
+            return Ok(DiffOutcome::NotReady(import_version_id, status));
+        }
+        world_ids.push(row.get::<Option<Uuid>, _>("world_id"));
+    }
+    // Entities are keyed by save GUIDs, so imports of unrelated worlds would
+    // report everything as added and removed.
+    if world_ids[0].is_none() || world_ids[0] != world_ids[1] {
+        return Ok(DiffOutcome::DifferentWorlds);
+    }
+
+    let diff = diff_normalized(
+        load_player_snapshots(state, from_id).await?,
+        load_player_snapshots(state, to_id).await?,
+        load_pal_snapshots(state, from_id).await?,
+        load_pal_snapshots(state, to_id).await?,
+    );
+
+    Ok(DiffOutcome::Diff(Box::new(ImportDiffResponse {
+        from_import_version_id: from_id,
+        to_import_version_id: to_id,
+        summary: ImportDiffSummary {
+            players_added: diff.players.added.len(),
+            players_removed: diff.players.removed.len(),
+            guild_changes: diff.players.guild_changes.len(),
+            pals_added: diff.pals.added.len(),
+            pals_removed: diff.pals.removed.len(),
+            pals_changed: diff.pals.changed.len(),
+        },
+        diff,
+    })))
+}
+
+async fn load_player_snapshots(
+    state: &AppState,
+    import_version_id: Uuid,
+) -> Result<Vec<PlayerSnapshot>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT player_uid, player_name, guild_id, level
+         FROM planner_players
+         WHERE import_version_id = $1",
+    )
+    .bind(import_version_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| PlayerSnapshot {
+            player_uid: row.get("player_uid"),
+            player_name: row.get("player_name"),
+            guild_id: row.get("guild_id"),
+            level: row.get("level"),
+        })
+        .collect())
+}
+
+async fn load_pal_snapshots(
+    state: &AppState,
+    import_version_id: Uuid,
+) -> Result<Vec<PalSnapshot>, sqlx::Error> {
+    let assignment_rows = sqlx::query(
+        "SELECT DISTINCT pal_instance_id, base_id
+         FROM planner_base_assignments
+         WHERE import_version_id = $1
+         ORDER BY pal_instance_id ASC, base_id ASC",
+    )
+    .bind(import_version_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut base_ids_by_pal = BTreeMap::<String, Vec<String>>::new();
+    for row in assignment_rows {
+        base_ids_by_pal
+            .entry(row.get("pal_instance_id"))
+            .or_default()
+            .push(row.get("base_id"));
+    }
+
+    let pal_rows = sqlx::query(
+        "SELECT pal_instance_id, owner_player_uid, species_id, nickname, level, exp, passive_skill_ids
+         FROM planner_pals
+         WHERE import_version_id = $1",
+    )
+    .bind(import_version_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(pal_rows
+        .into_iter()
+        .map(|row| {
+            let pal_instance_id: String = row.get("pal_instance_id");
+            let passive_skill_ids: Value = row.get("passive_skill_ids");
+            PalSnapshot {
+                base_ids: base_ids_by_pal.remove(&pal_instance_id).unwrap_or_default(),
+                pal_instance_id,
+                owner_player_uid: row.get("owner_player_uid"),
+                species_id: row.get("species_id"),
+                nickname: row.get("nickname"),
+                level: row.get("level"),
+                exp: row.get("exp"),
+                passive_skill_ids: serde_json::from_value(passive_skill_ids).unwrap_or_default(),
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,142 @@
+use crate::AppState;
+use crate::api::handlers::import_zip::{ApiError, ImportSource, ImportZipResponse, persist_import};
+use crate::save::archive::{ArchiveLimitError, read_directory_entries};
+use anyhow::{Context, Result};
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, DateTime, ZipWriter};
+
+#[derive(Deserialize)]
+pub struct ImportDirectoryRequest {
+    pub path: String,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+pub async fn import_directory(
+    State(state): State<AppState>,
+    Json(request): Json<ImportDirectoryRequest>,
+) -> impl IntoResponse {
+    let result =
+        match resolve_allowed_directory(&state.settings.import_directory_roots, &request.path) {
+            Ok(directory) => import_world_directory(&state, &directory, "directory").await,
+            Err(error) => Err(error),
+        };
+
+    match result {
+        Ok(response) => (StatusCode::CREATED, Json(response)).into_response(),
+        Err(error) => (
+            error.status,
+            Json(ErrorResponse {
+                error: error.message,
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Reads a world folder that has already been resolved inside an allow-listed
+/// root and runs it through the same pipeline as an uploaded archive.
+pub(crate) async fn import_world_directory(
+    state: &AppState,
+    directory: &Path,
+    source_kind: &'static str,
+) -> Result<ImportZipResponse, ApiError> {
+    let limits = state.settings.archive_limits;
+    let read_directory = directory.to_path_buf();
+    let (extraction, archive_bytes) = tokio::task::spawn_blocking(move || {
+        let extraction = read_directory_entries(&read_directory, &limits)?;
+        let archive_bytes = build_zip_archive(
+            extraction
+                .entries
+                .iter()
+                .map(|entry| (entry.path.as_str(), entry.bytes.as_slice())),
+        )?;
+        Ok::<_, anyhow::Error>((extraction, archive_bytes))
+    })
+    .await
+    .map_err(|error| ApiError::internal(format!("directory read worker panicked: {}", error)))?
+    .map_err(|error| match error.downcast_ref::<ArchiveLimitError>() {
+        Some(limit_error) => ApiError::from_archive_limit(limit_error),
+        None => ApiError::bad_request(format!("invalid world directory: {:#}", error)),
+    })?;
+
+    let folder_name = directory
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "world".to_string());
+
+    persist_import(
+        state,
+        ImportSource {
+            source_kind,
+            source_path: Some(directory.to_string_lossy().to_string()),
+            source_file_name: format!("{}.zip", folder_name),
+            archive_format: extraction.format,
+            archive_bytes,
+            entries: extraction.entries,
+            skipped: extraction.skipped,
+        },
+    )
+    .await
+}
+
+/// Resolves `requested` (absolute, or relative to a root) to a canonical
+/// directory that lies inside one of the canonical allow-listed roots.
+pub(crate) fn resolve_allowed_directory(
+    roots: &[PathBuf],
+    requested: &str,
+) -> Result<PathBuf, ApiError> {
+    if roots.is_empty() {
+        return Err(ApiError::bad_request(
+            "directory import is disabled; IMPORT_DIRECTORY_ROOTS is not configured",
+        ));
+    }
+
+    for root in roots {
+        let Ok(candidate) = root.join(requested).canonicalize() else {
+            continue;
+        };
+        if candidate.starts_with(root) && candidate.is_dir() {
+            return Ok(candidate);
+        }
+    }
+
+    Err(ApiError::bad_request(format!(
+        "directory is not inside an allowed import root: {}",
+        requested
+    )))
+}
+
+fn build_zip_archive<'a>(files: impl IntoIterator<Item = (&'a str, &'a [u8])>) -> Result<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    // Fixed timestamps keep the archive bytes, and therefore its hashes, a pure
+    // function of the file contents.
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .last_modified_time(DateTime::default())
+        .large_file(true);
+
+    for (path, bytes) in files {
+        writer
+            .start_file(path, options)
+            .with_context(|| format!("failed to start ZIP entry {}", path))?;
+        writer
+            .write_all(bytes)
+            .with_context(|| format!("failed to write ZIP entry {}", path))?;
+    }
+
+    let cursor = writer
+        .finish()
+        .with_context(|| "failed to finalize ZIP archive")?;
+    Ok(cursor.into_inner())
+}
@@ -1,12 +1,18 @@
 use crate::AppState;
+use crate::api::handlers::import_zip::seed_planner_players_from_files;
+use crate::catalog::pals::PalSpeciesInfo;
+use crate::jobs::{JobKind, cancel_queued_jobs, enqueue_job};
+use crate::save::normalize::CharacterSelectionPolicy;
+use anyhow::Context;
 use axum::Json;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::response::sse::{Event, KeepAlive, Sse};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::Row;
+use std::collections::HashMap;
 use std::convert::Infallible;
 use std::time::Duration;
 use uuid::Uuid;
@@ -15,7 +21,18 @@ use uuid::Uuid;
 pub struct ImportVersionListItem {
     pub id: Uuid,
     pub source_file_name: String,
+    pub source_kind: String,
+    pub source_path: Option<String>,
+    pub world_id: Option<Uuid>,
+    pub source_export_version_id: Option<Uuid>,
     pub world_root_path: String,
+    pub world_name: Option<String>,
+    pub host_player_name: Option<String>,
+    pub host_player_level: Option<i32>,
+    pub in_game_day: Option<i32>,
+    pub save_game_version: Option<i32>,
+    pub engine_version: Option<String>,
+    pub selection_policy: Option<String>,
     pub status: String,
     pub progress_phase: String,
     pub progress_pct: i32,
@@ -32,6 +49,18 @@ pub struct ImportVersionListItem {
     pub base_assignment_count: i64,
 }
 
+/// Optional reprocess body. A `selection_policy` is stored on the import and
+/// reused by later reprocessing.
+#[derive(Deserialize)]
+pub struct ReprocessRequest {
+    pub selection_policy: Option<CharacterSelectionPolicy>,
+}
+
+#[derive(Deserialize)]
+pub struct ImportVersionListQuery {
+    pub world_id: Option<Uuid>,
+}
+
 #[derive(Serialize)]
 pub struct ImportVersionListResponse {
     pub versions: Vec<ImportVersionListItem>,
@@ -73,6 +102,7 @@ pub struct NormalizedPlayerRow {
     pub player_name: Option<String>,
     pub guild_id: Option<String>,
     pub level: Option<i32>,
+    pub selection_reason: Option<String>,
     pub raw_file_ref: Option<Uuid>,
     pub raw_entity_path: String,
 }
@@ -83,8 +113,16 @@ pub struct NormalizedPalRow {
     pub pal_instance_id: String,
     pub owner_player_uid: Option<String>,
     pub species_id: Option<String>,
+    /// Catalog data for `species_id`; absent when the id is not in the
+    /// game data.
+    pub species: Option<PalSpeciesInfo>,
     pub nickname: Option<String>,
     pub level: Option<i32>,
+    pub is_lucky: bool,
+    pub location_kind: String,
+    pub container_id: Option<String>,
+    pub slot_index: Option<i32>,
+    pub selection_reason: Option<String>,
     pub raw_file_ref: Option<Uuid>,
     pub raw_entity_path: String,
 }
@@ -101,12 +139,57 @@ pub struct NormalizedAssignmentRow {
     pub raw_entity_path: String,
 }
 
+#[derive(Serialize)]
+pub struct NormalizedBaseRow {
+    pub id: Uuid,
+    pub base_id: String,
+    pub base_name: Option<String>,
+    pub state: Option<i32>,
+    pub area_range: Option<f64>,
+    pub group_id: Option<String>,
+    pub worker_container_id: Option<String>,
+    pub worker_order_type: Option<i32>,
+    pub location_x: Option<f64>,
+    pub location_y: Option<f64>,
+    pub location_z: Option<f64>,
+    pub transform: Option<Value>,
+    pub worker_count: i32,
+    pub raw_file_ref: Option<Uuid>,
+    pub raw_entity_path: String,
+}
+
+#[derive(Serialize)]
+pub struct NormalizedGuildMemberRow {
+    pub id: Uuid,
+    pub player_uid: String,
+    pub player_name: Option<String>,
+    pub last_online_ticks: Option<i64>,
+    pub offline_seconds: Option<i64>,
+    pub is_admin: bool,
+}
+
+#[derive(Serialize)]
+pub struct NormalizedGuildRow {
+    pub id: Uuid,
+    pub group_id: String,
+    pub group_type: String,
+    pub guild_name: Option<String>,
+    pub admin_player_uid: Option<String>,
+    pub base_camp_level: Option<i32>,
+    pub base_ids: Value,
+    pub members: Vec<NormalizedGuildMemberRow>,
+    pub raw_file_ref: Option<Uuid>,
+    pub raw_entity_path: String,
+}
+
 #[derive(Serialize)]
 pub struct NormalizedResponse {
     pub import_version_id: Uuid,
     pub players: Vec<NormalizedPlayerRow>,
     pub pals: Vec<NormalizedPalRow>,
     pub base_assignments: Vec<NormalizedAssignmentRow>,
+    pub bases: Vec<NormalizedBaseRow>,
+    pub guilds: Vec<NormalizedGuildRow>,
 }
 
 #[derive(Serialize, Clone, PartialEq)]
@@ -129,8 +212,17 @@ struct ErrorResponse {
     error: String,
 }
 
-pub async fn list_import_versions(State(state): State<AppState>) -> impl IntoResponse {
-    match run_list_import_versions(&state).await {
+enum ImportVersionTransition {
+    Applied(ImportProgressEvent),
+    NotFound,
+    Conflict(String),
+}
+
+pub async fn list_import_versions(
+    State(state): State<AppState>,
+    Query(query): Query<ImportVersionListQuery>,
+) -> impl IntoResponse {
+    match run_list_import_versions(&state, query.world_id).await {
         Ok(response) => (StatusCode::OK, Json(response)).into_response(),
         Err(error) => (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -188,6 +280,59 @@ pub async fn get_normalized(
     }
 }
 
+pub async fn cancel_import_version(
+    State(state): State<AppState>,
+    Path(import_version_id): Path<Uuid>,
+) -> impl IntoResponse {
+    transition_response(
+        run_cancel_import_version(&state, import_version_id).await,
+        StatusCode::OK,
+        "failed to cancel import version",
+    )
+}
+
+pub async fn reprocess_import_version(
+    State(state): State<AppState>,
+    Path(import_version_id): Path<Uuid>,
+    request: Option<Json<ReprocessRequest>>,
+) -> impl IntoResponse {
+    let selection_policy = request.and_then(|Json(request)| request.selection_policy);
+    transition_response(
+        run_reprocess_import_version(&state, import_version_id, selection_policy).await,
+        StatusCode::ACCEPTED,
+        "failed to reprocess import version",
+    )
+}
+
+fn transition_response(
+    result: anyhow::Result<ImportVersionTransition>,
+    success_status: StatusCode,
+    error_context: &str,
+) -> axum::response::Response {
+    match result {
+        Ok(ImportVersionTransition::Applied(progress)) => {
+            (success_status, Json(progress)).into_response()
+        }
+        Ok(ImportVersionTransition::NotFound) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "import version not found".to_string(),
+            }),
+        )
+            .into_response(),
+        Ok(ImportVersionTransition::Conflict(message)) => {
+            (StatusCode::CONFLICT, Json(ErrorResponse { error: message })).into_response()
+        }
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("{}: {:#}", error_context, error),
+            }),
+        )
+            .into_response(),
+    }
+}
+
 pub async fn stream_import_version_events(
     State(state): State<AppState>,
     Path(import_version_id): Path<Uuid>,
@@ -250,12 +395,24 @@ pub async fn stream_import_version_events(
 
 async fn run_list_import_versions(
     state: &AppState,
+    world_id: Option<Uuid>,
 ) -> Result<ImportVersionListResponse, sqlx::Error> {
     let rows = sqlx::query(
         "SELECT
             iv.id,
             iv.source_file_name,
+            iv.source_kind,
+            iv.source_path,
+            iv.world_id,
+            iv.source_export_version_id,
             iv.world_root_path,
+            iv.world_name,
+            iv.host_player_name,
+            iv.host_player_level,
+            iv.in_game_day,
+            iv.save_game_version,
+            iv.engine_version,
+            iv.selection_policy,
             iv.status,
             iv.progress_phase,
             iv.progress_pct,
@@ -271,9 +428,11 @@ async fn run_list_import_versions(
             (SELECT COUNT(*) FROM planner_pals pp WHERE pp.import_version_id = iv.id) AS pal_count,
             (SELECT COUNT(*) FROM planner_base_assignments pba WHERE pba.import_version_id = iv.id) AS base_assignment_count
          FROM save_import_versions iv
+         WHERE $1::uuid IS NULL OR iv.world_id = $1
          ORDER BY iv.created_at DESC
          LIMIT 100",
     )
+    .bind(world_id)
     .fetch_all(&state.pool)
     .await?;
 
@@ -282,7 +441,18 @@ async fn run_list_import_versions(
         versions.push(ImportVersionListItem {
             id: row.get("id"),
             source_file_name: row.get("source_file_name"),
+            source_kind: row.get("source_kind"),
+            source_path: row.get("source_path"),
+            world_id: row.get("world_id"),
+            source_export_version_id: row.get("source_export_version_id"),
             world_root_path: row.get("world_root_path"),
+            world_name: row.get("world_name"),
+            host_player_name: row.get("host_player_name"),
+            host_player_level: row.get("host_player_level"),
+            in_game_day: row.get("in_game_day"),
+            save_game_version: row.get("save_game_version"),
+            engine_version: row.get("engine_version"),
+            selection_policy: row.get("selection_policy"),
             status: row.get("status"),
             progress_phase: row.get("progress_phase"),
             progress_pct: row.get("progress_pct"),
@@ -311,7 +481,18 @@ async fn run_get_import_version(
         "SELECT
             iv.id,
             iv.source_file_name,
+            iv.source_kind,
+            iv.source_path,
+            iv.world_id,
+            iv.source_export_version_id,
             iv.world_root_path,
+            iv.world_name,
+            iv.host_player_name,
+            iv.host_player_level,
+            iv.in_game_day,
+            iv.save_game_version,
+            iv.engine_version,
+            iv.selection_policy,
             iv.status,
             iv.progress_phase,
             iv.progress_pct,
@@ -341,7 +522,18 @@ async fn run_get_import_version(
     let version = ImportVersionListItem {
         id: row.get("id"),
         source_file_name: row.get("source_file_name"),
+        source_kind: row.get("source_kind"),
+        source_path: row.get("source_path"),
+        world_id: row.get("world_id"),
+        source_export_version_id: row.get("source_export_version_id"),
         world_root_path: row.get("world_root_path"),
+        world_name: row.get("world_name"),
+        host_player_name: row.get("host_player_name"),
+        host_player_level: row.get("host_player_level"),
+        in_game_day: row.get("in_game_day"),
+        save_game_version: row.get("save_game_version"),
+        engine_version: row.get("engine_version"),
+        selection_policy: row.get("selection_policy"),
         status: row.get("status"),
         progress_phase: row.get("progress_phase"),
         progress_pct: row.get("progress_pct"),
@@ -428,7 +620,7 @@ async fn run_get_normalized(
     }
 
     let player_rows = sqlx::query(
-        "SELECT id, player_uid, player_instance_id, player_name, guild_id, level, raw_file_ref, raw_entity_path
+        "SELECT id, player_uid, player_instance_id, player_name, guild_id, level, selection_reason, raw_file_ref, raw_entity_path
          FROM planner_players
          WHERE import_version_id = $1
          ORDER BY player_uid ASC",
@@ -438,7 +630,8 @@ async fn run_get_normalized(
     .await?;
 
     let pal_rows = sqlx::query(
-        "SELECT id, pal_instance_id, owner_player_uid, species_id, nickname, level, raw_file_ref, raw_entity_path
+        "SELECT id, pal_instance_id, owner_player_uid, species_id, nickname, level, is_lucky, location_kind, container_id,
+                slot_index, selection_reason, raw_file_ref, raw_entity_path
          FROM planner_pals
          WHERE import_version_id = $1
          ORDER BY pal_instance_id ASC",
@@ -457,6 +650,37 @@ async fn run_get_normalized(
     .fetch_all(&state.pool)
     .await?;
 
+    let base_rows = sqlx::query(
+        "SELECT id, base_id, base_name, state, area_range, group_id, worker_container_id, worker_order_type,
+                location_x, location_y, location_z, transform, worker_count, raw_file_ref, raw_entity_path
+         FROM planner_bases
+         WHERE import_version_id = $1
+         ORDER BY base_id ASC",
+    )
+    .bind(import_version_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let guild_rows = sqlx::query(
+        "SELECT id, group_id, group_type, guild_name, admin_player_uid, base_camp_level, base_ids, raw_file_ref, raw_entity_path
+         FROM planner_guilds
+         WHERE import_version_id = $1
+         ORDER BY group_id ASC",
+    )
+    .bind(import_version_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let member_rows = sqlx::query(
+        "SELECT id, group_id, player_uid, player_name, last_online_ticks, offline_seconds, is_admin
+         FROM planner_guild_members
+         WHERE import_version_id = $1
+         ORDER BY group_id ASC, is_admin DESC, player_uid ASC",
+    )
+    .bind(import_version_id)
+    .fetch_all(&state.pool)
+    .await?;
+
     let mut players = Vec::with_capacity(player_rows.len());
     for row in player_rows {
         players.push(NormalizedPlayerRow {
@@ -466,6 +690,7 @@ async fn run_get_normalized(
             player_name: row.get("player_name"),
             guild_id: row.get("guild_id"),
             level: row.get("level"),
+            selection_reason: row.get("selection_reason"),
             raw_file_ref: row.get("raw_file_ref"),
             raw_entity_path: row.get("raw_entity_path"),
         });
@@ -473,13 +698,24 @@ async fn run_get_normalized(
 
     let mut pals = Vec::with_capacity(pal_rows.len());
     for row in pal_rows {
+        let species_id: Option<String> = row.get("species_id");
+        let species = species_id
+            .as_deref()
+            .and_then(|species_id| state.catalog.species(species_id))
+            .map(|species| species.info());
         pals.push(NormalizedPalRow {
             id: row.get("id"),
             pal_instance_id: row.get("pal_instance_id"),
             owner_player_uid: row.get("owner_player_uid"),
-            species_id: row.get("species_id"),
+            species_id,
+            species,
             nickname: row.get("nickname"),
             level: row.get("level"),
+            is_lucky: row.get("is_lucky"),
+            location_kind: row.get("location_kind"),
+            container_id: row.get("container_id"),
+            slot_index: row.get("slot_index"),
+            selection_reason: row.get("selection_reason"),
             raw_file_ref: row.get("raw_file_ref"),
             raw_entity_path: row.get("raw_entity_path"),
         });
@@ -499,14 +735,236 @@ async fn run_get_normalized(
         });
     }
 
+    let mut bases = Vec::with_capacity(base_rows.len());
+    for row in base_rows {
+        bases.push(NormalizedBaseRow {
+            id: row.get("id"),
+            base_id: row.get("base_id"),
+            base_name: row.get("base_name"),
+            state: row.get("state"),
+            area_range: row.get("area_range"),
+            group_id: row.get("group_id"),
+            worker_container_id: row.get("worker_container_id"),
+            worker_order_type: row.get("worker_order_type"),
+            location_x: row.get("location_x"),
+            location_y: row.get("location_y"),
+            location_z: row.get("location_z"),
+            transform: row.get("transform"),
+            worker_count: row.get("worker_count"),
+            raw_file_ref: row.get("raw_file_ref"),
+            raw_entity_path: row.get("raw_entity_path"),
+        });
+    }
+
+    let mut members_by_group = HashMap::<String, Vec<NormalizedGuildMemberRow>>::new();
+    for row in member_rows {
+        members_by_group
+            .entry(row.get("group_id"))
+            .or_default()
+            .push(NormalizedGuildMemberRow {
+                id: row.get("id"),
+                player_uid: row.get("player_uid"),
+                player_name: row.get("player_name"),
+                last_online_ticks: row.get("last_online_ticks"),
+                offline_seconds: row.get("offline_seconds"),
+                is_admin: row.get("is_admin"),
+            });
+    }
+
+    let mut guilds = Vec::with_capacity(guild_rows.len());
+    for row in guild_rows {
+        let group_id: String = row.get("group_id");
+        guilds.push(NormalizedGuildRow {
+            id: row.get("id"),
+            members: members_by_group.remove(&group_id).unwrap_or_default(),
+            group_id,
+            group_type: row.get("group_type"),
+            guild_name: row.get("guild_name"),
+            admin_player_uid: row.get("admin_player_uid"),
+            base_camp_level: row.get("base_camp_level"),
+            base_ids: row.get("base_ids"),
+            raw_file_ref: row.get("raw_file_ref"),
+            raw_entity_path: row.get("raw_entity_path"),
+        });
+    }
+
     Ok(Some(NormalizedResponse {
         import_version_id,
         players,
         pals,
         base_assignments,
+        bases,
+        guilds,
     }))
 }
 
+async fn run_cancel_import_version(
+    state: &AppState,
+    import_version_id: Uuid,
+) -> anyhow::Result<ImportVersionTransition> {
+    let mut tx = state
+        .pool
+        .begin()
+        .await
+        .with_context(|| "failed to start cancel transaction")?;
+
+    let status: Option<String> =
+        sqlx::query_scalar("SELECT status FROM save_import_versions WHERE id = $1 FOR UPDATE")
+            .bind(import_version_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .with_context(|| "failed to lock import version")?;
+    let Some(status) = status else {
+        return Ok(ImportVersionTransition::NotFound);
+    };
+    if status != "processing" {
+        return Ok(ImportVersionTransition::Conflict(format!(
+            "import version is {}; only processing imports can be cancelled",
+            status
+        )));
+    }
+
+    sqlx::query(
+        "UPDATE save_import_versions
+         SET status = 'failed',
+             progress_phase = 'cancelled',
+             progress_pct = 100,
+             progress_message = 'Import processing cancelled',
+             failed_error = 'cancelled',
+             completed_at = NOW()
+         WHERE id = $1",
+    )
+    .bind(import_version_id)
+    .execute(&mut *tx)
+    .await
+    .with_context(|| "failed to mark import version cancelled")?;
+
+    cancel_queued_jobs(&mut *tx, import_version_id).await?;
+
+    tx.commit()
+        .await
+        .with_context(|| "failed to commit cancel transaction")?;
+
+    applied_transition(state, import_version_id).await
+}
+
+/// Clears planner rows and requeues decode/normalize from the stored immutable
+/// artifacts; the source archive and file manifests are left untouched.
+async fn run_reprocess_import_version(
+    state: &AppState,
+    import_version_id: Uuid,
+    selection_policy: Option<CharacterSelectionPolicy>,
+) -> anyhow::Result<ImportVersionTransition> {
+    let mut tx = state
+        .pool
+        .begin()
+        .await
+        .with_context(|| "failed to start reprocess transaction")?;
+
+    let status: Option<String> =
+        sqlx::query_scalar("SELECT status FROM save_import_versions WHERE id = $1 FOR UPDATE")
+            .bind(import_version_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .with_context(|| "failed to lock import version")?;
+    let Some(status) = status else {
+        return Ok(ImportVersionTransition::NotFound);
+    };
+    if status == "processing" {
+        return Ok(ImportVersionTransition::Conflict(
+            "import version is still processing; cancel it before reprocessing".to_string(),
+        ));
+    }
+
+    let has_active_job: bool = sqlx::query_scalar(
+        "SELECT EXISTS (
+            SELECT 1 FROM background_jobs
+            WHERE import_version_id = $1 AND status IN ('queued', 'running')
+         )",
+    )
+    .bind(import_version_id)
+    .fetch_one(&mut *tx)
+    .await
+    .with_context(|| "failed to check active jobs")?;
+    if has_active_job {
+        return Ok(ImportVersionTransition::Conflict(
+            "a cancelled job for this import version is still stopping; retry shortly".to_string(),
+        ));
+    }
+
+    for statement in [
+        "DELETE FROM planner_player_links WHERE planner_player_id IN (SELECT id FROM planner_players WHERE import_version_id = $1)",
+        "DELETE FROM planner_pal_links WHERE planner_pal_id IN (SELECT id FROM planner_pals WHERE import_version_id = $1)",
+        "DELETE FROM planner_base_assignment_links WHERE planner_base_assignment_id IN (SELECT id FROM planner_base_assignments WHERE import_version_id = $1)",
+        "DELETE FROM planner_base_links WHERE planner_base_id IN (SELECT id FROM planner_bases WHERE import_version_id = $1)",
+        "DELETE FROM planner_guild_member_links WHERE planner_guild_member_id IN (SELECT id FROM planner_guild_members WHERE import_version_id = $1)",
+        "DELETE FROM planner_guild_links WHERE planner_guild_id IN (SELECT id FROM planner_guilds WHERE import_version_id = $1)",
+        "DELETE FROM planner_players WHERE import_version_id = $1",
+        "DELETE FROM planner_pals WHERE import_version_id = $1",
+        "DELETE FROM planner_base_assignments WHERE import_version_id = $1",
+        "DELETE FROM planner_bases WHERE import_version_id = $1",
+        "DELETE FROM planner_guild_members WHERE import_version_id = $1",
+        "DELETE FROM planner_guilds WHERE import_version_id = $1",
+    ] {
+        sqlx::query(statement)
+            .bind(import_version_id)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| "failed to clear planner rows")?;
+    }
+
+    seed_planner_players_from_files(&mut tx, import_version_id).await?;
+
+    sqlx::query(
+        "UPDATE save_import_versions
+         SET status = 'processing',
+             progress_phase = 'queued_decode',
+             progress_pct = 35,
+             progress_message = 'Queued reprocessing from stored artifacts',
+             failed_error = NULL,
+             parse_metrics_json = NULL,
+             world_name = NULL,
+             host_player_name = NULL,
+             host_player_level = NULL,
+             in_game_day = NULL,
+             save_game_version = NULL,
+             engine_version = NULL,
+             selection_policy = COALESCE($2, selection_policy),
+             completed_at = NULL
+         WHERE id = $1",
+    )
+    .bind(import_version_id)
+    .bind(selection_policy.map(CharacterSelectionPolicy::as_str))
+    .execute(&mut *tx)
+    .await
+    .with_context(|| "failed to reset import version for reprocessing")?;
+
+    enqueue_job(
+        &mut *tx,
+        JobKind::PostImport,
+        import_version_id,
+        state.settings.job_max_attempts,
+    )
+    .await?;
+
+    tx.commit()
+        .await
+        .with_context(|| "failed to commit reprocess transaction")?;
+    state.job_wakeup.notify_waiters();
+
+    applied_transition(state, import_version_id).await
+}
+
+async fn applied_transition(
+    state: &AppState,
+    import_version_id: Uuid,
+) -> anyhow::Result<ImportVersionTransition> {
+    let progress = fetch_import_progress(state, import_version_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("import version disappeared after update"))?;
+    Ok(ImportVersionTransition::Applied(progress))
+}
+
 async fn fetch_import_progress(
     state: &AppState,
     import_version_id: Uuid,
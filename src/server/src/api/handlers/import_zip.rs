@@ -1,12 +1,20 @@
 use crate::AppState;
+use crate::api::handlers::worlds::{derive_world_key, resolve_import_world};
+use crate::jobs::{JobKind, enqueue_job};
+use crate::save::archive::{
+    ArchiveEntry, ArchiveFormat, ArchiveLimitError, SkippedArchiveEntry, detect_world_root,
+    is_supported_world_file, parse_archive_entries, strip_root_prefix,
+};
 use crate::save::detect::detect_save_variant;
+use crate::save::level_meta::{LevelMeta, decode_level_meta};
 use crate::save::normalize::{
-    self, ExtractedAssignment, ExtractedPal, ExtractedPlayer, NormalizedPlannerSummary,
+    self, CharacterSelectionPolicy, ExtractedAssignment, ExtractedBase, ExtractedGuild,
+    ExtractedGuildMember, ExtractedPal, ExtractedPlayer, NormalizedPlannerSummary,
+    SELECTION_PLAYER_SAVE_FILE,
 };
 use crate::save::parse::inspect_gvas;
-use crate::save::zip::{
-    detect_world_root, is_supported_world_file, parse_zip_entries, strip_root_prefix,
-};
+use crate::save::player_save::{PlayerContainers, decode_player_containers};
+use crate::save::rules::pal_work_levels;
 use crate::storage::fs;
 use anyhow::Context;
 use axum::Json;
@@ -18,17 +26,23 @@ use serde_json::{Value, json};
 use sha2::{Digest, Sha256};
 use sqlx::Row;
 use std::collections::BTreeMap;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
-use tracing::{error, warn};
+use tracing::warn;
 use uuid::Uuid;
 use xxhash_rust::xxh64::xxh64;
 
 #[derive(Serialize)]
 pub struct ImportZipResponse {
     pub import_version_id: Uuid,
+    pub world_id: Uuid,
     pub world_root_path: String,
+    pub archive_format: ArchiveFormat,
     pub persisted_file_count: usize,
     pub supported_file_count: usize,
+    pub skipped_entries: Vec<SkippedArchiveEntry>,
     pub normalized_summary: NormalizedPlannerSummary,
 }
 
@@ -38,20 +52,82 @@ struct ErrorResponse {
 }
 
 #[derive(Debug)]
-struct ApiError {
-    status: StatusCode,
-    message: String,
+pub(crate) struct ApiError {
+    pub(crate) status: StatusCode,
+    pub(crate) message: String,
+}
+
+/// Returned by post-import processing once the import version has left the
+/// `processing` state, which only happens when it was cancelled.
+#[derive(Debug)]
+pub(crate) struct ImportCancelled;
+
+impl std::fmt::Display for ImportCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "import processing was cancelled")
+    }
+}
+
+impl std::error::Error for ImportCancelled {}
+
+/// One import attempt from any source, handed to [`persist_import`].
+pub(crate) struct ImportSource {
+    pub source_kind: &'static str,
+    pub source_path: Option<String>,
+    pub source_file_name: String,
+    pub archive_format: ArchiveFormat,
+    pub archive_bytes: Vec<u8>,
+    pub entries: Vec<ArchiveEntry>,
+    pub skipped: Vec<SkippedArchiveEntry>,
 }
 
 impl ApiError {
-    fn bad_request(message: impl Into<String>) -> Self {
+    pub(crate) fn bad_request(message: impl Into<String>) -> Self {
         Self {
             status: StatusCode::BAD_REQUEST,
             message: message.into(),
         }
     }
 
-    fn internal(message: impl Into<String>) -> Self {
+    pub(crate) fn payload_too_large(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::PAYLOAD_TOO_LARGE,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn from_archive_limit(error: &ArchiveLimitError) -> Self {
+        match error {
+            ArchiveLimitError::TooManyEntries { entry_count, limit } => {
+                Self::payload_too_large(format!(
+                    "archive contains too many entries ({} entries > {} entries)",
+                    entry_count, limit
+                ))
+            }
+            ArchiveLimitError::EntryTooLarge {
+                path,
+                byte_size,
+                limit,
+            } => Self::payload_too_large(format!(
+                "archive entry {} is too large when uncompressed ({} bytes > {} bytes)",
+                path, byte_size, limit
+            )),
+            ArchiveLimitError::TotalTooLarge { byte_size, limit } => {
+                Self::payload_too_large(format!(
+                    "archive total uncompressed size is too large ({} bytes > {} bytes)",
+                    byte_size, limit
+                ))
+            }
+            ArchiveLimitError::CompressionRatioExceeded { path, ratio, limit } => {
+                Self::payload_too_large(format!(
+                    "archive entry {} exceeds the maximum compression ratio ({}:1 > {}:1)",
+                    path, ratio, limit
+                ))
+            }
+        }
+    }
+
+    pub(crate) fn internal(message: impl Into<String>) -> Self {
         Self {
             status: StatusCode::INTERNAL_SERVER_ERROR,
             message: message.into(),
@@ -88,28 +164,22 @@ async fn run_import_zip(
 
         if uploaded_name.is_some() {
             return Err(ApiError::bad_request(
-                "multipart request must contain exactly one ZIP file field",
+                "multipart request must contain exactly one archive file field",
             ));
         }
 
         let file_name = file_name.to_string();
-        if !file_name.to_ascii_lowercase().ends_with(".zip") {
-            return Err(ApiError::bad_request(
-                "uploaded file must have .zip extension",
-            ));
-        }
-
         let bytes = field
             .bytes()
             .await
             .map_err(|error| {
-                ApiError::bad_request(format!("failed to read uploaded ZIP bytes: {}", error))
+                ApiError::bad_request(format!("failed to read uploaded archive bytes: {}", error))
             })?
             .to_vec();
 
         if bytes.len() > state.settings.max_import_zip_bytes {
             return Err(ApiError::bad_request(format!(
-                "uploaded ZIP is too large ({} bytes > {} bytes)",
+                "uploaded archive is too large ({} bytes > {} bytes)",
                 bytes.len(),
                 state.settings.max_import_zip_bytes
             )));
@@ -119,13 +189,58 @@ async fn run_import_zip(
         uploaded_bytes = Some(bytes);
     }
 
-    let source_file_name = uploaded_name
-        .ok_or_else(|| ApiError::bad_request("multipart request did not include a ZIP file"))?;
-    let zip_bytes = uploaded_bytes
-        .ok_or_else(|| ApiError::bad_request("multipart request did not include ZIP bytes"))?;
+    let source_file_name = uploaded_name.ok_or_else(|| {
+        ApiError::bad_request("multipart request did not include an archive file")
+    })?;
+    let archive_bytes = uploaded_bytes
+        .ok_or_else(|| ApiError::bad_request("multipart request did not include archive bytes"))?;
+
+    let extraction = parse_archive_entries(&archive_bytes, &state.settings.archive_limits)
+        .map_err(|error| match error.downcast_ref::<ArchiveLimitError>() {
+            Some(limit_error) => ApiError::from_archive_limit(limit_error),
+            None => ApiError::bad_request(format!("invalid archive content: {}", error)),
+        })?;
+
+    persist_import(
+        state,
+        ImportSource {
+            source_kind: "upload",
+            source_path: None,
+            source_file_name,
+            archive_format: extraction.format,
+            archive_bytes,
+            entries: extraction.entries,
+            skipped: extraction.skipped,
+        },
+    )
+    .await
+}
+
+/// Runs the shared artifact, manifest, and post-import pipeline for an
+/// extracted import regardless of where its files came from.
+pub(crate) async fn persist_import(
+    state: &AppState,
+    source: ImportSource,
+) -> Result<ImportZipResponse, ApiError> {
+    let ImportSource {
+        source_kind,
+        source_path,
+        source_file_name,
+        archive_format,
+        archive_bytes,
+        entries,
+        skipped: skipped_entries,
+    } = source;
+    for skipped in &skipped_entries {
+        warn!(
+            source_file_name = %source_file_name,
+            archive_format = archive_format.as_str(),
+            path = %skipped.path,
+            reason = %skipped.reason,
+            "skipped archive entry"
+        );
+    }
 
-    let entries = parse_zip_entries(&zip_bytes)
-        .map_err(|error| ApiError::bad_request(format!("invalid ZIP content: {}", error)))?;
     let world_root_path = detect_world_root(&entries)
         .map_err(|error| ApiError::bad_request(format!("invalid world root: {}", error)))?;
 
@@ -141,7 +256,7 @@ async fn run_import_zip(
                 .is_some()
             {
                 return Err(ApiError::bad_request(format!(
-                    "ZIP contains duplicate file after root stripping: {}",
+                    "archive contains duplicate file after root stripping: {}",
                     relative_path
                 )));
             }
@@ -158,7 +273,7 @@ async fn run_import_zip(
         .any(|path| path.starts_with("Player/"))
     {
         return Err(ApiError::bad_request(
-            "ZIP uses Player/ directory; only Players/ is supported",
+            "archive uses Player/ directory; only Players/ is supported",
         ));
     }
     if !rooted_entries.contains_key("Level.sav") {
@@ -176,15 +291,19 @@ async fn run_import_zip(
     }
 
     let import_version_id = Uuid::new_v4();
-    let source_zip_id = Uuid::new_v4();
-    let source_zip_storage_key = format!("storage/imports/{}/source.zip", import_version_id);
-    let (source_sha256, source_xxh64, source_byte_size) = compute_hashes(&zip_bytes)
-        .map_err(|error| ApiError::internal(format!("failed to hash uploaded ZIP: {}", error)))?;
+    let source_archive_id = Uuid::new_v4();
+    let source_archive_storage_key = format!(
+        "storage/imports/{}/source.{}",
+        import_version_id,
+        archive_format.file_extension()
+    );
+    let (source_sha256, source_xxh64, source_byte_size) = compute_hashes(&archive_bytes)
+        .map_err(|error| ApiError::internal(format!("failed to hash source archive: {}", error)))?;
 
     fs::write_bytes(
         &state.settings.artifact_storage_root,
-        &source_zip_storage_key,
-        &zip_bytes,
+        &source_archive_storage_key,
+        &archive_bytes,
     )
     .await
     .map_err(|error| ApiError::internal(error.to_string()))?;
@@ -193,36 +312,48 @@ async fn run_import_zip(
         ApiError::internal(format!("failed to start database transaction: {}", error))
     })?;
 
+    let world = resolve_import_world(
+        &mut tx,
+        &derive_world_key(&world_root_path, &source_file_name),
+        &source_sha256,
+    )
+    .await
+    .map_err(|error| ApiError::internal(format!("failed to attach world: {}", error)))?;
+
     sqlx::query(
         "INSERT INTO save_import_versions (
-            id, source_file_name, world_root_path, status, progress_phase, progress_pct, progress_message
-         ) VALUES ($1, $2, $3, 'processing', 'persisting_artifacts', 10, 'Persisting ZIP and extracted files')",
+            id, source_file_name, world_root_path, source_kind, source_path, world_id, source_export_version_id, status, progress_phase, progress_pct, progress_message
+         ) VALUES ($1, $2, $3, $4, $5, $6, $7, 'processing', 'persisting_artifacts', 10, 'Persisting source archive and extracted files')",
     )
     .bind(import_version_id)
     .bind(&source_file_name)
     .bind(&world_root_path)
+    .bind(source_kind)
+    .bind(&source_path)
+    .bind(world.world_id)
+    .bind(world.source_export_version_id)
     .execute(&mut *tx)
     .await
     .map_err(|error| ApiError::internal(format!("failed to insert save_import_versions row: {}", error)))?;
 
     sqlx::query(
-        "INSERT INTO save_zip_artifacts (id, import_version_id, export_version_id, kind, storage_key, file_name, byte_size, sha256, xxh64, immutable, retention_policy)
-         VALUES ($1, $2, NULL, 'import_source_zip', $3, $4, $5, $6, $7, TRUE, 'forever')",
+        "INSERT INTO save_zip_artifacts (id, import_version_id, export_version_id, kind, archive_format, storage_key, file_name, byte_size, sha256, xxh64, immutable, retention_policy)
+         VALUES ($1, $2, NULL, 'import_source_archive', $3, $4, $5, $6, $7, $8, TRUE, 'forever')",
     )
-    .bind(source_zip_id)
+    .bind(source_archive_id)
     .bind(import_version_id)
-    .bind(&source_zip_storage_key)
+    .bind(archive_format.as_str())
+    .bind(&source_archive_storage_key)
     .bind(&source_file_name)
     .bind(source_byte_size)
     .bind(&source_sha256)
     .bind(&source_xxh64)
     .execute(&mut *tx)
     .await
-    .map_err(|error| ApiError::internal(format!("failed to insert source ZIP artifact row: {}", error)))?;
+    .map_err(|error| ApiError::internal(format!("failed to insert source archive artifact row: {}", error)))?;
 
     let mut persisted_file_count = 0usize;
     let mut supported_file_count = 0usize;
-    let mut normalized_player_seed_rows: Vec<(String, Uuid, String)> = Vec::new();
 
     for (relative_path, bytes) in rooted_entries {
         let file_id = Uuid::new_v4();
@@ -266,54 +397,13 @@ async fn run_import_zip(
         if is_supported {
             supported_file_count += 1;
         }
-
-        if let Some(player_uid) = extract_player_uid_from_path(&relative_path) {
-            normalized_player_seed_rows.push((player_uid, file_id, relative_path.clone()));
-        }
     }
 
     let normalized_summary;
 
-    for (player_uid, raw_file_ref, raw_entity_path) in normalized_player_seed_rows {
-        let planner_player_id = upsert_planner_player(
-            &mut tx,
-            import_version_id,
-            &ExtractedPlayer {
-                player_uid,
-                player_instance_id: None,
-                player_name: None,
-                guild_id: None,
-                level: None,
-                raw_file_ref,
-                raw_entity_path: raw_entity_path.clone(),
-            },
-        )
-        .await
-        .map_err(|error| {
-            ApiError::internal(format!(
-                "failed to seed planner_players row from Players/*.sav path: {}",
-                error
-            ))
-        })?;
-
-        sqlx::query(
-            "INSERT INTO planner_player_links (id, planner_player_id, save_file_id, raw_entity_path)
-             VALUES ($1, $2, $3, $4)
-             ON CONFLICT (planner_player_id, save_file_id, raw_entity_path) DO NOTHING",
-        )
-        .bind(Uuid::new_v4())
-        .bind(planner_player_id)
-        .bind(raw_file_ref)
-        .bind(&raw_entity_path)
-        .execute(&mut *tx)
+    seed_planner_players_from_files(&mut tx, import_version_id)
         .await
-        .map_err(|error| {
-            ApiError::internal(format!(
-                "failed to seed planner_player_links row from Players/*.sav path: {}",
-                error
-            ))
-        })?;
-    }
+        .map_err(|error| ApiError::internal(format!("{:#}", error)))?;
 
     let summary_row = sqlx::query(
         "SELECT
@@ -356,41 +446,43 @@ async fn run_import_zip(
         ))
     })?;
 
+    enqueue_job(
+        &mut *tx,
+        JobKind::PostImport,
+        import_version_id,
+        state.settings.job_max_attempts,
+    )
+    .await
+    .map_err(|error| ApiError::internal(format!("{:#}", error)))?;
+
     tx.commit().await.map_err(|error| {
         ApiError::internal(format!("failed to commit import transaction: {}", error))
     })?;
 
-    spawn_post_import_processing(state.clone(), import_version_id);
+    state.job_wakeup.notify_waiters();
 
     Ok(ImportZipResponse {
         import_version_id,
+        world_id: world.world_id,
         world_root_path,
+        archive_format,
         persisted_file_count,
         supported_file_count,
+        skipped_entries,
         normalized_summary,
     })
 }
 
-fn spawn_post_import_processing(state: AppState, import_version_id: Uuid) {
-    tokio::spawn(async move {
-        if let Err(error) = run_post_import_processing(state.clone(), import_version_id).await {
-            error!(
-                import_version_id = %import_version_id,
-                "post-import processing failed: {error:#}"
-            );
-            if let Err(mark_error) =
-                mark_import_failed(&state, import_version_id, &error.to_string()).await
-            {
-                error!(
-                    import_version_id = %import_version_id,
-                    "failed to mark import as failed after background error: {mark_error:#}"
-                );
-            }
-        }
-    });
+/// Sets its flag when dropped.
+struct StopOnDrop(Arc<AtomicBool>);
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
 }
 
-async fn run_post_import_processing(
+pub(crate) async fn run_post_import_processing(
     state: AppState,
     import_version_id: Uuid,
 ) -> anyhow::Result<()> {
@@ -418,9 +510,14 @@ async fn run_post_import_processing(
     .with_context(|| format!("failed to load save_files for import {}", import_version_id))?;
 
     let mut level_sav_for_normalize: Option<(Uuid, Vec<u8>)> = None;
+    let mut level_meta_bytes: Option<Vec<u8>> = None;
+    let mut player_save_bytes: Vec<(String, Vec<u8>)> = Vec::new();
     let mut parse_metrics_json: Option<Value> = None;
+    let mut level_in_game_day: Option<i32> = None;
 
     for row in save_files {
+        ensure_import_processing(&state, import_version_id).await?;
+
         let save_file_id: Uuid = row.get("id");
         let relative_path: String = row.get("relative_path");
         let storage_key: String = row.get("storage_key");
@@ -501,6 +598,44 @@ async fn run_post_import_processing(
 
         if relative_path == "Level.sav" {
             level_sav_for_normalize = Some((save_file_id, bytes));
+        } else if relative_path == "LevelMeta.sav" {
+            level_meta_bytes = Some(bytes);
+        } else if extract_player_uid_from_path(&relative_path).is_some() {
+            player_save_bytes.push((relative_path, bytes));
+        }
+    }
+
+    if let Some(bytes) = level_meta_bytes {
+        let decoded = tokio::time::timeout(
+            Duration::from_secs(VARIANT_INSPECT_TIMEOUT_SECS),
+            tokio::task::spawn_blocking(move || decode_level_meta(&bytes)),
+        )
+        .await;
+        match decoded {
+            Ok(Ok(Ok(level_meta))) => {
+                persist_level_meta(&state, import_version_id, &level_meta).await?;
+            }
+            Ok(Ok(Err(error))) => {
+                warn!(
+                    import_version_id = %import_version_id,
+                    "LevelMeta.sav decode skipped: {}",
+                    error
+                );
+            }
+            Ok(Err(error)) => {
+                return Err(anyhow::anyhow!(
+                    "LevelMeta.sav decode worker panicked for {}: {}",
+                    import_version_id,
+                    error
+                ));
+            }
+            Err(_) => {
+                warn!(
+                    import_version_id = %import_version_id,
+                    "LevelMeta.sav decode timed out after {}s",
+                    VARIANT_INSPECT_TIMEOUT_SECS
+                );
+            }
         }
     }
 
@@ -513,20 +648,68 @@ async fn run_post_import_processing(
     )
     .await?;
 
+    let stored_selection_policy: Option<String> =
+        sqlx::query_scalar("SELECT selection_policy FROM save_import_versions WHERE id = $1")
+            .bind(import_version_id)
+            .fetch_one(&state.pool)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to load selection policy for import {}",
+                    import_version_id
+                )
+            })?;
+    let selection_policy = stored_selection_policy
+        .as_deref()
+        .and_then(CharacterSelectionPolicy::parse)
+        .unwrap_or(state.settings.character_selection_policy);
+
     if let Some((level_file_id, level_bytes)) = level_sav_for_normalize {
         let (progress_tx, mut progress_rx) =
             tokio::sync::mpsc::unbounded_channel::<normalize::NormalizationProgress>();
+        // Aborting a blocking task does not stop it, so the worker polls this
+        // flag at every progress point and unwinds on its own. The guard sets
+        // it however this future ends, including being dropped by the job
+        // runner after its lease is lost.
+        let stop_requested = Arc::new(AtomicBool::new(false));
+        let worker_stop_requested = stop_requested.clone();
+        let _stop_on_drop = StopOnDrop(stop_requested);
         let mut normalize_worker = tokio::task::spawn_blocking(move || {
+            // Player saves only contribute container ids for pal locations, so
+            // one that fails to decode leaves its pals unlocated.
+            let player_containers: Vec<PlayerContainers> = player_save_bytes
+                .iter()
+                .filter_map(|(relative_path, bytes)| {
+                    decode_player_containers(bytes)
+                        .inspect_err(|error| {
+                            warn!(
+                                import_version_id = %import_version_id,
+                                "{} container decode skipped: {}",
+                                relative_path,
+                                error
+                            );
+                        })
+                        .ok()
+                })
+                .collect();
             normalize::extract_from_level_sav_with_progress(
                 &level_bytes,
                 level_file_id,
+                &player_containers,
+                selection_policy,
                 |progress| {
                     let _ = progress_tx.send(progress);
+                    if worker_stop_requested.load(Ordering::Relaxed) {
+                        ControlFlow::Break(())
+                    } else {
+                        ControlFlow::Continue(())
+                    }
                 },
             )
         });
         let timeout = tokio::time::sleep(Duration::from_secs(LEVEL_NORMALIZE_TIMEOUT_SECS));
         tokio::pin!(timeout);
+        let mut cancel_check = tokio::time::interval(Duration::from_secs(1));
 
         let mut last_processed_report: Option<usize> = None;
         let mut last_progress_pct: Option<i32> = None;
@@ -563,6 +746,9 @@ async fn run_post_import_processing(
                         last_reported_at = Instant::now();
                     }
                 }
+                _ = cancel_check.tick() => {
+                    ensure_import_processing(&state, import_version_id).await?;
+                }
                 joined = &mut normalize_worker => {
                     match joined {
                         Ok(Ok(extracted)) => break Some(extracted),
@@ -596,27 +782,92 @@ async fn run_post_import_processing(
 
         if let Some(normalized) = extracted {
             parse_metrics_json = Some(serde_json::to_value(&normalized.metrics)?);
+            level_in_game_day = normalized.data.in_game_day;
             persist_normalized_extract(&state, import_version_id, normalized.data).await?;
         }
     }
 
-    sqlx::query(
+    let finalized = sqlx::query(
         "UPDATE save_import_versions
          SET status = 'ready',
              progress_phase = 'complete',
              progress_pct = 100,
              progress_message = 'Import processing complete',
              parse_metrics_json = $2,
+             in_game_day = COALESCE(in_game_day, $3),
+             selection_policy = $4,
              failed_error = NULL,
              completed_at = NOW()
-         WHERE id = $1",
+         WHERE id = $1 AND status = 'processing'",
     )
     .bind(import_version_id)
     .bind(parse_metrics_json)
+    .bind(level_in_game_day)
+    .bind(selection_policy.as_str())
     .execute(&state.pool)
     .await
     .with_context(|| format!("failed to finalize import {}", import_version_id))?;
 
+    if finalized.rows_affected() == 0 {
+        return Err(ImportCancelled.into());
+    }
+
+    Ok(())
+}
+
+/// Stores the decoded `LevelMeta.sav` fields on the import and names its
+/// world after the in-game world name unless the world was renamed by hand.
+async fn persist_level_meta(
+    state: &AppState,
+    import_version_id: Uuid,
+    level_meta: &LevelMeta,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE save_import_versions
+         SET world_name = $2,
+             host_player_name = $3,
+             host_player_level = $4,
+             in_game_day = $5,
+             save_game_version = $6,
+             engine_version = $7
+         WHERE id = $1 AND status = 'processing'",
+    )
+    .bind(import_version_id)
+    .bind(&level_meta.world_name)
+    .bind(&level_meta.host_player_name)
+    .bind(level_meta.host_player_level)
+    .bind(level_meta.in_game_day)
+    .bind(level_meta.save_game_version)
+    .bind(&level_meta.engine_version)
+    .execute(&state.pool)
+    .await
+    .with_context(|| {
+        format!(
+            "failed to store LevelMeta.sav fields for {}",
+            import_version_id
+        )
+    })?;
+
+    if let Some(world_name) = level_meta
+        .world_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        sqlx::query(
+            "UPDATE worlds
+             SET display_name = $2, updated_at = NOW()
+             WHERE id = (SELECT world_id FROM save_import_versions WHERE id = $1)
+               AND display_name IS DISTINCT FROM $2
+               AND display_name = world_key",
+        )
+        .bind(import_version_id)
+        .bind(world_name)
+        .execute(&state.pool)
+        .await
+        .with_context(|| format!("failed to name world for import {}", import_version_id))?;
+    }
+
     Ok(())
 }
 
@@ -632,6 +883,18 @@ async fn persist_normalized_extract(
         )
     })?;
 
+    // Holding the row lock keeps a concurrent cancel from landing between this
+    // check and the commit.
+    let status: Option<String> =
+        sqlx::query_scalar("SELECT status FROM save_import_versions WHERE id = $1 FOR UPDATE")
+            .bind(import_version_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .with_context(|| format!("failed to lock import {}", import_version_id))?;
+    if status.as_deref() != Some("processing") {
+        return Err(ImportCancelled.into());
+    }
+
     for player in extracted.players {
         let planner_player_id = upsert_planner_player(&mut tx, import_version_id, &player)
             .await
@@ -652,9 +915,16 @@ async fn persist_normalized_extract(
     }
 
     for pal in extracted.pals {
-        let planner_pal_id = upsert_planner_pal(&mut tx, import_version_id, &pal)
-            .await
-            .with_context(|| "failed to upsert normalized pal row")?;
+        let work_suitability_ranks = pal_work_levels(
+            &state.catalog,
+            pal.species_id.as_deref(),
+            &pal.work_suitability_add_ranks,
+            pal.rank,
+        );
+        let planner_pal_id =
+            upsert_planner_pal(&mut tx, import_version_id, &pal, &work_suitability_ranks)
+                .await
+                .with_context(|| "failed to upsert normalized pal row")?;
 
         sqlx::query(
             "INSERT INTO planner_pal_links (id, planner_pal_id, save_file_id, raw_entity_path)
@@ -670,6 +940,25 @@ async fn persist_normalized_extract(
         .with_context(|| "failed to upsert normalized pal link row")?;
     }
 
+    for base in extracted.bases {
+        let planner_base_id = upsert_planner_base(&mut tx, import_version_id, &base)
+            .await
+            .with_context(|| "failed to upsert normalized base row")?;
+
+        sqlx::query(
+            "INSERT INTO planner_base_links (id, planner_base_id, save_file_id, raw_entity_path)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (planner_base_id, save_file_id, raw_entity_path) DO NOTHING",
+        )
+        .bind(Uuid::new_v4())
+        .bind(planner_base_id)
+        .bind(base.raw_file_ref)
+        .bind(&base.raw_entity_path)
+        .execute(&mut *tx)
+        .await
+        .with_context(|| "failed to upsert normalized base link row")?;
+    }
+
     for assignment in extracted.assignments {
         let planner_assignment_id =
             upsert_planner_assignment(&mut tx, import_version_id, &assignment)
@@ -690,6 +979,45 @@ async fn persist_normalized_extract(
         .with_context(|| "failed to upsert normalized assignment link row")?;
     }
 
+    for guild in extracted.guilds {
+        let planner_guild_id = upsert_planner_guild(&mut tx, import_version_id, &guild)
+            .await
+            .with_context(|| "failed to upsert normalized guild row")?;
+
+        sqlx::query(
+            "INSERT INTO planner_guild_links (id, planner_guild_id, save_file_id, raw_entity_path)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (planner_guild_id, save_file_id, raw_entity_path) DO NOTHING",
+        )
+        .bind(Uuid::new_v4())
+        .bind(planner_guild_id)
+        .bind(guild.raw_file_ref)
+        .bind(&guild.raw_entity_path)
+        .execute(&mut *tx)
+        .await
+        .with_context(|| "failed to upsert normalized guild link row")?;
+
+        for member in &guild.members {
+            let planner_guild_member_id =
+                upsert_planner_guild_member(&mut tx, import_version_id, &guild, member)
+                    .await
+                    .with_context(|| "failed to upsert normalized guild member row")?;
+
+            sqlx::query(
+                "INSERT INTO planner_guild_member_links (id, planner_guild_member_id, save_file_id, raw_entity_path)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (planner_guild_member_id, save_file_id, raw_entity_path) DO NOTHING",
+            )
+            .bind(Uuid::new_v4())
+            .bind(planner_guild_member_id)
+            .bind(guild.raw_file_ref)
+            .bind(&guild.raw_entity_path)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| "failed to upsert normalized guild member link row")?;
+        }
+    }
+
     tx.commit().await.with_context(|| {
         format!(
             "failed to commit normalized extract for {}",
@@ -700,7 +1028,7 @@ async fn persist_normalized_extract(
     Ok(())
 }
 
-async fn update_import_progress(
+pub(crate) async fn update_import_progress(
     state: &AppState,
     import_version_id: Uuid,
     phase: &str,
@@ -708,14 +1036,13 @@ async fn update_import_progress(
     message: &str,
 ) -> anyhow::Result<()> {
     let clamped_pct = progress_pct.clamp(0, 100);
-    sqlx::query(
+    let updated = sqlx::query(
         "UPDATE save_import_versions
-         SET status = 'processing',
-             progress_phase = $2,
+         SET progress_phase = $2,
              progress_pct = $3,
              progress_message = $4,
              failed_error = NULL
-         WHERE id = $1",
+         WHERE id = $1 AND status = 'processing'",
     )
     .bind(import_version_id)
     .bind(phase)
@@ -724,10 +1051,26 @@ async fn update_import_progress(
     .execute(&state.pool)
     .await
     .with_context(|| format!("failed to update import progress for {}", import_version_id))?;
+    if updated.rows_affected() == 0 {
+        return Err(ImportCancelled.into());
+    }
     Ok(())
 }
 
-async fn mark_import_failed(
+async fn ensure_import_processing(state: &AppState, import_version_id: Uuid) -> anyhow::Result<()> {
+    let status: Option<String> =
+        sqlx::query_scalar("SELECT status FROM save_import_versions WHERE id = $1")
+            .bind(import_version_id)
+            .fetch_optional(&state.pool)
+            .await
+            .with_context(|| format!("failed to load import status for {}", import_version_id))?;
+    if status.as_deref() != Some("processing") {
+        return Err(ImportCancelled.into());
+    }
+    Ok(())
+}
+
+pub(crate) async fn mark_import_failed(
     state: &AppState,
     import_version_id: Uuid,
     error_text: &str,
@@ -740,7 +1083,7 @@ async fn mark_import_failed(
              progress_message = 'Import processing failed',
              failed_error = $2,
              completed_at = NOW()
-         WHERE id = $1",
+         WHERE id = $1 AND status = 'processing'",
     )
     .bind(import_version_id)
     .bind(error_text)
@@ -761,6 +1104,64 @@ fn compute_hashes(bytes: &[u8]) -> Result<(String, String, i64), std::num::TryFr
     Ok((sha256, xxh64_hex, byte_size))
 }
 
+/// Seeds one `planner_players` row per `Players/<uid>.sav` file so players
+/// exist even when Level.sav normalization is skipped.
+pub(crate) async fn seed_planner_players_from_files(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    import_version_id: Uuid,
+) -> anyhow::Result<()> {
+    let save_files = sqlx::query(
+        "SELECT id, relative_path
+         FROM save_files
+         WHERE import_version_id = $1
+         ORDER BY relative_path ASC",
+    )
+    .bind(import_version_id)
+    .fetch_all(&mut **tx)
+    .await
+    .with_context(|| format!("failed to load save_files for import {}", import_version_id))?;
+
+    for row in save_files {
+        let raw_file_ref: Uuid = row.get("id");
+        let raw_entity_path: String = row.get("relative_path");
+        let Some(player_uid) = extract_player_uid_from_path(&raw_entity_path) else {
+            continue;
+        };
+
+        let planner_player_id = upsert_planner_player(
+            tx,
+            import_version_id,
+            &ExtractedPlayer {
+                player_uid,
+                player_instance_id: None,
+                player_name: None,
+                guild_id: None,
+                level: None,
+                selection_reason: SELECTION_PLAYER_SAVE_FILE.to_string(),
+                raw_file_ref,
+                raw_entity_path: raw_entity_path.clone(),
+            },
+        )
+        .await
+        .with_context(|| "failed to seed planner_players row from Players/*.sav path")?;
+
+        sqlx::query(
+            "INSERT INTO planner_player_links (id, planner_player_id, save_file_id, raw_entity_path)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (planner_player_id, save_file_id, raw_entity_path) DO NOTHING",
+        )
+        .bind(Uuid::new_v4())
+        .bind(planner_player_id)
+        .bind(raw_file_ref)
+        .bind(&raw_entity_path)
+        .execute(&mut **tx)
+        .await
+        .with_context(|| "failed to seed planner_player_links row from Players/*.sav path")?;
+    }
+
+    Ok(())
+}
+
 fn extract_player_uid_from_path(relative_path: &str) -> Option<String> {
     let file_name = relative_path
         .strip_prefix("Players/")?
@@ -779,13 +1180,14 @@ async fn upsert_planner_player(
     player: &ExtractedPlayer,
 ) -> Result<Uuid, sqlx::Error> {
     let row = sqlx::query(
-        "INSERT INTO planner_players (id, import_version_id, player_uid, player_instance_id, player_name, guild_id, level, raw_file_ref, raw_entity_path)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "INSERT INTO planner_players (id, import_version_id, player_uid, player_instance_id, player_name, guild_id, level, selection_reason, raw_file_ref, raw_entity_path)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
          ON CONFLICT (import_version_id, player_uid) DO UPDATE SET
             player_instance_id = COALESCE(EXCLUDED.player_instance_id, planner_players.player_instance_id),
             player_name = COALESCE(EXCLUDED.player_name, planner_players.player_name),
             guild_id = COALESCE(EXCLUDED.guild_id, planner_players.guild_id),
             level = COALESCE(EXCLUDED.level, planner_players.level),
+            selection_reason = EXCLUDED.selection_reason,
             raw_file_ref = EXCLUDED.raw_file_ref,
             raw_entity_path = EXCLUDED.raw_entity_path
          RETURNING id",
@@ -797,6 +1199,7 @@ async fn upsert_planner_player(
     .bind(&player.player_name)
     .bind(&player.guild_id)
     .bind(player.level)
+    .bind(&player.selection_reason)
     .bind(player.raw_file_ref)
     .bind(&player.raw_entity_path)
     .fetch_one(&mut **tx)
@@ -808,14 +1211,19 @@ async fn upsert_planner_pal(
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     import_version_id: Uuid,
     pal: &ExtractedPal,
+    work_suitability_ranks: &BTreeMap<String, i64>,
 ) -> Result<Uuid, sqlx::Error> {
     let row = sqlx::query(
         "INSERT INTO planner_pals (
-            id, import_version_id, pal_instance_id, owner_player_uid, species_id, nickname, gender, level, exp,
-            passive_skill_ids, mastered_waza_ids, equip_waza_ids, work_suitability_ranks, raw_file_ref, raw_entity_path
+            id, import_version_id, pal_instance_id, owner_player_uid, species_id, nickname, gender, level, exp, rank,
+            passive_skill_ids, mastered_waza_ids, equip_waza_ids, work_suitability_ranks, location_kind, container_id, slot_index,
+            selection_reason, raw_file_ref, raw_entity_path, is_lucky, rank_craftspeed,
+            rank_hp, rank_attack, rank_defense, talent_hp, talent_melee, talent_shot, talent_defense,
+            work_suitability_add_ranks
          ) VALUES (
-            $1, $2, $3, $4, $5, $6, $7, $8, $9,
-            $10, $11, $12, $13, $14, $15
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+            $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22,
+            $23, $24, $25, $26, $27, $28, $29, $30
          )
          ON CONFLICT (import_version_id, pal_instance_id) DO UPDATE SET
             owner_player_uid = COALESCE(EXCLUDED.owner_player_uid, planner_pals.owner_player_uid),
@@ -824,12 +1232,27 @@ async fn upsert_planner_pal(
             gender = COALESCE(EXCLUDED.gender, planner_pals.gender),
             level = COALESCE(EXCLUDED.level, planner_pals.level),
             exp = COALESCE(EXCLUDED.exp, planner_pals.exp),
+            rank = COALESCE(EXCLUDED.rank, planner_pals.rank),
             passive_skill_ids = EXCLUDED.passive_skill_ids,
             mastered_waza_ids = EXCLUDED.mastered_waza_ids,
             equip_waza_ids = EXCLUDED.equip_waza_ids,
             work_suitability_ranks = EXCLUDED.work_suitability_ranks,
+            work_suitability_add_ranks = EXCLUDED.work_suitability_add_ranks,
+            location_kind = EXCLUDED.location_kind,
+            container_id = EXCLUDED.container_id,
+            slot_index = EXCLUDED.slot_index,
+            selection_reason = EXCLUDED.selection_reason,
             raw_file_ref = EXCLUDED.raw_file_ref,
-            raw_entity_path = EXCLUDED.raw_entity_path
+            raw_entity_path = EXCLUDED.raw_entity_path,
+            is_lucky = EXCLUDED.is_lucky,
+            rank_craftspeed = COALESCE(EXCLUDED.rank_craftspeed, planner_pals.rank_craftspeed),
+            rank_hp = COALESCE(EXCLUDED.rank_hp, planner_pals.rank_hp),
+            rank_attack = COALESCE(EXCLUDED.rank_attack, planner_pals.rank_attack),
+            rank_defense = COALESCE(EXCLUDED.rank_defense, planner_pals.rank_defense),
+            talent_hp = COALESCE(EXCLUDED.talent_hp, planner_pals.talent_hp),
+            talent_melee = COALESCE(EXCLUDED.talent_melee, planner_pals.talent_melee),
+            talent_shot = COALESCE(EXCLUDED.talent_shot, planner_pals.talent_shot),
+            talent_defense = COALESCE(EXCLUDED.talent_defense, planner_pals.talent_defense)
          RETURNING id",
     )
     .bind(Uuid::new_v4())
@@ -841,12 +1264,27 @@ async fn upsert_planner_pal(
     .bind(&pal.gender)
     .bind(pal.level)
     .bind(pal.exp)
+    .bind(pal.rank)
     .bind(pal.passive_skill_ids_json())
     .bind(pal.mastered_waza_ids_json())
     .bind(pal.equip_waza_ids_json())
-    .bind(json!({}))
+    .bind(json!(work_suitability_ranks))
+    .bind(&pal.location_kind)
+    .bind(&pal.container_id)
+    .bind(pal.slot_index)
+    .bind(&pal.selection_reason)
     .bind(pal.raw_file_ref)
     .bind(&pal.raw_entity_path)
+    .bind(pal.is_lucky)
+    .bind(pal.rank_craftspeed)
+    .bind(pal.rank_hp)
+    .bind(pal.rank_attack)
+    .bind(pal.rank_defense)
+    .bind(pal.talent_hp)
+    .bind(pal.talent_melee)
+    .bind(pal.talent_shot)
+    .bind(pal.talent_defense)
+    .bind(json!(pal.work_suitability_add_ranks))
     .fetch_one(&mut **tx)
     .await?;
     Ok(row.get("id"))
@@ -882,3 +1320,124 @@ async fn upsert_planner_assignment(
     .await?;
     Ok(row.get("id"))
 }
+
+async fn upsert_planner_base(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    import_version_id: Uuid,
+    base: &ExtractedBase,
+) -> Result<Uuid, sqlx::Error> {
+    let row = sqlx::query(
+        "INSERT INTO planner_bases (
+            id, import_version_id, base_id, base_name, state, area_range, group_id, worker_container_id, worker_order_type,
+            location_x, location_y, location_z, transform, worker_count, raw_file_ref, raw_entity_path
+         ) VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16
+         )
+         ON CONFLICT (import_version_id, base_id) DO UPDATE SET
+            base_name = EXCLUDED.base_name,
+            state = EXCLUDED.state,
+            area_range = EXCLUDED.area_range,
+            group_id = EXCLUDED.group_id,
+            worker_container_id = EXCLUDED.worker_container_id,
+            worker_order_type = EXCLUDED.worker_order_type,
+            location_x = EXCLUDED.location_x,
+            location_y = EXCLUDED.location_y,
+            location_z = EXCLUDED.location_z,
+            transform = EXCLUDED.transform,
+            worker_count = EXCLUDED.worker_count,
+            raw_file_ref = EXCLUDED.raw_file_ref,
+            raw_entity_path = EXCLUDED.raw_entity_path
+         RETURNING id",
+    )
+    .bind(Uuid::new_v4())
+    .bind(import_version_id)
+    .bind(&base.base_id)
+    .bind(&base.base_name)
+    .bind(base.state)
+    .bind(base.area_range)
+    .bind(&base.group_id)
+    .bind(&base.worker_container_id)
+    .bind(base.worker_order_type)
+    .bind(base.location_x)
+    .bind(base.location_y)
+    .bind(base.location_z)
+    .bind(&base.transform)
+    .bind(base.worker_count)
+    .bind(base.raw_file_ref)
+    .bind(&base.raw_entity_path)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(row.get("id"))
+}
+
+async fn upsert_planner_guild(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    import_version_id: Uuid,
+    guild: &ExtractedGuild,
+) -> Result<Uuid, sqlx::Error> {
+    let row = sqlx::query(
+        "INSERT INTO planner_guilds (
+            id, import_version_id, group_id, group_type, guild_name, admin_player_uid, base_camp_level, base_ids, raw_file_ref, raw_entity_path
+         ) VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10
+         )
+         ON CONFLICT (import_version_id, group_id) DO UPDATE SET
+            group_type = EXCLUDED.group_type,
+            guild_name = EXCLUDED.guild_name,
+            admin_player_uid = EXCLUDED.admin_player_uid,
+            base_camp_level = EXCLUDED.base_camp_level,
+            base_ids = EXCLUDED.base_ids,
+            raw_file_ref = EXCLUDED.raw_file_ref,
+            raw_entity_path = EXCLUDED.raw_entity_path
+         RETURNING id",
+    )
+    .bind(Uuid::new_v4())
+    .bind(import_version_id)
+    .bind(&guild.group_id)
+    .bind(&guild.group_type)
+    .bind(&guild.guild_name)
+    .bind(&guild.admin_player_uid)
+    .bind(guild.base_camp_level)
+    .bind(guild.base_ids_json())
+    .bind(guild.raw_file_ref)
+    .bind(&guild.raw_entity_path)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(row.get("id"))
+}
+
+async fn upsert_planner_guild_member(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    import_version_id: Uuid,
+    guild: &ExtractedGuild,
+    member: &ExtractedGuildMember,
+) -> Result<Uuid, sqlx::Error> {
+    let row = sqlx::query(
+        "INSERT INTO planner_guild_members (
+            id, import_version_id, group_id, player_uid, player_name, last_online_ticks, offline_seconds, is_admin, raw_file_ref, raw_entity_path
+         ) VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10
+         )
+         ON CONFLICT (import_version_id, group_id, player_uid) DO UPDATE SET
+            player_name = EXCLUDED.player_name,
+            last_online_ticks = EXCLUDED.last_online_ticks,
+            offline_seconds = EXCLUDED.offline_seconds,
+            is_admin = EXCLUDED.is_admin,
+            raw_file_ref = EXCLUDED.raw_file_ref,
+            raw_entity_path = EXCLUDED.raw_entity_path
+         RETURNING id",
+    )
+    .bind(Uuid::new_v4())
+    .bind(import_version_id)
+    .bind(&guild.group_id)
+    .bind(&member.player_uid)
+    .bind(&member.player_name)
+    .bind(member.last_online_ticks)
+    .bind(member.offline_seconds)
+    .bind(member.is_admin)
+    .bind(guild.raw_file_ref)
+    .bind(&guild.raw_entity_path)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(row.get("id"))
+}
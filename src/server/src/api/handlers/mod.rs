@@ -1,3 +1,13 @@
+pub mod breeding;
+pub mod catalog;
 pub mod health;
+pub mod import_diff;
+pub mod import_directory;
 pub mod import_versions;
 pub mod import_zip;
+pub mod patches;
+pub mod planner;
+pub mod production;
+pub mod stats;
+pub mod world_option;
+pub mod worlds;
@@ -0,0 +1,180 @@
+use crate::AppState;
+use crate::api::handlers::world_option::load_world_settings;
+use crate::save::patch::{
+    OperationValidation, PalState, PatchOperation, PatchTargets, PlayerState, validate_operations,
+};
+use crate::save::world_option::default_world_settings;
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::Row;
+use sqlx::postgres::PgRow;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct ValidatePatchRequest {
+    pub operations: Vec<PatchOperation>,
+}
+
+#[derive(Serialize)]
+pub struct ValidatePatchResponse {
+    pub import_version_id: Uuid,
+    /// True when every operation validated.
+    pub valid: bool,
+    pub operations: Vec<OperationValidation>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+enum PatchError {
+    ImportNotFound,
+    Internal(anyhow::Error),
+}
+
+impl From<sqlx::Error> for PatchError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Internal(error.into())
+    }
+}
+
+impl From<anyhow::Error> for PatchError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::Internal(error)
+    }
+}
+
+/// Validates patch operations against an import's entities and the game
+/// catalog without storing them.
+pub async fn validate_patch_operations(
+    State(state): State<AppState>,
+    Path(import_version_id): Path<Uuid>,
+    Json(request): Json<ValidatePatchRequest>,
+) -> impl IntoResponse {
+    match run_validate_patch_operations(&state, import_version_id, &request.operations).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(PatchError::ImportNotFound) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "import version not found".to_string(),
+            }),
+        )
+            .into_response(),
+        Err(PatchError::Internal(error)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to validate patch operations: {:#}", error),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+async fn run_validate_patch_operations(
+    state: &AppState,
+    import_version_id: Uuid,
+    operations: &[PatchOperation],
+) -> Result<ValidatePatchResponse, PatchError> {
+    let exists = sqlx::query("SELECT id FROM save_import_versions WHERE id = $1")
+        .bind(import_version_id)
+        .fetch_optional(&state.pool)
+        .await?;
+    if exists.is_none() {
+        return Err(PatchError::ImportNotFound);
+    }
+
+    let mut targets = load_patch_targets(state, import_version_id).await?;
+    let operations = validate_operations(&state.catalog, operations, &mut targets);
+    Ok(ValidatePatchResponse {
+        import_version_id,
+        valid: operations.iter().all(|operation| operation.validated),
+        operations,
+    })
+}
+
+async fn load_patch_targets(
+    state: &AppState,
+    import_version_id: Uuid,
+) -> Result<PatchTargets, PatchError> {
+    let pals = sqlx::query(
+        "SELECT pal_instance_id, species_id, nickname, gender, level, exp, rank,
+                rank_hp, rank_attack, rank_defense, rank_craftspeed,
+                talent_hp, talent_melee, talent_shot, talent_defense,
+                passive_skill_ids, mastered_waza_ids, equip_waza_ids, work_suitability_ranks,
+                work_suitability_add_ranks
+         FROM planner_pals
+         WHERE import_version_id = $1",
+    )
+    .bind(import_version_id)
+    .fetch_all(&state.pool)
+    .await?;
+    let players =
+        sqlx::query("SELECT player_uid, level FROM planner_players WHERE import_version_id = $1")
+            .bind(import_version_id)
+            .fetch_all(&state.pool)
+            .await?;
+    let bases = sqlx::query_scalar::<_, String>(
+        "SELECT base_id FROM planner_bases WHERE import_version_id = $1",
+    )
+    .bind(import_version_id)
+    .fetch_all(&state.pool)
+    .await?;
+    let world_settings = match load_world_settings(state, import_version_id).await? {
+        Some(settings) => settings,
+        None => default_world_settings().map_err(anyhow::Error::msg)?,
+    };
+
+    Ok(PatchTargets {
+        pals: pals
+            .iter()
+            .map(|row| (row.get("pal_instance_id"), pal_state(row)))
+            .collect(),
+        players: players
+            .iter()
+            .map(|row| {
+                let level: Option<i32> = row.get("level");
+                (
+                    row.get("player_uid"),
+                    PlayerState {
+                        level: level.map(i64::from),
+                    },
+                )
+            })
+            .collect(),
+        bases: bases.into_iter().collect(),
+        world_settings,
+    })
+}
+
+fn pal_state(row: &PgRow) -> PalState {
+    let int = |column: &str| row.get::<Option<i32>, _>(column).map(i64::from);
+    let json = |column: &str| row.get::<Value, _>(column);
+    PalState {
+        species_id: row.get("species_id"),
+        nickname: row.get("nickname"),
+        gender: row.get("gender"),
+        level: int("level"),
+        exp: row.get("exp"),
+        rank: int("rank"),
+        rank_hp: int("rank_hp"),
+        rank_attack: int("rank_attack"),
+        rank_defense: int("rank_defense"),
+        rank_craftspeed: int("rank_craftspeed"),
+        talent_hp: int("talent_hp"),
+        talent_melee: int("talent_melee"),
+        talent_shot: int("talent_shot"),
+        talent_defense: int("talent_defense"),
+        passive_skill_ids: serde_json::from_value(json("passive_skill_ids")).unwrap_or_default(),
+        mastered_waza_ids: serde_json::from_value(json("mastered_waza_ids")).unwrap_or_default(),
+        equip_waza_ids: serde_json::from_value(json("equip_waza_ids")).unwrap_or_default(),
+        work_suitability_ranks: serde_json::from_value(json("work_suitability_ranks"))
+            .unwrap_or_default(),
+        work_suitability_add_ranks: serde_json::from_value(json("work_suitability_add_ranks"))
+            .unwrap_or_default(),
+    }
+}
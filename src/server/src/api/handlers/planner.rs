@@ -0,0 +1,349 @@
+use crate::AppState;
+use crate::api::handlers::production::{load_guild_candidate_pals, load_import_pals};
+use crate::api::handlers::world_option::load_world_settings;
+use crate::planner::production::{WorkerContribution, worker_contribution};
+use crate::planner::solver::{
+    PlannedStation, PlannedWorker, ProductionTarget, TargetOutcome, solve,
+};
+use crate::save::normalize::normalize_guid;
+use crate::save::world_option::{WorldSettingValue, default_world_settings};
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sqlx::Row;
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+const WORKER_CAP_SETTING: &str = "BaseCampWorkerMaxNum";
+
+#[derive(Deserialize)]
+pub struct SolveRequest {
+    pub import_version_id: Uuid,
+    pub base_id: String,
+    pub targets: Vec<ProductionTarget>,
+    /// Pals the solver may pick from. When omitted: the base's current
+    /// workers and pals owned by its guild's members that work at no other
+    /// base.
+    pub candidate_pal_instance_ids: Option<Vec<String>>,
+    /// Overrides the world's `BaseCampWorkerMaxNum`.
+    pub max_workers: Option<usize>,
+    /// Also return the plan as base assignment operations.
+    #[serde(default)]
+    pub emit_patchset: bool,
+}
+
+#[derive(Serialize)]
+pub struct SolveResponse {
+    pub import_version_id: Uuid,
+    pub base_id: String,
+    pub worker_cap: usize,
+    /// `request`, `world_option`, or `default`.
+    pub worker_cap_source: &'static str,
+    pub workers: Vec<PlannedWorker>,
+    /// Current workers of the base the plan removes to stay under the cap.
+    pub released_pal_instance_ids: Vec<String>,
+    pub stations: Vec<PlannedStation>,
+    pub targets: Vec<TargetOutcome>,
+    pub patchset: Option<PlannedPatchset>,
+}
+
+/// Patch operations for the plan, not yet stored as a patchset.
+#[derive(Serialize)]
+pub struct PlannedPatchset {
+    pub operations: Vec<PlannedPatchOperation>,
+}
+
+#[derive(Serialize)]
+pub struct PlannedPatchOperation {
+    pub sequence: i32,
+    pub op_type: &'static str,
+    pub target_kind: &'static str,
+    pub target_id: String,
+    pub payload_json: Value,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+enum SolveError {
+    ImportNotFound,
+    BaseNotFound(String),
+    Invalid(String),
+    Internal(anyhow::Error),
+}
+
+impl From<sqlx::Error> for SolveError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Internal(error.into())
+    }
+}
+
+impl From<anyhow::Error> for SolveError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::Internal(error)
+    }
+}
+
+pub async fn solve_planner(
+    State(state): State<AppState>,
+    Json(request): Json<SolveRequest>,
+) -> impl IntoResponse {
+    match run_solve_planner(&state, request).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(SolveError::ImportNotFound) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "import version not found".to_string(),
+            }),
+        )
+            .into_response(),
+        Err(SolveError::BaseNotFound(base_id)) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("base {} not found in this import", base_id),
+            }),
+        )
+            .into_response(),
+        Err(SolveError::Invalid(error)) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ErrorResponse { error }),
+        )
+            .into_response(),
+        Err(SolveError::Internal(error)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to solve planner targets: {:#}", error),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+async fn run_solve_planner(
+    state: &AppState,
+    request: SolveRequest,
+) -> Result<SolveResponse, SolveError> {
+    let import_version_id = request.import_version_id;
+    let base_id = normalize_guid(&request.base_id);
+    let exists = sqlx::query("SELECT id FROM save_import_versions WHERE id = $1")
+        .bind(import_version_id)
+        .fetch_optional(&state.pool)
+        .await?;
+    if exists.is_none() {
+        return Err(SolveError::ImportNotFound);
+    }
+    let base = sqlx::query(
+        "SELECT base_id FROM planner_bases WHERE import_version_id = $1 AND base_id = $2",
+    )
+    .bind(import_version_id)
+    .bind(&base_id)
+    .fetch_optional(&state.pool)
+    .await?;
+    if base.is_none() {
+        return Err(SolveError::BaseNotFound(base_id));
+    }
+
+    let (worker_cap, worker_cap_source) = match request.max_workers {
+        Some(0) => {
+            return Err(SolveError::Invalid(
+                "max_workers must be at least 1".to_string(),
+            ));
+        }
+        Some(max_workers) => (max_workers, "request"),
+        None => world_worker_cap(state, import_version_id).await?,
+    };
+
+    let current_workers = load_slot_workers(state, import_version_id, &base_id).await?;
+    let mut pals = match &request.candidate_pal_instance_ids {
+        Some(ids) => {
+            let mut pals = load_import_pals(state, import_version_id).await?;
+            let wanted: BTreeSet<String> = ids.iter().map(|id| normalize_guid(id)).collect();
+            pals.retain(|pal| wanted.contains(&pal.pal_instance_id));
+            if pals.len() < wanted.len() {
+                let known: BTreeSet<&str> = pals
+                    .iter()
+                    .map(|pal| pal.pal_instance_id.as_str())
+                    .collect();
+                let unknown: Vec<&str> = wanted
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|id| !known.contains(id))
+                    .collect();
+                return Err(SolveError::Invalid(format!(
+                    "unknown candidate pals: {}",
+                    unknown.join(", ")
+                )));
+            }
+            pals
+        }
+        None => load_guild_candidate_pals(state, import_version_id, &base_id).await?,
+    };
+    // Current workers go first so the solver keeps them on ties.
+    pals.sort_by_key(|pal| {
+        !current_workers
+            .iter()
+            .any(|worker| worker.pal_instance_id == pal.pal_instance_id)
+    });
+    let candidates: Vec<WorkerContribution> = pals
+        .iter()
+        .map(|pal| worker_contribution(&state.catalog, pal))
+        .collect();
+
+    let plan = solve(&state.catalog, &candidates, &request.targets, worker_cap)
+        .map_err(SolveError::Invalid)?;
+
+    // Current workers count against the cap. Those the plan did not pick stay
+    // unless their slots are needed, highest slot released first.
+    let planned: BTreeSet<&str> = plan
+        .workers
+        .iter()
+        .map(|worker| worker.pal_instance_id.as_str())
+        .collect();
+    let (kept, mut idle): (Vec<&SlotWorker>, Vec<&SlotWorker>) = current_workers
+        .iter()
+        .partition(|worker| planned.contains(worker.pal_instance_id.as_str()));
+    let added: Vec<&PlannedWorker> = plan
+        .workers
+        .iter()
+        .filter(|worker| {
+            !kept
+                .iter()
+                .any(|current| current.pal_instance_id == worker.pal_instance_id)
+        })
+        .collect();
+    let overflow = (current_workers.len() + added.len()).saturating_sub(worker_cap);
+    let released = idle.split_off(idle.len() - overflow.min(idle.len()));
+    let occupied: BTreeSet<usize> = kept
+        .iter()
+        .chain(&idle)
+        .filter_map(|worker| worker.slot)
+        .collect();
+    let free_slots = (0..).filter(|slot| !occupied.contains(slot));
+
+    let patchset = request.emit_patchset.then(|| {
+        let deletes = released.iter().map(|worker| {
+            (
+                "delete_base_assignment",
+                json!({
+                    "pal_instance_id": worker.pal_instance_id,
+                    "assignment_kind": "base_slot",
+                    "assignment_target": worker.assignment_target,
+                    "priority": worker.priority,
+                }),
+            )
+        });
+        let upserts = added.iter().zip(free_slots).map(|(worker, slot)| {
+            (
+                "upsert_base_assignment",
+                json!({
+                    "pal_instance_id": worker.pal_instance_id,
+                    "assignment_kind": "base_slot",
+                    "assignment_target": slot.to_string(),
+                    "priority": slot,
+                }),
+            )
+        });
+        PlannedPatchset {
+            operations: deletes
+                .chain(upserts)
+                .enumerate()
+                .map(|(index, (op_type, payload_json))| PlannedPatchOperation {
+                    sequence: index as i32 + 1,
+                    op_type,
+                    target_kind: "base",
+                    target_id: base_id.clone(),
+                    payload_json,
+                })
+                .collect(),
+        }
+    });
+    let released_pal_instance_ids = released
+        .iter()
+        .map(|worker| worker.pal_instance_id.clone())
+        .collect();
+
+    Ok(SolveResponse {
+        import_version_id,
+        base_id,
+        worker_cap,
+        worker_cap_source,
+        workers: plan.workers,
+        released_pal_instance_ids,
+        stations: plan.stations,
+        targets: plan.targets,
+        patchset,
+    })
+}
+
+struct SlotWorker {
+    pal_instance_id: String,
+    assignment_target: Option<String>,
+    priority: Option<i32>,
+    slot: Option<usize>,
+}
+
+/// Pals in a worker slot of `base_id`, in slot order.
+async fn load_slot_workers(
+    state: &AppState,
+    import_version_id: Uuid,
+    base_id: &str,
+) -> Result<Vec<SlotWorker>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT pal_instance_id, assignment_target, priority
+         FROM planner_base_assignments
+         WHERE import_version_id = $1
+           AND base_id = $2
+           AND assignment_kind = 'base_slot'",
+    )
+    .bind(import_version_id)
+    .bind(base_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut workers: Vec<SlotWorker> = rows
+        .iter()
+        .map(|row| {
+            let assignment_target: Option<String> = row.get("assignment_target");
+            SlotWorker {
+                pal_instance_id: row.get("pal_instance_id"),
+                slot: assignment_target
+                    .as_deref()
+                    .and_then(|target| target.parse().ok()),
+                assignment_target,
+                priority: row.get("priority"),
+            }
+        })
+        .collect();
+    workers.sort_by_key(|worker| (worker.slot.is_none(), worker.slot));
+    Ok(workers)
+}
+
+/// `BaseCampWorkerMaxNum` from the import's `WorldOption.sav`, else the game
+/// default.
+async fn world_worker_cap(
+    state: &AppState,
+    import_version_id: Uuid,
+) -> anyhow::Result<(usize, &'static str)> {
+    let saved = load_world_settings(state, import_version_id)
+        .await?
+        .and_then(|settings| worker_cap_setting(settings.get(WORKER_CAP_SETTING)));
+    if let Some(cap) = saved {
+        return Ok((cap, "world_option"));
+    }
+    let defaults = default_world_settings().map_err(anyhow::Error::msg)?;
+    let cap = worker_cap_setting(defaults.get(WORKER_CAP_SETTING))
+        .ok_or_else(|| anyhow::anyhow!("{} has no default", WORKER_CAP_SETTING))?;
+    Ok((cap, "default"))
+}
+
+fn worker_cap_setting(value: Option<&WorldSettingValue>) -> Option<usize> {
+    match value {
+        Some(WorldSettingValue::Int(cap)) if *cap > 0 => usize::try_from(*cap).ok(),
+        _ => None,
+    }
+}
@@ -0,0 +1,206 @@
+use crate::AppState;
+use crate::planner::production::{BaseProduction, WorkerInput, compute_base_production};
+use crate::save::normalize::normalize_guid;
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::Row;
+use sqlx::postgres::PgRow;
+use uuid::Uuid;
+
+#[derive(Serialize)]
+pub struct BaseProductionResponse {
+    pub import_version_id: Uuid,
+    pub base_id: String,
+    pub base_name: Option<String>,
+    #[serde(flatten)]
+    pub production: BaseProduction,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+enum ProductionError {
+    ImportNotFound,
+    BaseNotFound,
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for ProductionError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+pub async fn get_base_production(
+    State(state): State<AppState>,
+    Path((import_version_id, base_id)): Path<(Uuid, String)>,
+) -> impl IntoResponse {
+    match run_get_base_production(&state, import_version_id, &base_id).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(ProductionError::ImportNotFound) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "import version not found".to_string(),
+            }),
+        )
+            .into_response(),
+        Err(ProductionError::BaseNotFound) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("base {} not found in this import", base_id),
+            }),
+        )
+            .into_response(),
+        Err(ProductionError::Database(error)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to compute base production: {}", error),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+async fn run_get_base_production(
+    state: &AppState,
+    import_version_id: Uuid,
+    base_id: &str,
+) -> Result<BaseProductionResponse, ProductionError> {
+    let base_id = normalize_guid(base_id);
+    let exists = sqlx::query("SELECT id FROM save_import_versions WHERE id = $1")
+        .bind(import_version_id)
+        .fetch_optional(&state.pool)
+        .await?;
+    if exists.is_none() {
+        return Err(ProductionError::ImportNotFound);
+    }
+
+    let base = sqlx::query(
+        "SELECT base_name FROM planner_bases WHERE import_version_id = $1 AND base_id = $2",
+    )
+    .bind(import_version_id)
+    .bind(&base_id)
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(ProductionError::BaseNotFound)?;
+
+    let workers = load_base_workers(state, import_version_id, &base_id).await?;
+    Ok(BaseProductionResponse {
+        import_version_id,
+        base_name: base.get("base_name"),
+        production: compute_base_production(&state.catalog, &workers),
+        base_id,
+    })
+}
+
+/// Pals assigned to a base worker slot of `base_id`.
+pub async fn load_base_workers(
+    state: &AppState,
+    import_version_id: Uuid,
+    base_id: &str,
+) -> Result<Vec<WorkerInput>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT DISTINCT pp.pal_instance_id, pp.species_id, pp.rank, pp.rank_craftspeed,
+                pp.passive_skill_ids, pp.work_suitability_ranks
+         FROM planner_base_assignments pba
+         JOIN planner_pals pp
+           ON pp.import_version_id = pba.import_version_id
+          AND pp.pal_instance_id = pba.pal_instance_id
+         WHERE pba.import_version_id = $1
+           AND pba.base_id = $2
+         ORDER BY pp.pal_instance_id ASC",
+    )
+    .bind(import_version_id)
+    .bind(base_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(rows.iter().map(worker_input).collect())
+}
+
+/// Every pal of the import, to resolve explicit solver candidates.
+pub async fn load_import_pals(
+    state: &AppState,
+    import_version_id: Uuid,
+) -> Result<Vec<WorkerInput>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT pal_instance_id, species_id, rank, rank_craftspeed,
+                passive_skill_ids, work_suitability_ranks
+         FROM planner_pals
+         WHERE import_version_id = $1
+         ORDER BY pal_instance_id ASC",
+    )
+    .bind(import_version_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(rows.iter().map(worker_input).collect())
+}
+
+/// Default solver candidates for `base_id`: its current workers plus pals
+/// owned by members of the base's guild that do not work at another base.
+/// Guild member ids are stored as decoded from RawData, so both sides are
+/// compared in `normalize_guid` form.
+pub async fn load_guild_candidate_pals(
+    state: &AppState,
+    import_version_id: Uuid,
+    base_id: &str,
+) -> Result<Vec<WorkerInput>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT pp.pal_instance_id, pp.species_id, pp.rank, pp.rank_craftspeed,
+                pp.passive_skill_ids, pp.work_suitability_ranks
+         FROM planner_pals pp
+         WHERE pp.import_version_id = $1
+           AND (
+             EXISTS (
+               SELECT 1 FROM planner_base_assignments pba
+               WHERE pba.import_version_id = pp.import_version_id
+                 AND pba.pal_instance_id = pp.pal_instance_id
+                 AND pba.base_id = $2
+             )
+             OR (
+               UPPER(REPLACE(pp.owner_player_uid, '-', '')) IN (
+                 SELECT UPPER(REPLACE(pgm.player_uid, '-', ''))
+                 FROM planner_guild_members pgm
+                 JOIN planner_bases pb
+                   ON pb.import_version_id = pgm.import_version_id
+                  AND UPPER(REPLACE(pb.group_id, '-', '')) = UPPER(REPLACE(pgm.group_id, '-', ''))
+                 WHERE pgm.import_version_id = $1
+                   AND pb.base_id = $2
+               )
+               AND NOT EXISTS (
+                 SELECT 1 FROM planner_base_assignments pba
+                 WHERE pba.import_version_id = pp.import_version_id
+                   AND pba.pal_instance_id = pp.pal_instance_id
+                   AND pba.base_id <> $2
+               )
+             )
+           )
+         ORDER BY pp.pal_instance_id ASC",
+    )
+    .bind(import_version_id)
+    .bind(base_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(rows.iter().map(worker_input).collect())
+}
+
+fn worker_input(row: &PgRow) -> WorkerInput {
+    let passive_skill_ids: Value = row.get("passive_skill_ids");
+    let work_suitability_ranks: Value = row.get("work_suitability_ranks");
+    WorkerInput {
+        pal_instance_id: row.get("pal_instance_id"),
+        species_id: row.get("species_id"),
+        rank: row.get("rank"),
+        rank_craftspeed: row.get("rank_craftspeed"),
+        passive_skill_ids: serde_json::from_value(passive_skill_ids).unwrap_or_default(),
+        work_suitability_ranks: serde_json::from_value(work_suitability_ranks).unwrap_or_default(),
+    }
+}
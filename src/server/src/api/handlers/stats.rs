@@ -0,0 +1,127 @@
+use crate::AppState;
+use crate::planner::stats::{CONDENSER_STAT_PCT, PalStats, SOUL_STAT_PCT, StatInput, pal_stats};
+use crate::save::normalize::normalize_guid;
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::Row;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct PalStatsQuery {
+    pub owner_player_uid: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct PalStatsResponse {
+    pub import_version_id: Uuid,
+    pub condenser_stat_pct: f64,
+    pub soul_stat_pct: f64,
+    /// Highest `combat_power` first; pals without stats last.
+    pub pals: Vec<PalStats>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+enum StatsError {
+    ImportNotFound,
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for StatsError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Database(error)
+    }
+}
+
+pub async fn get_pal_stats(
+    State(state): State<AppState>,
+    Path(import_version_id): Path<Uuid>,
+    Query(query): Query<PalStatsQuery>,
+) -> impl IntoResponse {
+    match run_get_pal_stats(&state, import_version_id, query.owner_player_uid.as_deref()).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(StatsError::ImportNotFound) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "import version not found".to_string(),
+            }),
+        )
+            .into_response(),
+        Err(StatsError::Database(error)) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to compute pal stats: {}", error),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+async fn run_get_pal_stats(
+    state: &AppState,
+    import_version_id: Uuid,
+    owner_player_uid: Option<&str>,
+) -> Result<PalStatsResponse, StatsError> {
+    let exists = sqlx::query("SELECT id FROM save_import_versions WHERE id = $1")
+        .bind(import_version_id)
+        .fetch_optional(&state.pool)
+        .await?;
+    if exists.is_none() {
+        return Err(StatsError::ImportNotFound);
+    }
+
+    let rows = sqlx::query(
+        "SELECT pal_instance_id, owner_player_uid, species_id, nickname, level, rank,
+                rank_hp, rank_attack, rank_defense, talent_hp, talent_shot, talent_defense,
+                passive_skill_ids
+         FROM planner_pals
+         WHERE import_version_id = $1
+           AND ($2::TEXT IS NULL OR owner_player_uid = $2)
+         ORDER BY pal_instance_id ASC",
+    )
+    .bind(import_version_id)
+    .bind(owner_player_uid.map(normalize_guid))
+    .fetch_all(&state.pool)
+    .await?;
+
+    let mut pals: Vec<PalStats> = rows
+        .iter()
+        .map(|row| {
+            let passive_skill_ids: Value = row.get("passive_skill_ids");
+            pal_stats(
+                &state.catalog,
+                &StatInput {
+                    pal_instance_id: row.get("pal_instance_id"),
+                    owner_player_uid: row.get("owner_player_uid"),
+                    species_id: row.get("species_id"),
+                    nickname: row.get("nickname"),
+                    level: row.get("level"),
+                    rank: row.get("rank"),
+                    rank_hp: row.get("rank_hp"),
+                    rank_attack: row.get("rank_attack"),
+                    rank_defense: row.get("rank_defense"),
+                    talent_hp: row.get("talent_hp"),
+                    talent_shot: row.get("talent_shot"),
+                    talent_defense: row.get("talent_defense"),
+                    passive_skill_ids: serde_json::from_value(passive_skill_ids)
+                        .unwrap_or_default(),
+                },
+            )
+        })
+        .collect();
+    pals.sort_by_key(|pal| std::cmp::Reverse(pal.combat_power));
+
+    Ok(PalStatsResponse {
+        import_version_id,
+        condenser_stat_pct: CONDENSER_STAT_PCT,
+        soul_stat_pct: SOUL_STAT_PCT,
+        pals,
+    })
+}
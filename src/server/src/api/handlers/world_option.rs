@@ -0,0 +1,261 @@
+use crate::AppState;
+use crate::save::world_option::{
+    UpdateWorldOptionPayload, WorldOptionBuildError, WorldOptionSource, WorldSettings,
+    build_export_world_option, decode_world_option, format_option_settings_ini,
+    parse_option_settings_ini, validate_world_settings,
+};
+use crate::storage::fs;
+use anyhow::Context;
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::{StatusCode, header};
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::time::Duration;
+use uuid::Uuid;
+
+const WORLD_OPTION_FILE: &str = "WorldOption.sav";
+const LEVEL_META_FILE: &str = "LevelMeta.sav";
+const WORLD_OPTION_SOURCE_HEADER: &str = "x-world-option-source";
+const WORLD_OPTION_DECODE_TIMEOUT_SECS: u64 = 20;
+
+#[derive(Serialize)]
+pub struct WorldOptionResponse {
+    pub import_version_id: Uuid,
+    pub settings: WorldSettings,
+    pub option_settings_ini: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct WorldSettingsPayload {
+    pub settings: WorldSettings,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+enum WorldOptionOutcome<T> {
+    Ok(T),
+    NotFound(String),
+    BadRequest(String),
+    Unprocessable(String),
+}
+
+pub async fn get_world_option(
+    State(state): State<AppState>,
+    Path(import_version_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match run_get_world_option(&state, import_version_id).await {
+        Ok(WorldOptionOutcome::Ok(response)) => (StatusCode::OK, Json(response)).into_response(),
+        Ok(outcome) => outcome_error(outcome),
+        Err(error) => internal_error("failed to decode world option", error),
+    }
+}
+
+/// Previews an `update_world_option` payload: returns the `WorldOption.sav`
+/// `build_export_world_option` produces for the import, rebuilt or, when the
+/// import has none, synthesized. Export does not call it yet; that is
+/// blocked on the export pipeline.
+pub async fn rebuild_world_option_file(
+    State(state): State<AppState>,
+    Path(import_version_id): Path<Uuid>,
+    Json(request): Json<UpdateWorldOptionPayload>,
+) -> impl IntoResponse {
+    match run_rebuild_world_option(&state, import_version_id, request).await {
+        Ok(WorldOptionOutcome::Ok((source, bytes))) => (
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", WORLD_OPTION_FILE),
+                ),
+                (
+                    header::HeaderName::from_static(WORLD_OPTION_SOURCE_HEADER),
+                    source.as_str().to_string(),
+                ),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Ok(outcome) => outcome_error(outcome),
+        Err(error) => internal_error("failed to rebuild world option", error),
+    }
+}
+
+pub async fn world_settings_from_ini(body: String) -> impl IntoResponse {
+    match parse_option_settings_ini(&body) {
+        Ok(settings) => (StatusCode::OK, Json(WorldSettingsPayload { settings })).into_response(),
+        Err(error) => (StatusCode::BAD_REQUEST, Json(ErrorResponse { error })).into_response(),
+    }
+}
+
+pub async fn world_settings_to_ini(Json(request): Json<WorldSettingsPayload>) -> impl IntoResponse {
+    match validate_world_settings(&request.settings, &WorldSettings::new()) {
+        Ok(settings) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+            format_option_settings_ini(&settings),
+        )
+            .into_response(),
+        Err(error) => (StatusCode::BAD_REQUEST, Json(ErrorResponse { error })).into_response(),
+    }
+}
+
+async fn run_get_world_option(
+    state: &AppState,
+    import_version_id: Uuid,
+) -> anyhow::Result<WorldOptionOutcome<WorldOptionResponse>> {
+    if !import_version_exists(state, import_version_id).await? {
+        return Ok(WorldOptionOutcome::NotFound(
+            "import version not found".to_string(),
+        ));
+    }
+    let Some(bytes) = load_save_file(state, import_version_id, WORLD_OPTION_FILE).await? else {
+        return Ok(WorldOptionOutcome::NotFound(
+            "import version has no WorldOption.sav".to_string(),
+        ));
+    };
+
+    let settings = match decode_blocking(move || decode_world_option(&bytes)).await? {
+        Ok(settings) => settings,
+        Err(error) => return Ok(WorldOptionOutcome::Unprocessable(error)),
+    };
+    let option_settings_ini = format_option_settings_ini(&settings);
+
+    Ok(WorldOptionOutcome::Ok(WorldOptionResponse {
+        import_version_id,
+        settings,
+        option_settings_ini,
+    }))
+}
+
+async fn run_rebuild_world_option(
+    state: &AppState,
+    import_version_id: Uuid,
+    request: UpdateWorldOptionPayload,
+) -> anyhow::Result<WorldOptionOutcome<(WorldOptionSource, Vec<u8>)>> {
+    if !import_version_exists(state, import_version_id).await? {
+        return Ok(WorldOptionOutcome::NotFound(
+            "import version not found".to_string(),
+        ));
+    }
+
+    let changes = match request.changes() {
+        Ok(changes) => changes,
+        Err(error) => return Ok(WorldOptionOutcome::BadRequest(error)),
+    };
+
+    let original = load_save_file(state, import_version_id, WORLD_OPTION_FILE).await?;
+    let level_meta = match original {
+        Some(_) => None,
+        None => load_save_file(state, import_version_id, LEVEL_META_FILE).await?,
+    };
+    let result = decode_blocking(move || {
+        build_export_world_option(original.as_deref(), level_meta.as_deref(), &changes)
+    })
+    .await?;
+
+    Ok(match result {
+        Ok(file) => WorldOptionOutcome::Ok(file),
+        Err(WorldOptionBuildError::Invalid(error)) => WorldOptionOutcome::BadRequest(error),
+        Err(WorldOptionBuildError::Unprocessable(error)) => {
+            WorldOptionOutcome::Unprocessable(error)
+        }
+    })
+}
+
+/// Settings decoded from the import's `WorldOption.sav`; `None` when the
+/// import has no such file or it cannot be decoded.
+pub async fn load_world_settings(
+    state: &AppState,
+    import_version_id: Uuid,
+) -> anyhow::Result<Option<WorldSettings>> {
+    let Some(bytes) = load_save_file(state, import_version_id, WORLD_OPTION_FILE).await? else {
+        return Ok(None);
+    };
+    Ok(decode_blocking(move || decode_world_option(&bytes))
+        .await?
+        .ok())
+}
+
+async fn import_version_exists(state: &AppState, import_version_id: Uuid) -> anyhow::Result<bool> {
+    sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM save_import_versions WHERE id = $1)")
+        .bind(import_version_id)
+        .fetch_one(&state.pool)
+        .await
+        .context("failed to load import version")
+}
+
+/// Reads one stored save file of the import by its path under the world root.
+async fn load_save_file(
+    state: &AppState,
+    import_version_id: Uuid,
+    relative_path: &str,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    let row = sqlx::query(
+        "SELECT storage_key
+         FROM save_files
+         WHERE import_version_id = $1 AND relative_path = $2",
+    )
+    .bind(import_version_id)
+    .bind(relative_path)
+    .fetch_optional(&state.pool)
+    .await
+    .with_context(|| format!("failed to load {} save_files row", relative_path))?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let storage_key: String = row.get("storage_key");
+    let bytes = fs::read_bytes(&state.settings.artifact_storage_root, &storage_key).await?;
+    Ok(Some(bytes))
+}
+
+async fn decode_blocking<T, F>(work: F) -> anyhow::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    tokio::time::timeout(
+        Duration::from_secs(WORLD_OPTION_DECODE_TIMEOUT_SECS),
+        tokio::task::spawn_blocking(work),
+    )
+    .await
+    .with_context(|| {
+        format!(
+            "WorldOption.sav decode timed out after {}s",
+            WORLD_OPTION_DECODE_TIMEOUT_SECS
+        )
+    })?
+    .context("WorldOption.sav decode worker panicked")
+}
+
+fn outcome_error<T>(outcome: WorldOptionOutcome<T>) -> axum::response::Response {
+    let (status, error) = match outcome {
+        WorldOptionOutcome::NotFound(message) => (StatusCode::NOT_FOUND, message),
+        WorldOptionOutcome::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+        WorldOptionOutcome::Unprocessable(message) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("WorldOption.sav: {}", message),
+        ),
+        WorldOptionOutcome::Ok(_) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "unexpected world option outcome".to_string(),
+        ),
+    };
+    (status, Json(ErrorResponse { error })).into_response()
+}
+
+fn internal_error(context: &str, error: anyhow::Error) -> axum::response::Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse {
+            error: format!("{}: {:#}", context, error),
+        }),
+    )
+        .into_response()
+}
@@ -0,0 +1,579 @@
+use crate::AppState;
+use crate::save::normalize::normalize_guid;
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::Row;
+use sqlx::postgres::PgRow;
+use uuid::Uuid;
+
+const ARCHIVE_EXTENSIONS: [&str; 5] = [".tar.gz", ".tgz", ".tar", ".zip", ".7z"];
+
+#[derive(Serialize)]
+pub struct WorldSummary {
+    pub id: Uuid,
+    pub world_key: Option<String>,
+    pub display_name: String,
+    pub archived_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub import_count: i64,
+    pub latest_import_version_id: Option<Uuid>,
+    pub latest_import_at: Option<String>,
+    pub export_count: i64,
+}
+
+#[derive(Serialize)]
+pub struct WorldListResponse {
+    pub worlds: Vec<WorldSummary>,
+}
+
+#[derive(Serialize)]
+pub struct WorldExportLineageItem {
+    pub export_version_id: Uuid,
+    pub import_version_id: Uuid,
+    pub patchset_id: Option<Uuid>,
+    pub status: String,
+    pub created_at: String,
+    pub reimported_as: Vec<Uuid>,
+}
+
+#[derive(Serialize)]
+pub struct WorldDetailResponse {
+    pub world: WorldSummary,
+    pub exports: Vec<WorldExportLineageItem>,
+}
+
+#[derive(Serialize)]
+pub struct ImportWorldAssignment {
+    pub import_version_id: Uuid,
+    pub world_id: Uuid,
+}
+
+#[derive(Deserialize)]
+pub struct WorldListQuery {
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+#[derive(Deserialize)]
+pub struct CreateWorldRequest {
+    pub display_name: String,
+    pub world_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateWorldRequest {
+    pub display_name: Option<String>,
+    pub archived: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct AssignImportWorldRequest {
+    pub world_id: Uuid,
+}
+
+/// World an import is attached to, and the export it was re-imported from
+/// when its source archive matches a stored export archive.
+pub(crate) struct WorldAttachment {
+    pub world_id: Uuid,
+    pub source_export_version_id: Option<Uuid>,
+}
+
+enum WorldOutcome<T> {
+    Done(T),
+    NotFound(String),
+    Conflict(String),
+    BadRequest(String),
+}
+
+#[derive(Serialize)]
+pub struct PalHistoryEntry {
+    pub import_version_id: Uuid,
+    pub imported_at: String,
+    pub species_id: Option<String>,
+    pub nickname: Option<String>,
+    pub owner_player_uid: Option<String>,
+    pub level: Option<i32>,
+    pub exp: Option<i64>,
+    pub rank: Option<i32>,
+    pub passive_skill_ids: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct PalHistoryResponse {
+    pub world_id: Uuid,
+    pub pal_instance_id: String,
+    pub entries: Vec<PalHistoryEntry>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// Stable key that groups imports of the same world: the world folder name
+/// (Palworld names it after the world GUID), falling back to the uploaded file
+/// name without its archive extension when the archive root is the world.
+pub(crate) fn derive_world_key(world_root_path: &str, source_file_name: &str) -> String {
+    let folder_name = world_root_path
+        .rsplit('/')
+        .find(|segment| !segment.is_empty());
+    let raw_key = match folder_name {
+        Some(folder_name) => folder_name.to_string(),
+        None => {
+            let lower = source_file_name.to_ascii_lowercase();
+            ARCHIVE_EXTENSIONS
+                .iter()
+                .find(|extension| lower.ends_with(*extension))
+                .map(|extension| {
+                    source_file_name[..source_file_name.len() - extension.len()].to_string()
+                })
+                .unwrap_or_else(|| source_file_name.to_string())
+        }
+    };
+
+    if raw_key.len() == 32 && raw_key.chars().all(|value| value.is_ascii_hexdigit()) {
+        raw_key.to_ascii_uppercase()
+    } else {
+        raw_key
+    }
+}
+
+/// Picks the world for a new import. A source archive that is byte-identical
+/// to a stored export joins the world of the import that export came from;
+/// otherwise the import joins the world for `world_key`, created on first sight.
+pub(crate) async fn resolve_import_world(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    world_key: &str,
+    source_sha256: &str,
+) -> Result<WorldAttachment, sqlx::Error> {
+    let lineage_row = sqlx::query(
+        "SELECT ev.id AS export_version_id, iv.world_id
+         FROM save_zip_artifacts za
+         JOIN save_export_versions ev ON ev.id = za.export_version_id
+         JOIN save_import_versions iv ON iv.id = ev.import_version_id
+         WHERE za.kind = 'export_zip' AND za.sha256 = $1
+         ORDER BY za.created_at DESC
+         LIMIT 1",
+    )
+    .bind(source_sha256)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let source_export_version_id = lineage_row
+        .as_ref()
+        .map(|row| row.get::<Uuid, _>("export_version_id"));
+    let lineage_world_id = lineage_row.and_then(|row| row.get::<Option<Uuid>, _>("world_id"));
+    let world_id = match lineage_world_id {
+        Some(world_id) => world_id,
+        None => attach_world(tx, world_key).await?,
+    };
+
+    Ok(WorldAttachment {
+        world_id,
+        source_export_version_id,
+    })
+}
+
+/// Returns the world for `world_key`, creating it on first sight.
+async fn attach_world(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    world_key: &str,
+) -> Result<Uuid, sqlx::Error> {
+    let row = sqlx::query(
+        "INSERT INTO worlds (id, world_key, display_name)
+         VALUES ($1, $2, $2)
+         ON CONFLICT (world_key) DO UPDATE SET world_key = EXCLUDED.world_key
+         RETURNING id",
+    )
+    .bind(Uuid::new_v4())
+    .bind(world_key)
+    .fetch_one(&mut **tx)
+    .await?;
+    Ok(row.get("id"))
+}
+
+pub async fn list_worlds(
+    State(state): State<AppState>,
+    Query(query): Query<WorldListQuery>,
+) -> impl IntoResponse {
+    let result = run_list_worlds(&state, query.include_archived)
+        .await
+        .map(WorldOutcome::Done);
+    world_response(result, StatusCode::OK, "failed to list worlds")
+}
+
+pub async fn create_world(
+    State(state): State<AppState>,
+    Json(request): Json<CreateWorldRequest>,
+) -> impl IntoResponse {
+    let result = run_create_world(&state, request).await;
+    world_response(result, StatusCode::CREATED, "failed to create world")
+}
+
+pub async fn get_world(
+    State(state): State<AppState>,
+    Path(world_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let result = run_get_world(&state, world_id).await;
+    world_response(result, StatusCode::OK, "failed to load world")
+}
+
+pub async fn update_world(
+    State(state): State<AppState>,
+    Path(world_id): Path<Uuid>,
+    Json(request): Json<UpdateWorldRequest>,
+) -> impl IntoResponse {
+    let result = run_update_world(&state, world_id, request).await;
+    world_response(result, StatusCode::OK, "failed to update world")
+}
+
+pub async fn assign_import_world(
+    State(state): State<AppState>,
+    Path(import_version_id): Path<Uuid>,
+    Json(request): Json<AssignImportWorldRequest>,
+) -> impl IntoResponse {
+    let result = run_assign_import_world(&state, import_version_id, request.world_id).await;
+    world_response(result, StatusCode::OK, "failed to assign import world")
+}
+
+fn world_response<T: Serialize>(
+    result: Result<WorldOutcome<T>, sqlx::Error>,
+    success_status: StatusCode,
+    error_context: &str,
+) -> axum::response::Response {
+    match result {
+        Ok(WorldOutcome::Done(response)) => (success_status, Json(response)).into_response(),
+        Ok(WorldOutcome::NotFound(message)) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse { error: message }),
+        )
+            .into_response(),
+        Ok(WorldOutcome::Conflict(message)) => {
+            (StatusCode::CONFLICT, Json(ErrorResponse { error: message })).into_response()
+        }
+        Ok(WorldOutcome::BadRequest(message)) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse { error: message }),
+        )
+            .into_response(),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("{}: {}", error_context, error),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+const WORLD_SUMMARY_SELECT: &str = "SELECT
+        w.id,
+        w.world_key,
+        w.display_name,
+        w.archived_at::text AS archived_at,
+        w.created_at::text AS created_at,
+        w.updated_at::text AS updated_at,
+        (SELECT COUNT(*) FROM save_import_versions iv WHERE iv.world_id = w.id) AS import_count,
+        latest.id AS latest_import_version_id,
+        latest.created_at::text AS latest_import_at,
+        (SELECT COUNT(*)
+         FROM save_export_versions ev
+         JOIN save_import_versions iv ON iv.id = ev.import_version_id
+         WHERE iv.world_id = w.id) AS export_count
+     FROM worlds w
+     LEFT JOIN LATERAL (
+        SELECT iv.id, iv.created_at
+        FROM save_import_versions iv
+        WHERE iv.world_id = w.id AND iv.status = 'ready'
+        ORDER BY iv.created_at DESC, iv.id DESC
+        LIMIT 1
+     ) latest ON TRUE";
+
+fn world_summary_from_row(row: &PgRow) -> WorldSummary {
+    WorldSummary {
+        id: row.get("id"),
+        world_key: row.get("world_key"),
+        display_name: row.get("display_name"),
+        archived_at: row.get("archived_at"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        import_count: row.get("import_count"),
+        latest_import_version_id: row.get("latest_import_version_id"),
+        latest_import_at: row.get("latest_import_at"),
+        export_count: row.get("export_count"),
+    }
+}
+
+async fn fetch_world_summary(
+    state: &AppState,
+    world_id: Uuid,
+) -> Result<Option<WorldSummary>, sqlx::Error> {
+    let row = sqlx::query(&format!("{} WHERE w.id = $1", WORLD_SUMMARY_SELECT))
+        .bind(world_id)
+        .fetch_optional(&state.pool)
+        .await?;
+    Ok(row.as_ref().map(world_summary_from_row))
+}
+
+async fn run_list_worlds(
+    state: &AppState,
+    include_archived: bool,
+) -> Result<WorldListResponse, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "{} WHERE $1 OR w.archived_at IS NULL ORDER BY w.display_name ASC, w.id ASC",
+        WORLD_SUMMARY_SELECT
+    ))
+    .bind(include_archived)
+    .fetch_all(&state.pool)
+    .await?;
+
+    Ok(WorldListResponse {
+        worlds: rows.iter().map(world_summary_from_row).collect(),
+    })
+}
+
+async fn run_create_world(
+    state: &AppState,
+    request: CreateWorldRequest,
+) -> Result<WorldOutcome<WorldSummary>, sqlx::Error> {
+    let display_name = request.display_name.trim();
+    if display_name.is_empty() {
+        return Ok(WorldOutcome::BadRequest(
+            "display_name must not be empty".to_string(),
+        ));
+    }
+    let world_key = request
+        .world_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    let world_id = Uuid::new_v4();
+    let inserted = sqlx::query(
+        "INSERT INTO worlds (id, world_key, display_name)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (world_key) DO NOTHING",
+    )
+    .bind(world_id)
+    .bind(world_key)
+    .bind(display_name)
+    .execute(&state.pool)
+    .await?;
+    if inserted.rows_affected() == 0 {
+        return Ok(WorldOutcome::Conflict(format!(
+            "a world with key {} already exists",
+            world_key.unwrap_or_default()
+        )));
+    }
+
+    Ok(match fetch_world_summary(state, world_id).await? {
+        Some(world) => WorldOutcome::Done(world),
+        None => WorldOutcome::NotFound("world not found".to_string()),
+    })
+}
+
+async fn run_get_world(
+    state: &AppState,
+    world_id: Uuid,
+) -> Result<WorldOutcome<WorldDetailResponse>, sqlx::Error> {
+    let Some(world) = fetch_world_summary(state, world_id).await? else {
+        return Ok(WorldOutcome::NotFound("world not found".to_string()));
+    };
+
+    let export_rows = sqlx::query(
+        "SELECT
+            ev.id AS export_version_id,
+            ev.import_version_id,
+            ev.patchset_id,
+            ev.status,
+            ev.created_at::text AS created_at,
+            COALESCE(
+                ARRAY_AGG(reimport.id ORDER BY reimport.created_at) FILTER (WHERE reimport.id IS NOT NULL),
+                '{}'
+            ) AS reimported_as
+         FROM save_export_versions ev
+         JOIN save_import_versions iv ON iv.id = ev.import_version_id
+         LEFT JOIN save_import_versions reimport ON reimport.source_export_version_id = ev.id
+         WHERE iv.world_id = $1
+         GROUP BY ev.id
+         ORDER BY ev.created_at ASC, ev.id ASC",
+    )
+    .bind(world_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    let exports = export_rows
+        .into_iter()
+        .map(|row| WorldExportLineageItem {
+            export_version_id: row.get("export_version_id"),
+            import_version_id: row.get("import_version_id"),
+            patchset_id: row.get("patchset_id"),
+            status: row.get("status"),
+            created_at: row.get("created_at"),
+            reimported_as: row.get("reimported_as"),
+        })
+        .collect();
+
+    Ok(WorldOutcome::Done(WorldDetailResponse { world, exports }))
+}
+
+async fn run_update_world(
+    state: &AppState,
+    world_id: Uuid,
+    request: UpdateWorldRequest,
+) -> Result<WorldOutcome<WorldSummary>, sqlx::Error> {
+    let display_name = request.display_name.as_deref().map(str::trim);
+    if display_name.is_some_and(str::is_empty) {
+        return Ok(WorldOutcome::BadRequest(
+            "display_name must not be empty".to_string(),
+        ));
+    }
+
+    let updated = sqlx::query(
+        "UPDATE worlds
+         SET display_name = COALESCE($2, display_name),
+             archived_at = CASE
+                WHEN $3::boolean IS NULL THEN archived_at
+                WHEN $3 THEN COALESCE(archived_at, NOW())
+                ELSE NULL
+             END,
+             updated_at = NOW()
+         WHERE id = $1",
+    )
+    .bind(world_id)
+    .bind(display_name)
+    .bind(request.archived)
+    .execute(&state.pool)
+    .await?;
+    if updated.rows_affected() == 0 {
+        return Ok(WorldOutcome::NotFound("world not found".to_string()));
+    }
+
+    Ok(match fetch_world_summary(state, world_id).await? {
+        Some(world) => WorldOutcome::Done(world),
+        None => WorldOutcome::NotFound("world not found".to_string()),
+    })
+}
+
+async fn run_assign_import_world(
+    state: &AppState,
+    import_version_id: Uuid,
+    world_id: Uuid,
+) -> Result<WorldOutcome<ImportWorldAssignment>, sqlx::Error> {
+    let archived_at: Option<Option<String>> =
+        sqlx::query_scalar("SELECT archived_at::text FROM worlds WHERE id = $1")
+            .bind(world_id)
+            .fetch_optional(&state.pool)
+            .await?;
+    match archived_at {
+        None => return Ok(WorldOutcome::NotFound("world not found".to_string())),
+        Some(Some(_)) => {
+            return Ok(WorldOutcome::Conflict(
+                "world is archived; unarchive it before attaching imports".to_string(),
+            ));
+        }
+        Some(None) => {}
+    }
+
+    let updated = sqlx::query("UPDATE save_import_versions SET world_id = $2 WHERE id = $1")
+        .bind(import_version_id)
+        .bind(world_id)
+        .execute(&state.pool)
+        .await?;
+    if updated.rows_affected() == 0 {
+        return Ok(WorldOutcome::NotFound(
+            "import version not found".to_string(),
+        ));
+    }
+
+    Ok(WorldOutcome::Done(ImportWorldAssignment {
+        import_version_id,
+        world_id,
+    }))
+}
+
+pub async fn get_pal_history(
+    State(state): State<AppState>,
+    Path((world_id, pal_instance_id)): Path<(Uuid, String)>,
+) -> impl IntoResponse {
+    match run_get_pal_history(&state, world_id, &pal_instance_id).await {
+        Ok(Some(response)) => (StatusCode::OK, Json(response)).into_response(),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "pal not found in any ready import of this world".to_string(),
+            }),
+        )
+            .into_response(),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("failed to load pal history: {}", error),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+async fn run_get_pal_history(
+    state: &AppState,
+    world_id: Uuid,
+    pal_instance_id: &str,
+) -> Result<Option<PalHistoryResponse>, sqlx::Error> {
+    let pal_instance_id = normalize_guid(pal_instance_id);
+    let rows = sqlx::query(
+        "SELECT
+            iv.id AS import_version_id,
+            iv.created_at::text AS imported_at,
+            pp.species_id,
+            pp.nickname,
+            pp.owner_player_uid,
+            pp.level,
+            pp.exp,
+            pp.rank,
+            pp.passive_skill_ids
+         FROM planner_pals pp
+         JOIN save_import_versions iv ON iv.id = pp.import_version_id
+         WHERE iv.world_id = $1
+           AND iv.status = 'ready'
+           AND pp.pal_instance_id = $2
+         ORDER BY iv.created_at ASC, iv.id ASC",
+    )
+    .bind(world_id)
+    .bind(&pal_instance_id)
+    .fetch_all(&state.pool)
+    .await?;
+
+    if rows.is_empty() {
+        return Ok(None);
+    }
+
+    let entries = rows
+        .into_iter()
+        .map(|row| {
+            let passive_skill_ids: Value = row.get("passive_skill_ids");
+            PalHistoryEntry {
+                import_version_id: row.get("import_version_id"),
+                imported_at: row.get("imported_at"),
+                species_id: row.get("species_id"),
+                nickname: row.get("nickname"),
+                owner_player_uid: row.get("owner_player_uid"),
+                level: row.get("level"),
+                exp: row.get("exp"),
+                rank: row.get("rank"),
+                passive_skill_ids: serde_json::from_value(passive_skill_ids).unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    Ok(Some(PalHistoryResponse {
+        world_id,
+        pal_instance_id,
+        entries,
+    }))
+}
@@ -2,12 +2,16 @@ use crate::AppState;
 use crate::api::handlers;
 use axum::{
     Router,
-    routing::{get, post},
+    routing::{get, post, put},
 };
 
 pub fn router(state: AppState) -> Router {
     let api_v1 = Router::new()
         .route("/save/import-zip", post(handlers::import_zip::import_zip))
+        .route(
+            "/save/import-directory",
+            post(handlers::import_directory::import_directory),
+        )
         .route(
             "/save/import-versions",
             get(handlers::import_versions::list_import_versions),
@@ -20,9 +24,95 @@ pub fn router(state: AppState) -> Router {
             "/save/import-versions/{id}/events",
             get(handlers::import_versions::stream_import_version_events),
         )
+        .route(
+            "/save/import-versions/{id}/cancel",
+            post(handlers::import_versions::cancel_import_version),
+        )
+        .route(
+            "/save/import-versions/{id}/reprocess",
+            post(handlers::import_versions::reprocess_import_version),
+        )
+        .route(
+            "/save/import-versions/{id}/world",
+            put(handlers::worlds::assign_import_world),
+        )
+        .route(
+            "/save/import-versions/{id}/diff/{other_id}",
+            get(handlers::import_diff::diff_import_versions),
+        )
         .route(
             "/save/import-versions/{id}/normalized",
             get(handlers::import_versions::get_normalized),
+        )
+        .route(
+            "/save/import-versions/{id}/world-option",
+            get(handlers::world_option::get_world_option),
+        )
+        .route(
+            "/save/import-versions/{id}/world-option/rebuild",
+            post(handlers::world_option::rebuild_world_option_file),
+        )
+        .route(
+            "/world-options/from-ini",
+            post(handlers::world_option::world_settings_from_ini),
+        )
+        .route(
+            "/world-options/to-ini",
+            post(handlers::world_option::world_settings_to_ini),
+        )
+        .route(
+            "/worlds",
+            get(handlers::worlds::list_worlds).post(handlers::worlds::create_world),
+        )
+        .route(
+            "/worlds/{id}",
+            get(handlers::worlds::get_world).patch(handlers::worlds::update_world),
+        )
+        .route(
+            "/save/import-versions/{id}/patch-operations/validate",
+            post(handlers::patches::validate_patch_operations),
+        )
+        .route(
+            "/save/import-versions/{id}/bases/{base_id}/production",
+            get(handlers::production::get_base_production),
+        )
+        .route(
+            "/save/import-versions/{id}/pal-stats",
+            get(handlers::stats::get_pal_stats),
+        )
+        .route("/planner/solve", post(handlers::planner::solve_planner))
+        .route(
+            "/save/import-versions/{id}/players/{player_uid}/breeding-path",
+            get(handlers::breeding::get_breeding_path),
+        )
+        .route(
+            "/breeding/offspring",
+            get(handlers::breeding::get_breeding_offspring),
+        )
+        .route(
+            "/breeding/parents/{species_id}",
+            get(handlers::breeding::get_breeding_parents),
+        )
+        .route(
+            "/breeding/passive-inheritance",
+            post(handlers::breeding::get_passive_inheritance),
+        )
+        .route(
+            "/worlds/{id}/pals/{instance_id}/history",
+            get(handlers::worlds::get_pal_history),
+        )
+        .route("/catalog/pals", get(handlers::catalog::list_catalog_pals))
+        .route(
+            "/catalog/pals/{character_id}",
+            get(handlers::catalog::get_catalog_pal),
+        )
+        .route(
+            "/catalog/passives",
+            get(handlers::catalog::list_catalog_passives),
+        )
+        .route(
+            "/catalog/skills",
+            get(handlers::catalog::list_catalog_skills),
         );
 
     Router::new()
@@ -0,0 +1,1660 @@
+//! Extracts the paldeck/paldb snapshots in `data/raw` into versioned JSON
+//! datasets under `data/json`.
+//!
+//! Usage: `extract_game_data [raw_dir] [out_dir]`. Every dataset file carries
+//! `schema_version` and the sha256 of each source page, and `manifest.json`
+//! lists them all. Any page whose structure no longer matches what the
+//! extractor expects fails the whole run instead of writing partial data.
+
+use anyhow::{Context, Result, anyhow, bail, ensure};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever a dataset record shape changes.
+const SCHEMA_VERSION: u32 = 2;
+
+const PALS_PAGE: &str = "__paldeck_pals.html";
+const BREEDING_PAGE: &str = "__paldeck_breeding.html";
+const ITEMS_PAGE: &str = "__paldeck_items.html";
+const SKILLS_PAGE: &str = "__paldeck_skills.html";
+const BUILDINGS_PAGE: &str = "__paldeck_buildings.html";
+const TECHNOLOGY_PAGE: &str = "__paldeck_technology.html";
+const PASSIVE_SKILLS_PAGE: &str = "__paldb_passive_skills.html";
+const WORK_SUITABILITY_PAGE: &str = "__paldb_work_suitability.html";
+const PRODUCTION_FACILITY_PAGES: [&str; 2] =
+    ["__paldb_stone_pit.html", "__paldb_wheat_plantation.html"];
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    let data_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("data");
+    let raw_dir = args
+        .get(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| data_root.join("raw"));
+    let out_dir = args
+        .get(2)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| data_root.join("json"));
+
+    let pals_page = RawPage::read(&raw_dir, PALS_PAGE)?;
+    let breeding_page = RawPage::read(&raw_dir, BREEDING_PAGE)?;
+    let items_page = RawPage::read(&raw_dir, ITEMS_PAGE)?;
+    let skills_page = RawPage::read(&raw_dir, SKILLS_PAGE)?;
+    let buildings_page = RawPage::read(&raw_dir, BUILDINGS_PAGE)?;
+    let technology_page = RawPage::read(&raw_dir, TECHNOLOGY_PAGE)?;
+    let passive_skills_page = RawPage::read(&raw_dir, PASSIVE_SKILLS_PAGE)?;
+    let work_suitability_page = RawPage::read(&raw_dir, WORK_SUITABILITY_PAGE)?;
+    let facility_pages = PRODUCTION_FACILITY_PAGES
+        .iter()
+        .map(|file_name| RawPage::read(&raw_dir, file_name))
+        .collect::<Result<Vec<_>>>()?;
+
+    let source_pals: Vec<SourcePal> = pals_page.flight_prop("initialPals")?;
+    let vocabulary = Vocabulary::from_pals(&source_pals)?;
+    let pals = extract_pals(&source_pals, &vocabulary)?;
+    let breeding = extract_breeding(&breeding_page, &source_pals)?;
+    let items = extract_items(&items_page)?;
+    let item_ids_by_name = item_ids_by_name(&items);
+    let active_skills = extract_active_skills(&skills_page, &vocabulary)?;
+    let buildings = extract_buildings(&buildings_page)?;
+    let technologies = extract_technologies(&technology_page)?;
+    let mut passive_skills = extract_passive_skills(&passive_skills_page)?;
+    assign_passive_ids(&passive_skills_page, &mut passive_skills, &items)?;
+    let work_priorities = extract_work_priorities(&work_suitability_page, &vocabulary)?;
+    let production_facilities = facility_pages
+        .iter()
+        .map(|page| extract_production_facility(page, &vocabulary, &item_ids_by_name))
+        .collect::<Result<Vec<_>>>()?;
+
+    let datasets = vec![
+        Dataset::new("pals", &[&pals_page], &pals)?,
+        Dataset::new("breeding", &[&breeding_page, &pals_page], &breeding)?,
+        Dataset::new("items", &[&items_page], &items)?,
+        Dataset::new("active_skills", &[&skills_page, &pals_page], &active_skills)?,
+        Dataset::new("buildings", &[&buildings_page], &buildings)?,
+        Dataset::new("technologies", &[&technology_page], &technologies)?,
+        Dataset::new(
+            "passive_skills",
+            &[&passive_skills_page, &items_page],
+            &passive_skills,
+        )?,
+        Dataset::new(
+            "work_priorities",
+            &[&work_suitability_page, &pals_page],
+            &work_priorities,
+        )?,
+        Dataset::new(
+            "production_facilities",
+            &facility_pages
+                .iter()
+                .chain([&pals_page, &items_page])
+                .collect::<Vec<_>>(),
+            &production_facilities,
+        )?,
+    ];
+
+    std::fs::create_dir_all(&out_dir)
+        .with_context(|| format!("failed to create {}", out_dir.display()))?;
+    let mut manifest = Vec::with_capacity(datasets.len());
+    for dataset in &datasets {
+        let file_name = format!("{}.json", dataset.dataset);
+        write_json(&out_dir.join(&file_name), dataset)?;
+        println!("{} => {} records", file_name, dataset.record_count);
+        manifest.push(ManifestEntry {
+            dataset: dataset.dataset,
+            file: file_name,
+            record_count: dataset.record_count,
+            sources: dataset.sources.clone(),
+        });
+    }
+    write_json(
+        &out_dir.join("manifest.json"),
+        &Manifest {
+            schema_version: SCHEMA_VERSION,
+            datasets: manifest,
+        },
+    )?;
+    Ok(())
+}
+
+struct RawPage {
+    file_name: String,
+    html: String,
+    sha256: String,
+}
+
+impl RawPage {
+    fn read(raw_dir: &Path, file_name: &str) -> Result<Self> {
+        let path = raw_dir.join(file_name);
+        let bytes =
+            std::fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+        let mut hasher = Sha256::new();
+        hasher.update(&bytes);
+        let sha256 = format!("{:x}", hasher.finalize());
+        let html = String::from_utf8(bytes)
+            .with_context(|| format!("{} is not valid UTF-8", file_name))?;
+        Ok(Self {
+            file_name: file_name.to_string(),
+            html,
+            sha256,
+        })
+    }
+
+    /// Reassembles the Next.js flight payload from `self.__next_f.push`
+    /// scripts and deserializes the first component prop named `prop`.
+    fn flight_prop<T: DeserializeOwned>(&self, prop: &str) -> Result<T> {
+        const PUSH_PREFIX: &str = "self.__next_f.push(";
+        let mut payload = String::new();
+        let mut rest = self.html.as_str();
+        while let Some(start) = rest.find(PUSH_PREFIX) {
+            let after = &rest[start + PUSH_PREFIX.len()..];
+            let end = after
+                .find(")</script>")
+                .ok_or_else(|| self.drift("unterminated __next_f.push script"))?;
+            let chunk: Value = serde_json::from_str(&after[..end])
+                .map_err(|error| self.drift(&format!("invalid __next_f.push chunk: {}", error)))?;
+            if chunk.get(0).and_then(Value::as_i64) == Some(1)
+                && let Some(text) = chunk.get(1).and_then(Value::as_str)
+            {
+                payload.push_str(text);
+            }
+            rest = &after[end..];
+        }
+        ensure!(
+            !payload.is_empty(),
+            self.drift("no Next.js flight payload found")
+        );
+
+        for row in payload.split('\n') {
+            let Some((_, body)) = row.split_once(':') else {
+                continue;
+            };
+            if !body.starts_with('[') && !body.starts_with('{') {
+                continue;
+            }
+            let value: Value = serde_json::from_str(body)
+                .map_err(|error| self.drift(&format!("invalid flight row: {}", error)))?;
+            if let Some(found) = find_prop(&value, prop) {
+                return serde_json::from_value(found.clone())
+                    .map_err(|error| self.drift(&format!("`{}` changed shape: {}", prop, error)));
+            }
+        }
+        Err(self.drift(&format!("no `{}` prop in flight payload", prop)))
+    }
+
+    fn drift(&self, message: &str) -> anyhow::Error {
+        anyhow!("{}: page structure changed: {}", self.file_name, message)
+    }
+}
+
+fn find_prop<'a>(value: &'a Value, prop: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(map) => map
+            .get(prop)
+            .or_else(|| map.values().find_map(|child| find_prop(child, prop))),
+        Value::Array(values) => values.iter().find_map(|child| find_prop(child, prop)),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DatasetSource {
+    file: String,
+    sha256: String,
+}
+
+#[derive(Serialize)]
+struct Dataset {
+    dataset: &'static str,
+    schema_version: u32,
+    sources: Vec<DatasetSource>,
+    record_count: usize,
+    records: Vec<Value>,
+}
+
+impl Dataset {
+    fn new<T: Serialize>(dataset: &'static str, pages: &[&RawPage], records: &[T]) -> Result<Self> {
+        ensure!(!records.is_empty(), "dataset {} has no records", dataset);
+        let records = records
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("failed to serialize dataset {}", dataset))?;
+        Ok(Self {
+            dataset,
+            schema_version: SCHEMA_VERSION,
+            sources: pages
+                .iter()
+                .map(|page| DatasetSource {
+                    file: page.file_name.clone(),
+                    sha256: page.sha256.clone(),
+                })
+                .collect(),
+            record_count: records.len(),
+            records,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct ManifestEntry {
+    dataset: &'static str,
+    file: String,
+    record_count: usize,
+    sources: Vec<DatasetSource>,
+}
+
+#[derive(Serialize)]
+struct Manifest {
+    schema_version: u32,
+    datasets: Vec<ManifestEntry>,
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let mut text = serde_json::to_string_pretty(value)?;
+    text.push('\n');
+    std::fs::write(path, text).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Display-name to internal-id maps taken from the pal records. The pages
+/// mix both spellings (`Ground`/`Earth`, `Handiwork`/`Handcraft`).
+struct Vocabulary {
+    elements: HashMap<String, String>,
+    work_types: HashMap<String, String>,
+}
+
+impl Vocabulary {
+    fn from_pals(pals: &[SourcePal]) -> Result<Self> {
+        let mut elements = HashMap::new();
+        let mut work_types = HashMap::new();
+        for pal in pals {
+            for (key, element) in &pal.elements {
+                elements.insert(key.clone(), key.clone());
+                elements.insert(element.name.clone(), key.clone());
+            }
+            for (name, work) in &pal.work_suitability {
+                let asset_name = work.asset_name.clone().ok_or_else(|| {
+                    anyhow!("{}: work suitability {} has no asset_name", PALS_PAGE, name)
+                })?;
+                work_types.insert(asset_name.clone(), asset_name.clone());
+                work_types.insert(name.clone(), asset_name);
+            }
+        }
+        ensure!(
+            !elements.is_empty() && !work_types.is_empty(),
+            "{}: page structure changed: no elements or work suitabilities",
+            PALS_PAGE
+        );
+        Ok(Self {
+            elements,
+            work_types,
+        })
+    }
+
+    /// Maps an element name to its internal key; `None` is no element.
+    fn element(&self, value: &str) -> Result<Option<String>> {
+        if value == "None" || value.is_empty() {
+            return Ok(None);
+        }
+        self.elements
+            .get(value)
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| anyhow!("unknown element `{}`", value))
+    }
+
+    fn work_type(&self, value: &str) -> Option<String> {
+        self.work_types.get(value).cloned()
+    }
+}
+
+/// Strips an Unreal enum prefix (`EPalItemTypeA::Material` -> `Material`);
+/// `None` values become absent.
+fn enum_value(value: &str) -> Option<String> {
+    let value = value.rsplit("::").next().unwrap_or(value);
+    (value != "None" && !value.is_empty()).then(|| value.to_string())
+}
+
+#[derive(Deserialize)]
+struct SourcePal {
+    name: String,
+    #[serde(rename = "type")]
+    species_id: String,
+    dexkey: Option<String>,
+    description: Option<String>,
+    rarity: i64,
+    size: String,
+    genus: Option<String>,
+    #[serde(default)]
+    is_boss: bool,
+    elements: BTreeMap<String, SourceElement>,
+    stats: SourcePalStats,
+    food: Option<i64>,
+    exp_ratio: Option<f64>,
+    price: Option<i64>,
+    breedingpower: i64,
+    ignorebreeding: bool,
+    maleprobability: f64,
+    work_suitability: BTreeMap<String, SourceWorkSuitability>,
+    partner_skill: Option<SourcePartnerSkill>,
+    skills: Vec<SourcePalSkill>,
+    #[serde(default)]
+    drops: Vec<SourceDrop>,
+}
+
+#[derive(Deserialize)]
+struct SourceElement {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct SourcePalStats {
+    hp: i64,
+    defense: i64,
+    stamina: i64,
+    meleeattack: i64,
+    rangedattack: i64,
+    support: i64,
+    craftspeed: i64,
+}
+
+#[derive(Deserialize)]
+struct SourceWorkSuitability {
+    asset_name: Option<String>,
+    level: i64,
+}
+
+#[derive(Deserialize)]
+struct SourcePartnerSkill {
+    name: Option<String>,
+    description: Option<String>,
+    unlock: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SourcePalSkill {
+    level: i64,
+    waza_type: String,
+}
+
+#[derive(Deserialize)]
+struct SourceDrop {
+    item_dev_name: String,
+    drop_rate: f64,
+    min: i64,
+    max: i64,
+}
+
+#[derive(Serialize)]
+struct PalRecord {
+    species_id: String,
+    name: String,
+    paldeck_no: Option<String>,
+    description: Option<String>,
+    rarity: i64,
+    size: String,
+    genus: Option<String>,
+    is_boss: bool,
+    elements: Vec<String>,
+    stats: PalStats,
+    food: Option<i64>,
+    exp_ratio: Option<f64>,
+    price: Option<i64>,
+    breeding_power: i64,
+    ignore_breeding: bool,
+    male_probability: f64,
+    /// Base work suitability level keyed by internal work type
+    /// (`EPalWorkSuitability` value without prefix).
+    work_suitability: BTreeMap<String, i64>,
+    partner_skill: Option<PartnerSkill>,
+    learnset: Vec<LearnedSkill>,
+    drops: Vec<PalDrop>,
+}
+
+#[derive(Serialize)]
+struct PalStats {
+    hp: i64,
+    defense: i64,
+    stamina: i64,
+    melee_attack: i64,
+    ranged_attack: i64,
+    support: i64,
+    craft_speed: i64,
+}
+
+#[derive(Serialize)]
+struct PartnerSkill {
+    name: Option<String>,
+    description: Option<String>,
+    unlock_item: Option<String>,
+}
+
+#[derive(Serialize)]
+struct LearnedSkill {
+    waza_id: String,
+    level: i64,
+}
+
+#[derive(Serialize)]
+struct PalDrop {
+    item_id: String,
+    drop_rate: f64,
+    min: i64,
+    max: i64,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
+fn extract_pals(source: &[SourcePal], vocabulary: &Vocabulary) -> Result<Vec<PalRecord>> {
+    let mut records = Vec::with_capacity(source.len());
+    for pal in source {
+        let mut work_suitability = BTreeMap::new();
+        for (name, work) in &pal.work_suitability {
+            let work_type = vocabulary
+                .work_type(name)
+                .ok_or_else(|| anyhow!("{}: unknown work suitability {}", PALS_PAGE, name))?;
+            work_suitability.insert(work_type, work.level);
+        }
+        records.push(PalRecord {
+            species_id: pal.species_id.clone(),
+            name: pal.name.clone(),
+            paldeck_no: non_empty(pal.dexkey.clone()),
+            description: non_empty(pal.description.clone()),
+            rarity: pal.rarity,
+            size: pal.size.clone(),
+            genus: non_empty(pal.genus.clone()),
+            is_boss: pal.is_boss,
+            elements: pal.elements.keys().cloned().collect(),
+            stats: PalStats {
+                hp: pal.stats.hp,
+                defense: pal.stats.defense,
+                stamina: pal.stats.stamina,
+                melee_attack: pal.stats.meleeattack,
+                ranged_attack: pal.stats.rangedattack,
+                support: pal.stats.support,
+                craft_speed: pal.stats.craftspeed,
+            },
+            food: pal.food,
+            exp_ratio: pal.exp_ratio,
+            price: pal.price,
+            breeding_power: pal.breedingpower,
+            ignore_breeding: pal.ignorebreeding,
+            male_probability: pal.maleprobability,
+            work_suitability,
+            partner_skill: pal.partner_skill.as_ref().map(|skill| PartnerSkill {
+                name: non_empty(skill.name.clone()),
+                description: non_empty(skill.description.clone()),
+                unlock_item: non_empty(skill.unlock.clone()),
+            }),
+            learnset: pal
+                .skills
+                .iter()
+                .map(|skill| LearnedSkill {
+                    waza_id: skill.waza_type.clone(),
+                    level: skill.level,
+                })
+                .collect(),
+            drops: pal
+                .drops
+                .iter()
+                .map(|drop| PalDrop {
+                    item_id: drop.item_dev_name.clone(),
+                    drop_rate: drop.drop_rate,
+                    min: drop.min,
+                    max: drop.max,
+                })
+                .collect(),
+        });
+    }
+    records.sort_by(|left, right| left.species_id.cmp(&right.species_id));
+    ensure_unique(
+        PALS_PAGE,
+        records.iter().map(|record| record.species_id.as_str()),
+    )?;
+    Ok(records)
+}
+
+fn ensure_unique<'a>(file_name: &str, ids: impl Iterator<Item = &'a str>) -> Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    for id in ids {
+        ensure!(seen.insert(id), "{}: duplicate record id {}", file_name, id);
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct SourceBreedingPal {
+    name: String,
+    #[serde(rename = "type")]
+    species_id: String,
+}
+
+#[derive(Deserialize)]
+struct SourceBreedingCombo {
+    parent1: String,
+    parent2: String,
+    child: String,
+}
+
+#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord)]
+struct BreedingRecord {
+    parent_a: String,
+    parent_b: String,
+    child: String,
+}
+
+/// Breeding combinations reference pals by display name; they are resolved
+/// to species ids through the breeding page's own pal list, falling back to
+/// the paldeck pal list for pals missing from it.
+fn extract_breeding(page: &RawPage, pals: &[SourcePal]) -> Result<Vec<BreedingRecord>> {
+    let breeding_pals: Vec<SourceBreedingPal> = page.flight_prop("initialPals")?;
+    let combos: Vec<SourceBreedingCombo> = page.flight_prop("initialBreedingData")?;
+
+    let mut species_by_name = HashMap::new();
+    for (name, species_id) in pals
+        .iter()
+        .map(|pal| (&pal.name, &pal.species_id))
+        .chain(breeding_pals.iter().map(|pal| (&pal.name, &pal.species_id)))
+    {
+        if let Some(existing) = species_by_name.insert(name.clone(), species_id.clone()) {
+            ensure!(
+                existing == *species_id,
+                "{}: pal name {} maps to both {} and {}",
+                page.file_name,
+                name,
+                existing,
+                species_id
+            );
+        }
+    }
+    let resolve = |name: &str| {
+        species_by_name
+            .get(name)
+            .cloned()
+            .ok_or_else(|| page.drift(&format!("breeding pal {} is not a known pal", name)))
+    };
+
+    // The page lists unique combinations first, each followed by its child's
+    // self-breed, then the breeding-power table with exactly one entry per
+    // pair. So a pair listed with several children has its unique children
+    // first and its power result last. A unique combination overrides the
+    // power result in game, so the last child is dropped. Two unique children
+    // for one pair depend on which parent is the female; the page does not
+    // say which. The game has no pair with more than two.
+    let mut children_by_pair: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for combo in &combos {
+        let mut parents = [resolve(&combo.parent1)?, resolve(&combo.parent2)?];
+        parents.sort();
+        let [parent_a, parent_b] = parents;
+        let child = resolve(&combo.child)?;
+        let children = children_by_pair.entry((parent_a, parent_b)).or_default();
+        if !children.contains(&child) {
+            children.push(child);
+        }
+    }
+
+    let mut records = Vec::with_capacity(children_by_pair.len());
+    for ((parent_a, parent_b), mut children) in children_by_pair {
+        if children.len() > 1 {
+            children.pop();
+        }
+        ensure!(
+            children.len() <= 2,
+            page.drift(&format!(
+                "{} x {} lists {} unique children besides its breeding-power result",
+                parent_a,
+                parent_b,
+                children.len()
+            ))
+        );
+        for child in children {
+            records.push(BreedingRecord {
+                parent_a: parent_a.clone(),
+                parent_b: parent_b.clone(),
+                child,
+            });
+        }
+    }
+    records.sort();
+    Ok(records)
+}
+
+#[derive(Deserialize)]
+struct SourceItem {
+    name: String,
+    description: Option<String>,
+    asset: String,
+    category: String,
+    type_a: String,
+    type_b: String,
+    rank: i64,
+    rarity: i64,
+    stack: i64,
+    weight: f64,
+    price: i64,
+    restore_satiety: f64,
+    restore_concentration: f64,
+    restore_sanity: f64,
+    restore_health: f64,
+    durability: i64,
+    element_type: String,
+    physical_attack_value: f64,
+    hp_value: f64,
+    physical_defense_value: f64,
+    shield_value: f64,
+    magic_attack_value: f64,
+    magic_defense_value: f64,
+    waza_id: String,
+    passive_skills: Vec<SourceItemPassive>,
+}
+
+#[derive(Deserialize)]
+struct SourceItemPassive {
+    skill: String,
+    desc: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ItemRecord {
+    item_id: String,
+    name: String,
+    description: Option<String>,
+    category: String,
+    type_a: Option<String>,
+    type_b: Option<String>,
+    rank: i64,
+    rarity: i64,
+    max_stack: i64,
+    weight: f64,
+    price: i64,
+    restore_satiety: f64,
+    restore_sanity: f64,
+    restore_health: f64,
+    restore_concentration: f64,
+    durability: i64,
+    element: Option<String>,
+    attack: f64,
+    defense: f64,
+    hp: f64,
+    shield: f64,
+    magic_attack: f64,
+    magic_defense: f64,
+    waza_id: Option<String>,
+    passive_skills: Vec<ItemPassive>,
+}
+
+#[derive(Serialize)]
+struct ItemPassive {
+    passive_id: String,
+    description: Option<String>,
+}
+
+fn extract_items(page: &RawPage) -> Result<Vec<ItemRecord>> {
+    let source: Vec<SourceItem> = page.flight_prop("initialItems")?;
+    let mut records: Vec<ItemRecord> = source
+        .into_iter()
+        .map(|item| ItemRecord {
+            item_id: item.asset,
+            name: item.name,
+            description: non_empty(item.description),
+            category: item.category,
+            type_a: enum_value(&item.type_a),
+            type_b: enum_value(&item.type_b),
+            rank: item.rank,
+            rarity: item.rarity,
+            max_stack: item.stack,
+            weight: item.weight,
+            price: item.price,
+            restore_satiety: item.restore_satiety,
+            restore_sanity: item.restore_sanity,
+            restore_health: item.restore_health,
+            restore_concentration: item.restore_concentration,
+            durability: item.durability,
+            element: enum_value(&item.element_type),
+            attack: item.physical_attack_value,
+            defense: item.physical_defense_value,
+            hp: item.hp_value,
+            shield: item.shield_value,
+            magic_attack: item.magic_attack_value,
+            magic_defense: item.magic_defense_value,
+            waza_id: enum_value(&item.waza_id),
+            passive_skills: item
+                .passive_skills
+                .into_iter()
+                .map(|passive| ItemPassive {
+                    passive_id: passive.skill,
+                    description: non_empty(passive.desc),
+                })
+                .collect(),
+        })
+        .collect();
+    records.sort_by(|left, right| left.item_id.cmp(&right.item_id));
+    ensure_unique(
+        &page.file_name,
+        records.iter().map(|record| record.item_id.as_str()),
+    )?;
+    Ok(records)
+}
+
+/// Item display name to id, skipping names shared by several items.
+fn item_ids_by_name(items: &[ItemRecord]) -> HashMap<String, String> {
+    let mut ids: HashMap<String, Option<String>> = HashMap::new();
+    for item in items {
+        ids.entry(item.name.clone())
+            .and_modify(|id| *id = None)
+            .or_insert_with(|| Some(item.item_id.clone()));
+    }
+    ids.into_iter()
+        .filter_map(|(name, id)| id.map(|id| (name, id)))
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct SourceSkill {
+    name: String,
+    description: Option<String>,
+    waza_type: String,
+    element: String,
+    category: String,
+    power: i64,
+    min_range: i64,
+    max_range: i64,
+    cool_time: f64,
+    effects: Vec<SourceSkillEffect>,
+}
+
+#[derive(Deserialize)]
+struct SourceSkillEffect {
+    #[serde(rename = "type")]
+    kind: String,
+    value: f64,
+}
+
+#[derive(Serialize)]
+struct ActiveSkillRecord {
+    waza_id: String,
+    name: Option<String>,
+    description: Option<String>,
+    element: Option<String>,
+    category: String,
+    power: i64,
+    min_range: i64,
+    max_range: i64,
+    cool_time: f64,
+    effects: Vec<ActiveSkillEffect>,
+}
+
+#[derive(Serialize)]
+struct ActiveSkillEffect {
+    kind: String,
+    value: f64,
+}
+
+fn extract_active_skills(
+    page: &RawPage,
+    vocabulary: &Vocabulary,
+) -> Result<Vec<ActiveSkillRecord>> {
+    let source: Vec<SourceSkill> = page.flight_prop("initialSkills")?;
+    let mut records = Vec::with_capacity(source.len());
+    for skill in source {
+        // Untranslated skills are listed as `en Text (Id)` or
+        // `Unknown Skill (Id)`; those carry no usable display name.
+        let name = (!skill.name.starts_with("en Text (")
+            && !skill.name.starts_with("Unknown Skill ("))
+        .then_some(skill.name);
+        records.push(ActiveSkillRecord {
+            element: vocabulary
+                .element(&skill.element)
+                .with_context(|| format!("{}: skill {}", page.file_name, skill.waza_type))?,
+            waza_id: skill.waza_type,
+            name,
+            description: non_empty(skill.description),
+            category: skill.category,
+            power: skill.power,
+            min_range: skill.min_range,
+            max_range: skill.max_range,
+            cool_time: skill.cool_time,
+            effects: skill
+                .effects
+                .into_iter()
+                .map(|effect| ActiveSkillEffect {
+                    kind: effect.kind,
+                    value: effect.value,
+                })
+                .collect(),
+        });
+    }
+    records.sort_by(|left, right| left.waza_id.cmp(&right.waza_id));
+    ensure_unique(
+        &page.file_name,
+        records.iter().map(|record| record.waza_id.as_str()),
+    )?;
+    Ok(records)
+}
+
+#[derive(Deserialize)]
+struct SourceBuilding {
+    name: String,
+    dev_name: String,
+    description: Option<String>,
+    type_a: String,
+    type_b: String,
+    hp: i64,
+    defense: i64,
+    materials: Vec<SourceBuildingMaterial>,
+    exprate: f64,
+    consumeenergyrate: f64,
+    rank: i64,
+    work_suitability: String,
+    work_suitability_rank: i64,
+}
+
+#[derive(Deserialize)]
+struct SourceBuildingMaterial {
+    assetname: String,
+    count: i64,
+}
+
+#[derive(Serialize)]
+struct BuildingRecord {
+    building_id: String,
+    name: String,
+    description: Option<String>,
+    type_a: Option<String>,
+    type_b: Option<String>,
+    rank: i64,
+    hp: i64,
+    defense: i64,
+    build_exp_rate: f64,
+    consume_energy_rate: f64,
+    /// Work type a pal needs to operate this building, if any.
+    work_suitability: Option<String>,
+    work_suitability_rank: i64,
+    materials: Vec<ItemCount>,
+}
+
+#[derive(Serialize)]
+struct ItemCount {
+    item_id: String,
+    count: i64,
+}
+
+fn extract_buildings(page: &RawPage) -> Result<Vec<BuildingRecord>> {
+    let source: Vec<SourceBuilding> = page.flight_prop("initialBuildings")?;
+    let mut records: Vec<BuildingRecord> = source
+        .into_iter()
+        .map(|building| BuildingRecord {
+            building_id: building.dev_name,
+            name: building.name,
+            description: non_empty(building.description),
+            type_a: enum_value(&building.type_a),
+            type_b: enum_value(&building.type_b),
+            rank: building.rank,
+            hp: building.hp,
+            defense: building.defense,
+            build_exp_rate: building.exprate,
+            consume_energy_rate: building.consumeenergyrate,
+            work_suitability: enum_value(&building.work_suitability),
+            work_suitability_rank: building.work_suitability_rank,
+            materials: building
+                .materials
+                .into_iter()
+                .map(|material| ItemCount {
+                    item_id: material.assetname,
+                    count: material.count,
+                })
+                .collect(),
+        })
+        .collect();
+    records.sort_by(|left, right| left.building_id.cmp(&right.building_id));
+    ensure_unique(
+        &page.file_name,
+        records.iter().map(|record| record.building_id.as_str()),
+    )?;
+    Ok(records)
+}
+
+#[derive(Deserialize)]
+struct SourceTechnology {
+    key: String,
+    name: String,
+    description: Option<String>,
+    unlock_build_objects: Vec<String>,
+    unlock_item_recipes: Vec<String>,
+    require_technology: String,
+    is_boss_technology: bool,
+    cost: i64,
+}
+
+#[derive(Serialize)]
+struct TechnologyRecord {
+    technology_id: String,
+    name: String,
+    description: Option<String>,
+    unlock_level: i64,
+    cost: i64,
+    is_ancient: bool,
+    required_technology_id: Option<String>,
+    unlock_building_ids: Vec<String>,
+    unlock_item_ids: Vec<String>,
+}
+
+fn extract_technologies(page: &RawPage) -> Result<Vec<TechnologyRecord>> {
+    let tiers: BTreeMap<String, Vec<SourceTechnology>> = page.flight_prop("initialTechByTier")?;
+    let mut records = Vec::new();
+    for (level, technologies) in tiers {
+        let unlock_level: i64 = level
+            .parse()
+            .map_err(|_| page.drift(&format!("technology tier `{}` is not a level", level)))?;
+        for technology in technologies {
+            records.push(TechnologyRecord {
+                technology_id: technology.key,
+                name: technology.name,
+                description: non_empty(technology.description),
+                unlock_level,
+                cost: technology.cost,
+                is_ancient: technology.is_boss_technology,
+                required_technology_id: enum_value(&technology.require_technology),
+                unlock_building_ids: technology.unlock_build_objects,
+                unlock_item_ids: technology.unlock_item_recipes,
+            });
+        }
+    }
+    records.sort_by(|left, right| {
+        (left.unlock_level, &left.technology_id).cmp(&(right.unlock_level, &right.technology_id))
+    });
+    ensure_unique(
+        &page.file_name,
+        records.iter().map(|record| record.technology_id.as_str()),
+    )?;
+    Ok(records)
+}
+
+// paldb pages are server-rendered HTML without an embedded payload, so they
+// are read with the small helpers below against the page's fixed markup.
+
+/// Returns the text between the first `start` and the next `end`.
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let from = text.find(start)? + start.len();
+    let to = text[from..].find(end)?;
+    Some(&text[from..from + to])
+}
+
+/// Removes tags, decodes the entities paldb uses and collapses whitespace.
+fn strip_tags(fragment: &str) -> String {
+    let mut text = String::with_capacity(fragment.len());
+    let mut in_tag = false;
+    for character in fragment.chars() {
+        match character {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(character),
+            _ => {}
+        }
+    }
+    decode_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Removes `data-bs-title="..."` tooltip attributes.
+fn strip_tooltips(html: &str) -> String {
+    const ATTRIBUTE: &str = " data-bs-title=\"";
+    let mut stripped = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(ATTRIBUTE) {
+        stripped.push_str(&rest[..start]);
+        let value = &rest[start + ATTRIBUTE.len()..];
+        rest = value.find('"').map_or("", |end| &value[end + 1..]);
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// Text inside `<small>N</small>` after a tab heading, used to check that
+/// every card on the tab was parsed.
+fn tab_count(page: &RawPage, tab: &str) -> Result<usize> {
+    between(tab, "<small>", "</small>")
+        .and_then(|count| count.trim().parse().ok())
+        .ok_or_else(|| page.drift("tab heading has no record count"))
+}
+
+#[derive(Serialize)]
+struct PassiveSkillRecord {
+    /// Save id (`PassiveSkillList` value); null when no source maps it.
+    passive_id: Option<String>,
+    name: String,
+    /// `pal` for skills a pal can roll, `other` for equipment and
+    /// special-source passives.
+    pool: &'static str,
+    /// Tier shown by the rank arrow: 1 to 4, negative for harmful skills.
+    rank: i64,
+    weight: Option<i64>,
+    tags: Vec<String>,
+    description: Option<String>,
+    effects: Vec<PassiveEffect>,
+}
+
+/// Save ids of pal passives that have no implant item, taken from the
+/// reference Pal Editor's passive list. paldb does not publish save ids, so
+/// every pal-pool passive without an implant must be listed here.
+const KNOWN_PASSIVE_IDS: [(&str, &str); 55] = [
+    ("Rare", "Lucky"),
+    ("Legend", "Legend"),
+    ("Vampire", "Vampiric"),
+    ("Witch", "Siren of the Void"),
+    ("EternalFlame", "Eternal Flame"),
+    ("Invader", "Invader"),
+    ("Lunker", "Lunker"),
+    ("Savior", "Savior"),
+    ("SwimSpeed_up_3", "King of the Waves"),
+    ("Alien", "Otherworldly Cells"),
+    ("ElementBoost_Normal_2_PAL", "Celestial Emperor"),
+    ("ElementBoost_Fire_2_PAL", "Flame Emperor"),
+    ("ElementBoost_Aqua_2_PAL", "Lord of the Sea"),
+    ("ElementBoost_Leaf_2_PAL", "Spirit Emperor"),
+    ("ElementBoost_Thunder_2_PAL", "Lord of Lightning"),
+    ("ElementBoost_Ice_2_PAL", "Ice Emperor"),
+    ("ElementBoost_Earth_2_PAL", "Earth Emperor"),
+    ("ElementBoost_Dark_2_PAL", "Lord of the Underworld"),
+    ("ElementBoost_Dragon_2_PAL", "Divine Dragon"),
+    ("ElementBoost_Normal_1_PAL", "Zen Mind"),
+    ("ElementBoost_Fire_1_PAL", "Pyromaniac"),
+    ("ElementBoost_Aqua_1_PAL", "Hydromaniac"),
+    ("ElementBoost_Leaf_1_PAL", "Fragrant Foliage"),
+    ("ElementBoost_Thunder_1_PAL", "Capacitor"),
+    ("ElementBoost_Ice_1_PAL", "Coldblooded"),
+    ("ElementBoost_Earth_1_PAL", "Power of Gaia"),
+    ("ElementBoost_Dark_1_PAL", "Veil of Darkness"),
+    ("ElementBoost_Dragon_1_PAL", "Blood of the Dragon"),
+    ("ElementResist_Normal_1_PAL", "Abnormal"),
+    ("ElementResist_Fire_1_PAL", "Suntan Lover"),
+    ("ElementResist_Aqua_1_PAL", "Waterproof"),
+    ("ElementResist_Leaf_1_PAL", "Botanical Barrier"),
+    ("ElementResist_Thunder_1_PAL", "Insulated Body"),
+    ("ElementResist_Ice_1_PAL", "Heated Body"),
+    ("ElementResist_Earth_1_PAL", "Earthquake Resistant"),
+    ("ElementResist_Dark_1_PAL", "Cheery"),
+    ("ElementResist_Dragon_1_PAL", "Dragonkiller"),
+    ("CraftSpeed_down1", "Clumsy"),
+    ("CraftSpeed_down2", "Slacker"),
+    ("Deffence_down1", "Downtrodden"),
+    ("Deffence_down2", "Brittle"),
+    ("PAL_ALLAttack_down1", "Coward"),
+    ("PAL_ALLAttack_down2", "Pacifist"),
+    ("PAL_FullStomach_Up_1", "Glutton"),
+    ("PAL_FullStomach_Up_2", "Bottomless Stomach"),
+    ("PAL_Sanity_Up_1", "Unstable"),
+    ("PAL_Sanity_Up_2", "Destructive"),
+    ("CoolTimeReduction_Down_1", "Easygoing"),
+    ("Stamina_Down_1", "Sickly"),
+    ("SalePrice_Down_1", "Shabby"),
+    ("PAL_rude", "Hooligan"),
+    ("PAL_conceited", "Conceited"),
+    ("PAL_sadist", "Sadist"),
+    ("PAL_masochist", "Masochist"),
+    ("PAL_oraora", "Aggressive"),
+];
+
+/// Fills `passive_id` for pal passives from the `PalPassiveSkillChange_<id>`
+/// implant items (named `Implant: <passive name>`) and `KNOWN_PASSIVE_IDS`.
+fn assign_passive_ids(
+    page: &RawPage,
+    records: &mut [PassiveSkillRecord],
+    items: &[ItemRecord],
+) -> Result<()> {
+    let implants = items.iter().filter_map(|item| {
+        let passive_id = item.item_id.strip_prefix("PalPassiveSkillChange_")?;
+        let name = item.name.strip_prefix("Implant: ")?;
+        Some((passive_id, name))
+    });
+    for (passive_id, name) in implants.chain(KNOWN_PASSIVE_IDS) {
+        let mut matches = records
+            .iter_mut()
+            .filter(|record| record.pool == "pal" && record.name == name);
+        let record = matches
+            .next()
+            .ok_or_else(|| page.drift(&format!("no pal passive named {}", name)))?;
+        ensure!(
+            matches.next().is_none(),
+            page.drift(&format!("several pal passives are named {}", name))
+        );
+        ensure!(
+            record.passive_id.is_none(),
+            page.drift(&format!("pal passive {} has two ids", name))
+        );
+        record.passive_id = Some(passive_id.to_string());
+    }
+    if let Some(record) = records
+        .iter()
+        .find(|record| record.pool == "pal" && record.passive_id.is_none())
+    {
+        bail!(page.drift(&format!("pal passive {} has no save id", record.name)));
+    }
+    ensure_unique(
+        &page.file_name,
+        records
+            .iter()
+            .filter_map(|record| record.passive_id.as_deref()),
+    )
+}
+
+#[derive(Serialize)]
+struct PassiveEffect {
+    /// Effect label as shown by paldb, either a display name
+    /// (`Work Speed`) or an internal one (`ElementBoost_Dark`).
+    stat: String,
+    value: f64,
+    is_percent: bool,
+    target: Option<String>,
+}
+
+fn extract_passive_skills(page: &RawPage) -> Result<Vec<PassiveSkillRecord>> {
+    const TABS: [(&str, &str, &str); 2] = [
+        (
+            "pal",
+            "id=\"PalPassiveSkills\" class=\"tab-pane",
+            "id=\"PassiveSkills\" class=\"tab-pane",
+        ),
+        (
+            "other",
+            "id=\"PassiveSkills\" class=\"tab-pane",
+            "id=\"PassiveSkills_marked\" class=\"tab-pane",
+        ),
+    ];
+    const CARD: &str = "<div class=\"col\"><div class=\"border bg-dark\">";
+
+    let mut records = Vec::new();
+    for (pool, start, end) in TABS {
+        let tab = between(&page.html, start, end)
+            .ok_or_else(|| page.drift(&format!("missing {} passive skill tab", pool)))?;
+        let expected = tab_count(page, tab)?;
+        let cards: Vec<&str> = tab.split(CARD).skip(1).collect();
+        ensure!(
+            cards.len() == expected,
+            page.drift(&format!(
+                "{} passive tab lists {} skills but {} cards were found",
+                pool,
+                expected,
+                cards.len()
+            ))
+        );
+        for card in cards {
+            records.push(parse_passive_card(page, pool, card)?);
+        }
+    }
+    Ok(records)
+}
+
+fn parse_passive_card(
+    page: &RawPage,
+    pool: &'static str,
+    card: &str,
+) -> Result<PassiveSkillRecord> {
+    let rank = between(card, "passive_banner_rank", "\"")
+        .and_then(|rank| rank.parse().ok())
+        .ok_or_else(|| page.drift("passive card has no rank"))?;
+    let name = between(card, "ps-2 py-1\">", "</div>")
+        .map(strip_tags)
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| page.drift("passive card has no name"))?;
+    let tooltip = between(card, "data-bs-title=\"", "\"")
+        .map(decode_entities)
+        .ok_or_else(|| page.drift(&format!("passive {} has no tooltip", name)))?;
+    let body = between(card, "<div class=\"p-2\">", "</div>\n    </div>")
+        .ok_or_else(|| page.drift(&format!("passive {} has no body", name)))?;
+
+    let mut weight = None;
+    let mut tags = Vec::new();
+    for badge in tooltip.split("<span class=\"badge bg-primary\">").skip(1) {
+        let badge = strip_tags(badge.split("</span>").next().unwrap_or_default());
+        match badge.strip_prefix("Weight ") {
+            Some(value) => weight = value.parse().ok(),
+            None => tags.push(badge),
+        }
+    }
+
+    // Effects appear either in the tooltip or in the card body depending on
+    // the tab; the body text is only a description when it has none.
+    let mut effects = parse_passive_effects(&tooltip);
+    let body_effects = parse_passive_effects(body);
+    let description = if body_effects.is_empty() {
+        between(body, "<div>", "</div>")
+            .map(strip_tags)
+            .filter(|text| !text.is_empty())
+    } else {
+        None
+    };
+    for effect in body_effects {
+        if !effects
+            .iter()
+            .any(|existing| existing.stat == effect.stat && existing.target == effect.target)
+        {
+            effects.push(effect);
+        }
+    }
+
+    Ok(PassiveSkillRecord {
+        passive_id: None,
+        name,
+        pool,
+        rank,
+        weight,
+        tags,
+        description,
+        effects,
+    })
+}
+
+/// Parses `<div>Label <span class="positive">+10%</a> <span ...>(ToSelf)</span></div>`
+/// effect lines.
+fn parse_passive_effects(fragment: &str) -> Vec<PassiveEffect> {
+    let mut effects = Vec::new();
+    for line in fragment.split("<div>") {
+        let Some(span_at) = line
+            .find("<span class=\"positive\">")
+            .or_else(|| line.find("<span class=\"negative\">"))
+        else {
+            continue;
+        };
+        let stat = strip_tags(&line[..span_at]);
+        let Some(raw_value) = between(&line[span_at..], "\">", "</") else {
+            continue;
+        };
+        let raw_value = raw_value.trim();
+        let is_percent = raw_value.ends_with('%');
+        let Ok(value) = raw_value.trim_end_matches('%').parse::<f64>() else {
+            continue;
+        };
+        if stat.is_empty() {
+            continue;
+        }
+        effects.push(PassiveEffect {
+            stat,
+            value,
+            is_percent,
+            target: between(&line[span_at..], "(", ")").map(str::to_string),
+        });
+    }
+    effects
+}
+
+#[derive(Serialize)]
+struct WorkPriorityRecord {
+    /// Internal base-camp work id, e.g. `ProductResource_Mining`.
+    work_id: String,
+    name: String,
+    /// Lower values are picked first by base pals.
+    priority: i64,
+    work_suitability: Option<String>,
+}
+
+fn extract_work_priorities(
+    page: &RawPage,
+    vocabulary: &Vocabulary,
+) -> Result<Vec<WorkPriorityRecord>> {
+    const ENTRY: &str = "<div class=\"d-flex justify-content-between border p-2\">";
+    let section = between(&page.html, ">Work Priority</h2>", "<footer")
+        .ok_or_else(|| page.drift("missing Work Priority card"))?;
+
+    let mut records = Vec::new();
+    for entry in section.split(ENTRY).skip(1) {
+        let work_id = between(entry, "<small>(", ")</small>")
+            .ok_or_else(|| page.drift("work priority entry has no work id"))?;
+        let name = strip_tags(entry.split("<small>").next().unwrap_or_default());
+        let priority = between(entry, "</small></div><div>", "</div>")
+            .and_then(|priority| priority.trim().parse().ok())
+            .ok_or_else(|| page.drift(&format!("work {} has no priority", work_id)))?;
+        let work_suitability = match between(entry, "<a href=\"", "\"") {
+            Some(slug) => Some(
+                vocabulary
+                    .work_type(&slug.replace('_', " "))
+                    .ok_or_else(|| page.drift(&format!("unknown work suitability {}", slug)))?,
+            ),
+            None => None,
+        };
+        records.push(WorkPriorityRecord {
+            work_id: work_id.to_string(),
+            name,
+            priority,
+            work_suitability,
+        });
+    }
+    ensure!(!records.is_empty(), page.drift("no work priorities found"));
+    ensure_unique(
+        &page.file_name,
+        records.iter().map(|record| record.work_id.as_str()),
+    )?;
+    Ok(records)
+}
+
+#[derive(Serialize)]
+struct ProductionFacilityRecord {
+    building_id: String,
+    name: String,
+    category: String,
+    technology_level: Option<i64>,
+    sanity_drain: Option<f64>,
+    /// Work suitability level a pal needs to operate the facility.
+    work_suitability: BTreeMap<String, i64>,
+    build_workload: Option<Workload>,
+    build_materials: Vec<ItemCount>,
+    /// Remaining key/value rows of the page's `Others` card.
+    properties: BTreeMap<String, String>,
+    /// One production cycle, from the page's `Farm` card.
+    production: Option<ProductionCycle>,
+}
+
+#[derive(Serialize)]
+struct Workload {
+    work_suitability: String,
+    amount: i64,
+}
+
+#[derive(Serialize)]
+struct ProductionCycle {
+    /// Work done in order; each step must finish before the next starts.
+    steps: Vec<ProductionStep>,
+    /// Unattended wait between steps (crop growth), in seconds.
+    growth_seconds: Option<f64>,
+    products: Vec<ItemCount>,
+}
+
+#[derive(Serialize)]
+struct ProductionStep {
+    work_suitability: String,
+    workload: i64,
+    /// paldb's time for the step by work suitability level, `Lv.1` first.
+    level_seconds: Vec<f64>,
+}
+
+fn extract_production_facility(
+    page: &RawPage,
+    vocabulary: &Vocabulary,
+    item_ids_by_name: &HashMap<String, String>,
+) -> Result<ProductionFacilityRecord> {
+    const ROW: &str =
+        "<div class=\"d-flex justify-content-between p-2 align-items-center border-bottom\">";
+    const MATERIAL_ROW: &str =
+        "<div class=\"d-flex justify-content-between p-2 align-items-center border-top\">";
+
+    let banner = between(
+        &page.html,
+        "class=\"hover_banner",
+        "class=\"hover_bg_rarity\"",
+    )
+    .ok_or_else(|| page.drift("missing facility banner"))?;
+    let name = between(banner, "<a class=\"itemname\"", "</a>")
+        .and_then(|link| link.split_once('>'))
+        .map(|(_, name)| strip_tags(name))
+        .ok_or_else(|| page.drift("facility banner has no name"))?;
+    let category = between(banner, "<span class=\"me-auto\"", "</span>")
+        .and_then(|span| span.split_once('>'))
+        .map(|(_, category)| strip_tags(category))
+        .ok_or_else(|| page.drift("facility banner has no category"))?;
+
+    let badges = between(&page.html, "class=\"hover_icon_bg", "class=\"card-body")
+        .ok_or_else(|| page.drift("missing facility badges"))?;
+    let badge_value = |label: &str| {
+        between(badges, &format!("{}</span>", label), "</span></span>")
+            .and_then(|value| value.rsplit('>').next())
+            .map(str::trim)
+    };
+    let technology_level = badge_value("Technology").and_then(|value| value.parse().ok());
+    let sanity_drain = badge_value("SAN").and_then(|value| value.parse().ok());
+
+    let recipes = between(
+        &page.html,
+        "<div class=\"recipes\">",
+        "<div class=\"card mt-3\">",
+    )
+    .ok_or_else(|| page.drift("missing build materials"))?;
+    let mut build_materials = Vec::new();
+    for row in recipes.split(MATERIAL_ROW).skip(1) {
+        let mut cells = row.split("<div>").skip(1).map(strip_tags);
+        let (Some(item_name), Some(count)) = (cells.next(), cells.next()) else {
+            bail!(page.drift("build material row is incomplete"));
+        };
+        let item_id = item_ids_by_name.get(&item_name).cloned().ok_or_else(|| {
+            page.drift(&format!("build material {} is not a known item", item_name))
+        })?;
+        let count = count
+            .parse()
+            .map_err(|_| page.drift(&format!("build material {} has no count", item_name)))?;
+        build_materials.push(ItemCount { item_id, count });
+    }
+
+    // The workload value carries a tooltip whose title is raw `<div>` markup
+    // with per-level timings; it is dropped so rows split cleanly.
+    let html = strip_tooltips(&page.html);
+    let stats = between(&html, "> Stats </h5>", "</div>\n    </div>")
+        .ok_or_else(|| page.drift("missing Stats card"))?;
+    let others = between(&html, "> Others </h5>", "</div>\n    </div>")
+        .ok_or_else(|| page.drift("missing Others card"))?;
+
+    let mut building_id = None;
+    let mut build_workload = None;
+    let mut work_suitability = BTreeMap::new();
+    for row in stats.split(ROW).skip(1) {
+        let (label, value) = facility_row(page, row)?;
+        match label.as_str() {
+            "Code" => building_id = Some(value),
+            "Workload" => {
+                let (work, amount) = value
+                    .rsplit_once(" x ")
+                    .ok_or_else(|| page.drift("workload is not `<work> x<amount>`"))?;
+                build_workload = Some(Workload {
+                    work_suitability: vocabulary
+                        .work_type(work)
+                        .ok_or_else(|| page.drift(&format!("unknown workload type {}", work)))?,
+                    amount: amount
+                        .parse()
+                        .map_err(|_| page.drift("workload amount is not a number"))?,
+                });
+            }
+            "Type" | "Defense" => {}
+            other => {
+                let work_type = vocabulary
+                    .work_type(other)
+                    .ok_or_else(|| page.drift(&format!("unexpected Stats row {}", other)))?;
+                let level = value.parse().map_err(|_| {
+                    page.drift(&format!("work level for {} is not a number", other))
+                })?;
+                work_suitability.insert(work_type, level);
+            }
+        }
+    }
+
+    let mut properties = BTreeMap::new();
+    for row in others.split(ROW).skip(1) {
+        let (label, value) = facility_row(page, row)?;
+        properties.insert(label, value);
+    }
+
+    let production = between(
+        &page.html,
+        ">\n                Farm\n            </h5>",
+        "\n        </div>\n    </div>",
+    )
+    .map(|card| parse_production_cycle(page, card, vocabulary, item_ids_by_name, &work_suitability))
+    .transpose()?;
+
+    Ok(ProductionFacilityRecord {
+        building_id: building_id.ok_or_else(|| page.drift("Stats card has no Code row"))?,
+        name,
+        category,
+        technology_level,
+        sanity_drain,
+        work_suitability,
+        build_workload,
+        build_materials,
+        properties,
+        production,
+    })
+}
+
+/// Parses the `Farm` card. Rows are either `<work> x<workload>`, `Growth`
+/// with a duration, a product (`Wheat x10`), or a product next to
+/// `Workload x<workload>` for facilities with a single work type.
+fn parse_production_cycle(
+    page: &RawPage,
+    card: &str,
+    vocabulary: &Vocabulary,
+    item_ids_by_name: &HashMap<String, String>,
+    work_suitability: &BTreeMap<String, i64>,
+) -> Result<ProductionCycle> {
+    const ROW: &str =
+        "<div class=\"d-flex justify-content-between p-2 align-items-center border-bottom\">";
+
+    let mut steps = Vec::new();
+    let mut growth_seconds = None;
+    let mut products = Vec::new();
+    for row in card.split(ROW).skip(1) {
+        let level_seconds = between(row, "data-bs-title=\"", "\"")
+            .map(|tooltip| parse_level_seconds(page, tooltip))
+            .transpose()?
+            .unwrap_or_default();
+        let row = strip_tooltips(row);
+        let mut cells = row.split("<div>").skip(1).map(strip_tags);
+        let (Some(label), Some(value)) = (cells.next(), cells.next()) else {
+            bail!(page.drift("production row is not a label/value pair"));
+        };
+        let mut step = |work_suitability: String, amount: &str| -> Result<()> {
+            let workload = amount
+                .trim()
+                .parse()
+                .map_err(|_| page.drift(&format!("workload {} is not a number", amount)))?;
+            ensure!(
+                !level_seconds.is_empty(),
+                page.drift("production step has no per-level timings")
+            );
+            steps.push(ProductionStep {
+                work_suitability,
+                workload,
+                level_seconds: level_seconds.clone(),
+            });
+            Ok(())
+        };
+
+        if label == "Growth" {
+            growth_seconds = Some(
+                value
+                    .strip_suffix(" sec")
+                    .and_then(|seconds| seconds.parse().ok())
+                    .ok_or_else(|| page.drift("growth time is not `<n> sec`"))?,
+            );
+        } else if let Some(work) = vocabulary.work_type(&label) {
+            let amount = value
+                .strip_prefix('x')
+                .ok_or_else(|| page.drift("production step is not `x<workload>`"))?;
+            step(work, amount)?;
+        } else {
+            for cell in [label, value] {
+                if cell.is_empty() {
+                    continue;
+                }
+                if let Some(amount) = cell.strip_prefix("Workload x") {
+                    let mut works = work_suitability.keys();
+                    let (Some(work), None) = (works.next(), works.next()) else {
+                        bail!(page.drift(
+                            "untyped production workload needs exactly one facility work type"
+                        ));
+                    };
+                    step(work.clone(), amount)?;
+                    continue;
+                }
+                let (item_name, count) = cell
+                    .rsplit_once(" x")
+                    .ok_or_else(|| page.drift(&format!("unexpected production cell {}", cell)))?;
+                let item_id = item_ids_by_name.get(item_name).cloned().ok_or_else(|| {
+                    page.drift(&format!("product {} is not a known item", item_name))
+                })?;
+                let count = count
+                    .parse()
+                    .map_err(|_| page.drift(&format!("product {} has no count", item_name)))?;
+                products.push(ItemCount { item_id, count });
+            }
+        }
+    }
+    ensure!(
+        !steps.is_empty() && !products.is_empty(),
+        page.drift("Farm card needs at least one step and one product")
+    );
+    Ok(ProductionCycle {
+        steps,
+        growth_seconds,
+        products,
+    })
+}
+
+/// Parses `<div>Lv.1: 1m47s</div><div>Lv.2: 50s</div>...` tooltips.
+fn parse_level_seconds(page: &RawPage, tooltip: &str) -> Result<Vec<f64>> {
+    let mut seconds = Vec::new();
+    for line in tooltip.split("<div>").skip(1) {
+        let line = strip_tags(line);
+        let (level, duration) = line
+            .split_once(": ")
+            .ok_or_else(|| page.drift(&format!("timing {} is not `Lv.N: <time>`", line)))?;
+        ensure!(
+            level == format!("Lv.{}", seconds.len() + 1),
+            page.drift(&format!("timing levels are out of order at {}", level))
+        );
+        seconds.push(
+            parse_duration(duration)
+                .ok_or_else(|| page.drift(&format!("unreadable duration {}", duration)))?,
+        );
+    }
+    Ok(seconds)
+}
+
+/// Parses `1h2m`, `1m47s`, `0.8s` style durations into seconds.
+fn parse_duration(text: &str) -> Option<f64> {
+    let mut total = 0.0;
+    let mut number = String::new();
+    for character in text.chars() {
+        let unit = match character {
+            'h' => 3600.0,
+            'm' => 60.0,
+            's' => 1.0,
+            _ => {
+                number.push(character);
+                continue;
+            }
+        };
+        total += number.parse::<f64>().ok()? * unit;
+        number.clear();
+    }
+    (number.is_empty() && total > 0.0).then_some(total)
+}
+
+/// Splits a `<div>label</div><div>value</div>` stats row.
+fn facility_row(page: &RawPage, row: &str) -> Result<(String, String)> {
+    let mut cells = row.split("<div>").skip(1).map(strip_tags);
+    match (cells.next(), cells.next()) {
+        (Some(label), Some(value)) if !label.is_empty() => Ok((label, value)),
+        _ => Err(page.drift("stats row is not a label/value pair")),
+    }
+}
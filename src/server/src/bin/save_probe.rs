@@ -1,7 +1,7 @@
+#[path = "../save/archive.rs"]
+mod archive;
 #[path = "../save/detect.rs"]
 mod detect;
-#[path = "../save/zip.rs"]
-mod zip;
 
 use anyhow::{Context, Result, bail};
 use gvas::cursor_ext::ReadExt;
@@ -21,20 +21,33 @@ use std::io::Read;
 
 fn main() -> Result<()> {
     let args: Vec<String> = std::env::args().collect();
-    let zip_path = args
+    let archive_path = args
         .get(1)
         .map(String::as_str)
         .unwrap_or("../../gamesave.zip");
-    let zip_bytes = std::fs::read(zip_path)
-        .with_context(|| format!("failed to read zip file at {}", zip_path))?;
-
-    let entries = zip::parse_zip_entries(&zip_bytes)?;
-    let root = zip::detect_world_root(&entries)?;
+    let limits = archive::ArchiveLimits::default();
+    let extraction = if std::path::Path::new(archive_path).is_dir() {
+        archive::read_directory_entries(std::path::Path::new(archive_path), &limits)?
+    } else {
+        let archive_bytes = std::fs::read(archive_path)
+            .with_context(|| format!("failed to read archive file at {}", archive_path))?;
+        archive::parse_archive_entries(&archive_bytes, &limits)?
+    };
+    println!(
+        "archive format: {} (.{})",
+        extraction.format.as_str(),
+        extraction.format.file_extension()
+    );
+    for skipped in &extraction.skipped {
+        println!("skipped entry: {} ({})", skipped.path, skipped.reason);
+    }
+    let entries = extraction.entries;
+    let root = archive::detect_world_root(&entries)?;
     println!("world root: {}", root);
 
     let mut rooted = BTreeMap::<String, Vec<u8>>::new();
     for entry in entries {
-        if let Some(relative_path) = zip::strip_root_prefix(&root, &entry.path) {
+        if let Some(relative_path) = archive::strip_root_prefix(&root, &entry.path) {
             rooted.insert(relative_path, entry.bytes);
         }
     }
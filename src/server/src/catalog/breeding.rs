@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// One `breeding` dataset record. Parents are sorted; a pair with two
+/// records is a unique combination whose child depends on which parent is
+/// the female.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogBreedingCombo {
+    pub parent_a: String,
+    pub parent_b: String,
+    pub child: String,
+}
+
+/// Lookups over the `breeding` records by parent pair, by child, and by
+/// either parent.
+#[derive(Default)]
+pub struct BreedingIndex {
+    by_pair: HashMap<(String, String), Vec<usize>>,
+    by_child: HashMap<String, Vec<usize>>,
+    by_parent: HashMap<String, Vec<usize>>,
+}
+
+impl BreedingIndex {
+    pub fn new(combos: &[CatalogBreedingCombo]) -> Self {
+        let mut index = Self::default();
+        for (position, combo) in combos.iter().enumerate() {
+            index
+                .by_pair
+                .entry(pair_key(&combo.parent_a, &combo.parent_b))
+                .or_default()
+                .push(position);
+            index
+                .by_child
+                .entry(combo.child.clone())
+                .or_default()
+                .push(position);
+            index
+                .by_parent
+                .entry(combo.parent_a.clone())
+                .or_default()
+                .push(position);
+            if combo.parent_b != combo.parent_a {
+                index
+                    .by_parent
+                    .entry(combo.parent_b.clone())
+                    .or_default()
+                    .push(position);
+            }
+        }
+        index
+    }
+
+    /// Records for the pair, in either parent order.
+    pub fn pair(&self, parent_a: &str, parent_b: &str) -> &[usize] {
+        self.by_pair
+            .get(&pair_key(parent_a, parent_b))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn child(&self, child: &str) -> &[usize] {
+        self.by_child.get(child).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn parent(&self, parent: &str) -> &[usize] {
+        self.by_parent.get(parent).map_or(&[], Vec::as_slice)
+    }
+}
+
+fn pair_key(parent_a: &str, parent_b: &str) -> (String, String) {
+    if parent_a <= parent_b {
+        (parent_a.to_string(), parent_b.to_string())
+    } else {
+        (parent_b.to_string(), parent_a.to_string())
+    }
+}
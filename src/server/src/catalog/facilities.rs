@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// One `production_facilities` dataset record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogProductionFacility {
+    pub building_id: String,
+    pub name: String,
+    pub category: String,
+    pub technology_level: Option<i64>,
+    pub sanity_drain: Option<f64>,
+    /// Work suitability level a pal needs to operate the facility.
+    pub work_suitability: BTreeMap<String, i64>,
+    pub build_workload: Option<CatalogWorkload>,
+    pub build_materials: Vec<CatalogItemCount>,
+    pub properties: BTreeMap<String, String>,
+    pub production: Option<CatalogProductionCycle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogWorkload {
+    pub work_suitability: String,
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogItemCount {
+    pub item_id: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogProductionCycle {
+    /// Done in order; each step must finish before the next starts.
+    pub steps: Vec<CatalogProductionStep>,
+    /// Unattended wait between steps (crop growth), in seconds.
+    pub growth_seconds: Option<f64>,
+    pub products: Vec<CatalogItemCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogProductionStep {
+    pub work_suitability: String,
+    pub workload: i64,
+    /// Step time by work suitability level, `Lv.1` first.
+    pub level_seconds: Vec<f64>,
+}
+
+/// Workload per second of one pal at each work suitability level (`Lv.1`
+/// first), taken from the fastest per-level timing over all production
+/// steps. paldb rounds timings up, so the largest workload/time ratio is the
+/// closest to the game's rate.
+pub fn reference_work_rates(facilities: &[CatalogProductionFacility]) -> Vec<f64> {
+    let mut rates: Vec<f64> = Vec::new();
+    let steps = facilities
+        .iter()
+        .filter_map(|facility| facility.production.as_ref())
+        .flat_map(|production| &production.steps);
+    for step in steps {
+        for (index, seconds) in step.level_seconds.iter().enumerate() {
+            let rate = step.workload as f64 / seconds;
+            match rates.get_mut(index) {
+                Some(existing) => *existing = existing.max(rate),
+                None => rates.push(rate),
+            }
+        }
+    }
+    rates
+}
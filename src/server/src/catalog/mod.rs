@@ -0,0 +1,260 @@
+pub mod breeding;
+pub mod facilities;
+pub mod pals;
+pub mod skills;
+
+use crate::save::rules::MAX_WORK_SUITABILITY_LEVEL;
+use anyhow::{Context, Result, ensure};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use breeding::{BreedingIndex, CatalogBreedingCombo};
+use facilities::{CatalogProductionFacility, reference_work_rates};
+use pals::{CatalogPal, SpeciesRef, parse_character_id};
+use skills::{CatalogActiveSkill, CatalogPassiveSkill, PassiveModifiers};
+
+/// Dataset `schema_version` this server reads; see
+/// `src/bin/extract_game_data.rs`.
+pub const SUPPORTED_SCHEMA_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetSource {
+    pub file: String,
+    pub sha256: String,
+}
+
+#[derive(Deserialize)]
+struct DatasetFile<T> {
+    dataset: String,
+    schema_version: u32,
+    sources: Vec<DatasetSource>,
+    record_count: usize,
+    records: Vec<T>,
+}
+
+struct Dataset<T> {
+    sources: Vec<DatasetSource>,
+    records: Vec<T>,
+}
+
+fn load_dataset<T: DeserializeOwned>(dir: &Path, name: &str) -> Result<Dataset<T>> {
+    let path = dir.join(format!("{}.json", name));
+    let bytes = std::fs::read(&path)
+        .with_context(|| format!("failed to read game data {}", path.display()))?;
+    let file: DatasetFile<T> = serde_json::from_slice(&bytes)
+        .with_context(|| format!("failed to parse game data {}", path.display()))?;
+    ensure!(
+        file.dataset == name,
+        "{} contains dataset {}, expected {}",
+        path.display(),
+        file.dataset,
+        name
+    );
+    ensure!(
+        file.schema_version == SUPPORTED_SCHEMA_VERSION,
+        "{} has schema_version {}, this server reads {}; rerun extract_game_data",
+        path.display(),
+        file.schema_version,
+        SUPPORTED_SCHEMA_VERSION
+    );
+    ensure!(
+        file.record_count == file.records.len(),
+        "{} declares {} records but has {}",
+        path.display(),
+        file.record_count,
+        file.records.len()
+    );
+    Ok(Dataset {
+        sources: file.sources,
+        records: file.records,
+    })
+}
+
+/// Only the level a technology unlocks at; see `GameCatalog::tech_level_cap`.
+#[derive(Deserialize)]
+struct TechnologyLevel {
+    unlock_level: i64,
+}
+
+/// Only the skill an item teaches; set for skill fruits.
+#[derive(Deserialize)]
+struct ItemWaza {
+    waza_id: Option<String>,
+}
+
+/// Game data loaded once at startup from the extracted `data/json` datasets.
+pub struct GameCatalog {
+    pals: Vec<CatalogPal>,
+    pal_sources: Vec<DatasetSource>,
+    /// Lowercased species id to index in `pals`; save files do not always
+    /// match the dataset's casing (`BluePlatypus`/`Blueplatypus`).
+    pal_index: HashMap<String, usize>,
+    passives: Vec<CatalogPassiveSkill>,
+    passive_sources: Vec<DatasetSource>,
+    active_skills: Vec<CatalogActiveSkill>,
+    active_skill_sources: Vec<DatasetSource>,
+    /// Save `passive_id` (lowercased) to index in `passives`.
+    passive_index: HashMap<String, usize>,
+    production_facilities: Vec<CatalogProductionFacility>,
+    reference_work_rates: Vec<f64>,
+    breeding: Vec<CatalogBreedingCombo>,
+    breeding_index: BreedingIndex,
+    /// `waza_id` to index in `active_skills`.
+    active_skill_index: HashMap<String, usize>,
+    /// Skills any pal can learn from a skill fruit.
+    skill_fruit_waza_ids: HashSet<String>,
+    tech_level_cap: i64,
+}
+
+impl GameCatalog {
+    pub fn load(dir: &Path) -> Result<Self> {
+        let pals: Dataset<CatalogPal> = load_dataset(dir, "pals")?;
+        let pal_index = pals
+            .records
+            .iter()
+            .enumerate()
+            .map(|(index, pal)| (pal.species_id.to_ascii_lowercase(), index))
+            .collect();
+        let mut passives: Dataset<CatalogPassiveSkill> = load_dataset(dir, "passive_skills")?;
+        for passive in &mut passives.records {
+            passive.modifiers = PassiveModifiers::from_effects(&passive.effects);
+        }
+        let passive_index = passives
+            .records
+            .iter()
+            .enumerate()
+            .filter_map(|(index, passive)| {
+                Some((passive.passive_id.as_ref()?.to_ascii_lowercase(), index))
+            })
+            .collect();
+        let active_skills: Dataset<CatalogActiveSkill> = load_dataset(dir, "active_skills")?;
+        let facilities: Dataset<CatalogProductionFacility> =
+            load_dataset(dir, "production_facilities")?;
+        let reference_work_rates = reference_work_rates(&facilities.records);
+        ensure!(
+            reference_work_rates.len() == MAX_WORK_SUITABILITY_LEVEL as usize,
+            "production_facilities has {} per-level timings, expected {}",
+            reference_work_rates.len(),
+            MAX_WORK_SUITABILITY_LEVEL
+        );
+        let breeding: Dataset<CatalogBreedingCombo> = load_dataset(dir, "breeding")?;
+        let breeding_index = BreedingIndex::new(&breeding.records);
+        let active_skill_index = active_skills
+            .records
+            .iter()
+            .enumerate()
+            .map(|(index, skill)| (skill.waza_id.clone(), index))
+            .collect();
+        let items: Dataset<ItemWaza> = load_dataset(dir, "items")?;
+        let skill_fruit_waza_ids = items
+            .records
+            .into_iter()
+            .filter_map(|item| item.waza_id)
+            .collect();
+        let technologies: Dataset<TechnologyLevel> = load_dataset(dir, "technologies")?;
+        let tech_level_cap = technologies
+            .records
+            .iter()
+            .map(|technology| technology.unlock_level)
+            .max()
+            .context("technologies has no records to derive the level cap from")?;
+        Ok(Self {
+            pals: pals.records,
+            pal_sources: pals.sources,
+            pal_index,
+            passives: passives.records,
+            passive_sources: passives.sources,
+            active_skills: active_skills.records,
+            active_skill_sources: active_skills.sources,
+            passive_index,
+            production_facilities: facilities.records,
+            reference_work_rates,
+            breeding: breeding.records,
+            breeding_index,
+            active_skill_index,
+            skill_fruit_waza_ids,
+            tech_level_cap,
+        })
+    }
+
+    pub fn pals(&self) -> &[CatalogPal] {
+        &self.pals
+    }
+
+    pub fn pal_sources(&self) -> &[DatasetSource] {
+        &self.pal_sources
+    }
+
+    pub fn passives(&self) -> &[CatalogPassiveSkill] {
+        &self.passives
+    }
+
+    pub fn passive_sources(&self) -> &[DatasetSource] {
+        &self.passive_sources
+    }
+
+    pub fn active_skills(&self) -> &[CatalogActiveSkill] {
+        &self.active_skills
+    }
+
+    pub fn active_skill_sources(&self) -> &[DatasetSource] {
+        &self.active_skill_sources
+    }
+
+    /// Looks up a passive by save id (a `PassiveSkillList` value).
+    pub fn passive(&self, passive_id: &str) -> Option<&CatalogPassiveSkill> {
+        let index = self.passive_index.get(&passive_id.to_ascii_lowercase())?;
+        Some(&self.passives[*index])
+    }
+
+    pub fn production_facilities(&self) -> &[CatalogProductionFacility] {
+        &self.production_facilities
+    }
+
+    /// Workload per second at each work suitability level, `Lv.1` first.
+    pub fn reference_work_rates(&self) -> &[f64] {
+        &self.reference_work_rates
+    }
+
+    /// Looks up an active skill by `waza_id` (without the `EPalWazaID::`
+    /// prefix).
+    pub fn active_skill(&self, waza_id: &str) -> Option<&CatalogActiveSkill> {
+        let index = self.active_skill_index.get(waza_id)?;
+        Some(&self.active_skills[*index])
+    }
+
+    pub fn is_skill_fruit_waza(&self, waza_id: &str) -> bool {
+        self.skill_fruit_waza_ids.contains(waza_id)
+    }
+
+    /// Highest technology unlock level, used as a stand-in for the player and
+    /// pal level cap. The real cap is the last row of the game's experience
+    /// table, which no snapshot has; this drifts if the technology tree stops
+    /// running up to the cap, so replace it once an exp table is extracted.
+    pub fn tech_level_cap(&self) -> i64 {
+        self.tech_level_cap
+    }
+
+    pub fn breeding(&self) -> &[CatalogBreedingCombo] {
+        &self.breeding
+    }
+
+    pub fn breeding_index(&self) -> &BreedingIndex {
+        &self.breeding_index
+    }
+
+    /// Resolves a save `CharacterID` (possibly prefixed, e.g. `BOSS_Anubis`)
+    /// to its catalog species.
+    pub fn species(&self, character_id: &str) -> Option<SpeciesRef<'_>> {
+        let parsed = parse_character_id(character_id);
+        let index = self
+            .pal_index
+            .get(&parsed.base_species_id.to_ascii_lowercase())?;
+        Some(SpeciesRef {
+            pal: &self.pals[*index],
+            variant: parsed.variant,
+        })
+    }
+}
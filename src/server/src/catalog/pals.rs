@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// One `pals` dataset record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogPal {
+    pub species_id: String,
+    pub name: String,
+    pub paldeck_no: Option<String>,
+    pub description: Option<String>,
+    pub rarity: i64,
+    pub size: String,
+    pub genus: Option<String>,
+    pub is_boss: bool,
+    pub elements: Vec<String>,
+    pub stats: CatalogPalStats,
+    pub food: Option<i64>,
+    pub exp_ratio: Option<f64>,
+    pub price: Option<i64>,
+    pub breeding_power: i64,
+    pub ignore_breeding: bool,
+    pub male_probability: f64,
+    /// Base suitability level keyed by `EPalWorkSuitability` value.
+    pub work_suitability: BTreeMap<String, i64>,
+    pub partner_skill: Option<CatalogPartnerSkill>,
+    pub learnset: Vec<CatalogLearnedSkill>,
+    pub drops: Vec<CatalogPalDrop>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogPalStats {
+    pub hp: i64,
+    pub defense: i64,
+    pub stamina: i64,
+    pub melee_attack: i64,
+    pub ranged_attack: i64,
+    pub support: i64,
+    pub craft_speed: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogPartnerSkill {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub unlock_item: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogLearnedSkill {
+    pub waza_id: String,
+    pub level: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogPalDrop {
+    pub item_id: String,
+    pub drop_rate: f64,
+    pub min: i64,
+    pub max: i64,
+}
+
+/// Encounter variant encoded as a `CharacterID` prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CharacterVariant {
+    /// `BOSS_`: alpha pal, capturable.
+    Alpha,
+    /// `GYM_`: tower boss.
+    TowerBoss,
+    /// `RAID_`: raid boss.
+    RaidBoss,
+    /// `PREDATOR_`: rampaging pal.
+    Predator,
+}
+
+const VARIANT_PREFIXES: [(&str, CharacterVariant); 4] = [
+    ("BOSS_", CharacterVariant::Alpha),
+    ("GYM_", CharacterVariant::TowerBoss),
+    ("RAID_", CharacterVariant::RaidBoss),
+    ("PREDATOR_", CharacterVariant::Predator),
+];
+
+pub struct ParsedCharacterId<'a> {
+    pub base_species_id: &'a str,
+    pub variant: Option<CharacterVariant>,
+}
+
+/// Splits a save `CharacterID` into its species id and variant prefix.
+/// Prefixes are matched case-insensitively because saves use both `BOSS_`
+/// and `Boss_`.
+pub fn parse_character_id(character_id: &str) -> ParsedCharacterId<'_> {
+    for (prefix, variant) in VARIANT_PREFIXES {
+        if character_id.len() > prefix.len()
+            && character_id.is_char_boundary(prefix.len())
+            && character_id[..prefix.len()].eq_ignore_ascii_case(prefix)
+        {
+            return ParsedCharacterId {
+                base_species_id: &character_id[prefix.len()..],
+                variant: Some(variant),
+            };
+        }
+    }
+    ParsedCharacterId {
+        base_species_id: character_id,
+        variant: None,
+    }
+}
+
+pub struct SpeciesRef<'a> {
+    pub pal: &'a CatalogPal,
+    pub variant: Option<CharacterVariant>,
+}
+
+/// Catalog fields attached to normalized pal rows.
+#[derive(Debug, Clone, Serialize)]
+pub struct PalSpeciesInfo {
+    pub species_id: String,
+    pub display_name: String,
+    pub paldeck_no: Option<String>,
+    pub elements: Vec<String>,
+    pub work_suitability: BTreeMap<String, i64>,
+    pub food: Option<i64>,
+    pub variant: Option<CharacterVariant>,
+    pub is_alpha: bool,
+    pub is_boss: bool,
+}
+
+impl SpeciesRef<'_> {
+    pub fn info(&self) -> PalSpeciesInfo {
+        PalSpeciesInfo {
+            species_id: self.pal.species_id.clone(),
+            display_name: self.pal.name.clone(),
+            paldeck_no: self.pal.paldeck_no.clone(),
+            elements: self.pal.elements.clone(),
+            work_suitability: self.pal.work_suitability.clone(),
+            food: self.pal.food,
+            variant: self.variant,
+            is_alpha: self.variant == Some(CharacterVariant::Alpha),
+            is_boss: self.pal.is_boss
+                || matches!(
+                    self.variant,
+                    Some(CharacterVariant::TowerBoss | CharacterVariant::RaidBoss)
+                ),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+/// One `passive_skills` dataset record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogPassiveSkill {
+    /// Save id as stored in `PassiveSkillList`; null for passives no source
+    /// maps to an id.
+    pub passive_id: Option<String>,
+    pub name: String,
+    /// `pal` for skills a pal can roll, `other` for equipment and
+    /// special-source passives.
+    pub pool: String,
+    /// 1 to 4, negative for harmful skills.
+    pub rank: i64,
+    pub weight: Option<i64>,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub effects: Vec<CatalogPassiveEffect>,
+    /// Derived from `effects` at load time.
+    #[serde(skip_deserializing)]
+    pub modifiers: PassiveModifiers,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogPassiveEffect {
+    pub stat: String,
+    pub value: f64,
+    pub is_percent: bool,
+    pub target: Option<String>,
+}
+
+/// Percentage modifiers a passive applies to the pal that has it. Negative
+/// `sanity_drain_pct`/`hunger_rate_pct` mean SAN and hunger drop slower.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PassiveModifiers {
+    pub work_speed_pct: f64,
+    pub max_hp_pct: f64,
+    pub attack_pct: f64,
+    pub defense_pct: f64,
+    pub sanity_drain_pct: f64,
+    pub hunger_rate_pct: f64,
+}
+
+impl PassiveModifiers {
+    /// Sums `ToSelf` effects; effects on the trainer or the partner pal
+    /// (`ToTrainer`, `ToOtomo`) do not change the pal's own numbers.
+    pub fn from_effects(effects: &[CatalogPassiveEffect]) -> Self {
+        let mut modifiers = Self::default();
+        for effect in effects {
+            if effect.target.as_deref() != Some("ToSelf") {
+                continue;
+            }
+            let field = match effect.stat.as_str() {
+                "Work Speed" => &mut modifiers.work_speed_pct,
+                "Max Health" => &mut modifiers.max_hp_pct,
+                "Attack" => &mut modifiers.attack_pct,
+                "Defense" => &mut modifiers.defense_pct,
+                "Sanity_Decrease" => &mut modifiers.sanity_drain_pct,
+                "FullStomatch_Decrease" => &mut modifiers.hunger_rate_pct,
+                _ => continue,
+            };
+            *field += effect.value;
+        }
+        modifiers
+    }
+}
+
+/// One `active_skills` dataset record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogActiveSkill {
+    /// `EPalWazaID` value without the enum prefix.
+    pub waza_id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub element: Option<String>,
+    /// `Melee` or `Shot`.
+    pub category: String,
+    pub power: i64,
+    pub min_range: i64,
+    pub max_range: i64,
+    /// Seconds.
+    pub cool_time: f64,
+    /// Status effects applied on hit, with their accumulation value.
+    pub effects: Vec<CatalogActiveSkillEffect>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogActiveSkillEffect {
+    pub kind: String,
+    pub value: f64,
+}
@@ -1,3 +1,4 @@
+use crate::save::normalize::CharacterSelectionPolicy;
 use anyhow::{Context, Result};
 use std::net::Ipv4Addr;
 use std::path::PathBuf;
@@ -10,6 +11,22 @@ pub struct AppConfig {
     pub database_url: String,
     pub artifact_storage_root: PathBuf,
     pub max_import_zip_bytes: usize,
+    pub max_import_zip_entries: usize,
+    pub max_import_entry_uncompressed_bytes: u64,
+    pub max_import_total_uncompressed_bytes: u64,
+    pub max_import_compression_ratio: u64,
+    pub import_directory_roots: Vec<PathBuf>,
+    pub import_watch_paths: Vec<PathBuf>,
+    pub import_watch_poll_secs: u64,
+    pub import_watch_settle_secs: u64,
+    pub import_watch_min_interval_secs: u64,
+    pub job_worker_concurrency: usize,
+    pub job_lease_secs: u64,
+    pub job_poll_secs: u64,
+    pub job_max_attempts: i32,
+    pub job_retry_base_secs: u64,
+    pub character_selection_policy: CharacterSelectionPolicy,
+    pub game_data_dir: PathBuf,
 }
 
 impl AppConfig {
@@ -26,6 +43,70 @@ impl AppConfig {
             .unwrap_or_else(|_| "157286400".to_string())
             .parse()
             .context("MAX_IMPORT_ZIP_BYTES must be a valid usize")?;
+        let max_import_zip_entries = std::env::var("MAX_IMPORT_ZIP_ENTRIES")
+            .unwrap_or_else(|_| "4096".to_string())
+            .parse()
+            .context("MAX_IMPORT_ZIP_ENTRIES must be a valid usize")?;
+        let max_import_entry_uncompressed_bytes =
+            std::env::var("MAX_IMPORT_ENTRY_UNCOMPRESSED_BYTES")
+                .unwrap_or_else(|_| "536870912".to_string())
+                .parse()
+                .context("MAX_IMPORT_ENTRY_UNCOMPRESSED_BYTES must be a valid u64")?;
+        let max_import_total_uncompressed_bytes =
+            std::env::var("MAX_IMPORT_TOTAL_UNCOMPRESSED_BYTES")
+                .unwrap_or_else(|_| "2147483648".to_string())
+                .parse()
+                .context("MAX_IMPORT_TOTAL_UNCOMPRESSED_BYTES must be a valid u64")?;
+        let max_import_compression_ratio = std::env::var("MAX_IMPORT_COMPRESSION_RATIO")
+            .unwrap_or_else(|_| "100".to_string())
+            .parse()
+            .context("MAX_IMPORT_COMPRESSION_RATIO must be a valid u64")?;
+        let import_directory_roots =
+            parse_path_list(&std::env::var("IMPORT_DIRECTORY_ROOTS").unwrap_or_default());
+        let import_watch_paths =
+            parse_path_list(&std::env::var("IMPORT_WATCH_PATHS").unwrap_or_default());
+        let import_watch_poll_secs = std::env::var("IMPORT_WATCH_POLL_SECS")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .context("IMPORT_WATCH_POLL_SECS must be a valid u64")?;
+        let import_watch_settle_secs = std::env::var("IMPORT_WATCH_SETTLE_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .context("IMPORT_WATCH_SETTLE_SECS must be a valid u64")?;
+        let import_watch_min_interval_secs = std::env::var("IMPORT_WATCH_MIN_INTERVAL_SECS")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .context("IMPORT_WATCH_MIN_INTERVAL_SECS must be a valid u64")?;
+        let job_worker_concurrency = std::env::var("JOB_WORKER_CONCURRENCY")
+            .unwrap_or_else(|_| "2".to_string())
+            .parse()
+            .context("JOB_WORKER_CONCURRENCY must be a valid usize")?;
+        let job_lease_secs = std::env::var("JOB_LEASE_SECS")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse()
+            .context("JOB_LEASE_SECS must be a valid u64")?;
+        let job_poll_secs = std::env::var("JOB_POLL_SECS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .context("JOB_POLL_SECS must be a valid u64")?;
+        let job_max_attempts = std::env::var("JOB_MAX_ATTEMPTS")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()
+            .context("JOB_MAX_ATTEMPTS must be a valid i32")?;
+        let job_retry_base_secs = std::env::var("JOB_RETRY_BASE_SECS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .context("JOB_RETRY_BASE_SECS must be a valid u64")?;
+        let character_selection_policy =
+            std::env::var("CHARACTER_SELECTION_POLICY").unwrap_or_else(|_| "planner".to_string());
+        let character_selection_policy =
+            CharacterSelectionPolicy::parse(&character_selection_policy)
+                .with_context(|| "CHARACTER_SELECTION_POLICY must be one of planner, owned, all")?;
+        let game_data_dir =
+            std::env::var("GAME_DATA_DIR").unwrap_or_else(|_| "../../data/json".to_string());
+        if !import_watch_paths.is_empty() && import_directory_roots.is_empty() {
+            anyhow::bail!("IMPORT_WATCH_PATHS requires IMPORT_DIRECTORY_ROOTS to be set");
+        }
 
         Ok(Self {
             host: host
@@ -35,10 +116,34 @@ impl AppConfig {
             database_url,
             artifact_storage_root: PathBuf::from(artifact_storage_root),
             max_import_zip_bytes,
+            max_import_zip_entries,
+            max_import_entry_uncompressed_bytes,
+            max_import_total_uncompressed_bytes,
+            max_import_compression_ratio,
+            import_directory_roots,
+            import_watch_paths,
+            import_watch_poll_secs,
+            import_watch_settle_secs,
+            import_watch_min_interval_secs,
+            job_worker_concurrency,
+            job_lease_secs,
+            job_poll_secs,
+            job_max_attempts,
+            job_retry_base_secs,
+            character_selection_policy,
+            game_data_dir: PathBuf::from(game_data_dir),
         })
     }
 }
 
+fn parse_path_list(raw: &str) -> Vec<PathBuf> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
 pub fn init_tracing() {
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
     let use_json = std::env::var("LOG_JSON")
@@ -0,0 +1,6 @@
+ALTER TABLE save_import_versions
+ADD COLUMN IF NOT EXISTS source_kind TEXT NOT NULL DEFAULT 'upload'
+CHECK (source_kind IN ('upload', 'directory', 'watch'));
+
+ALTER TABLE save_import_versions
+ADD COLUMN IF NOT EXISTS source_path TEXT;
@@ -0,0 +1,23 @@
+CREATE TABLE IF NOT EXISTS background_jobs (
+    id UUID PRIMARY KEY,
+    kind TEXT NOT NULL CHECK (kind IN ('post_import')),
+    import_version_id UUID NOT NULL REFERENCES save_import_versions(id) ON DELETE RESTRICT,
+    status TEXT NOT NULL CHECK (status IN ('queued', 'running', 'succeeded', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0 CHECK (attempts >= 0),
+    max_attempts INTEGER NOT NULL CHECK (max_attempts > 0),
+    run_after TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    lease_owner TEXT,
+    lease_expires_at TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_background_jobs_claimable
+ON background_jobs (run_after)
+WHERE status IN ('queued', 'running');
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_background_jobs_active_per_import
+ON background_jobs (kind, import_version_id)
+WHERE status IN ('queued', 'running');
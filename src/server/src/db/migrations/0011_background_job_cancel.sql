@@ -0,0 +1,6 @@
+ALTER TABLE background_jobs
+DROP CONSTRAINT IF EXISTS background_jobs_status_check;
+
+ALTER TABLE background_jobs
+ADD CONSTRAINT background_jobs_status_check
+CHECK (status IN ('queued', 'running', 'succeeded', 'failed', 'cancelled'));
@@ -0,0 +1,46 @@
+CREATE TABLE IF NOT EXISTS worlds (
+    id UUID PRIMARY KEY,
+    world_key TEXT NOT NULL UNIQUE,
+    display_name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE save_import_versions
+ADD COLUMN IF NOT EXISTS world_id UUID REFERENCES worlds(id) ON DELETE RESTRICT;
+
+CREATE INDEX IF NOT EXISTS idx_save_import_versions_world
+ON save_import_versions (world_id, created_at);
+
+-- Backfill mirrors the Rust world key derivation: last segment of the world
+-- root, else the source file name without its archive extension; 32-hex GUIDs
+-- are upper-cased.
+WITH derived AS (
+    SELECT
+        iv.id,
+        COALESCE(
+            NULLIF(regexp_replace(rtrim(iv.world_root_path, '/'), '^.*/', ''), ''),
+            regexp_replace(iv.source_file_name, '\.(zip|tar|tar\.gz|tgz|7z)$', '', 'i')
+        ) AS raw_key
+    FROM save_import_versions iv
+    WHERE iv.world_id IS NULL
+),
+keyed AS (
+    SELECT
+        id,
+        CASE WHEN raw_key ~* '^[0-9a-f]{32}$' THEN upper(raw_key) ELSE raw_key END AS world_key
+    FROM derived
+),
+inserted AS (
+    INSERT INTO worlds (id, world_key, display_name)
+    SELECT gen_random_uuid(), world_key, world_key
+    FROM (SELECT DISTINCT world_key FROM keyed) distinct_keys
+    ON CONFLICT (world_key) DO NOTHING
+    RETURNING id, world_key
+)
+UPDATE save_import_versions iv
+SET world_id = COALESCE(
+    (SELECT inserted.id FROM inserted WHERE inserted.world_key = keyed.world_key),
+    (SELECT worlds.id FROM worlds WHERE worlds.world_key = keyed.world_key)
+)
+FROM keyed
+WHERE iv.id = keyed.id;
@@ -0,0 +1,18 @@
+ALTER TABLE worlds
+ALTER COLUMN world_key DROP NOT NULL;
+
+ALTER TABLE worlds
+ADD COLUMN IF NOT EXISTS archived_at TIMESTAMPTZ;
+
+ALTER TABLE worlds
+ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+ALTER TABLE save_import_versions
+ADD COLUMN IF NOT EXISTS source_export_version_id UUID REFERENCES save_export_versions(id) ON DELETE RESTRICT;
+
+CREATE INDEX IF NOT EXISTS idx_save_import_versions_source_export
+ON save_import_versions (source_export_version_id)
+WHERE source_export_version_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_save_zip_artifacts_sha256
+ON save_zip_artifacts (sha256);
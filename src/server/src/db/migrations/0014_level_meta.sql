@@ -0,0 +1,17 @@
+ALTER TABLE save_import_versions
+ADD COLUMN IF NOT EXISTS world_name TEXT;
+
+ALTER TABLE save_import_versions
+ADD COLUMN IF NOT EXISTS host_player_name TEXT;
+
+ALTER TABLE save_import_versions
+ADD COLUMN IF NOT EXISTS host_player_level INTEGER;
+
+ALTER TABLE save_import_versions
+ADD COLUMN IF NOT EXISTS in_game_day INTEGER;
+
+ALTER TABLE save_import_versions
+ADD COLUMN IF NOT EXISTS save_game_version INTEGER;
+
+ALTER TABLE save_import_versions
+ADD COLUMN IF NOT EXISTS engine_version TEXT;
@@ -0,0 +1,47 @@
+CREATE TABLE IF NOT EXISTS planner_guilds (
+    id UUID PRIMARY KEY,
+    import_version_id UUID NOT NULL REFERENCES save_import_versions(id) ON DELETE RESTRICT,
+    group_id TEXT NOT NULL,
+    group_type TEXT NOT NULL,
+    guild_name TEXT,
+    admin_player_uid TEXT,
+    base_camp_level INTEGER,
+    base_ids JSONB NOT NULL DEFAULT '[]'::jsonb,
+    raw_file_ref UUID REFERENCES save_files(id) ON DELETE RESTRICT,
+    raw_entity_path TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (import_version_id, group_id)
+);
+
+CREATE TABLE IF NOT EXISTS planner_guild_members (
+    id UUID PRIMARY KEY,
+    import_version_id UUID NOT NULL REFERENCES save_import_versions(id) ON DELETE RESTRICT,
+    group_id TEXT NOT NULL,
+    player_uid TEXT NOT NULL,
+    player_name TEXT,
+    last_online_ticks BIGINT,
+    offline_seconds BIGINT,
+    is_admin BOOLEAN NOT NULL DEFAULT FALSE,
+    raw_file_ref UUID REFERENCES save_files(id) ON DELETE RESTRICT,
+    raw_entity_path TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (import_version_id, group_id, player_uid)
+);
+
+CREATE TABLE IF NOT EXISTS planner_guild_links (
+    id UUID PRIMARY KEY,
+    planner_guild_id UUID NOT NULL REFERENCES planner_guilds(id) ON DELETE RESTRICT,
+    save_file_id UUID NOT NULL REFERENCES save_files(id) ON DELETE RESTRICT,
+    raw_entity_path TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (planner_guild_id, save_file_id, raw_entity_path)
+);
+
+CREATE TABLE IF NOT EXISTS planner_guild_member_links (
+    id UUID PRIMARY KEY,
+    planner_guild_member_id UUID NOT NULL REFERENCES planner_guild_members(id) ON DELETE RESTRICT,
+    save_file_id UUID NOT NULL REFERENCES save_files(id) ON DELETE RESTRICT,
+    raw_entity_path TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (planner_guild_member_id, save_file_id, raw_entity_path)
+);
@@ -0,0 +1,29 @@
+CREATE TABLE IF NOT EXISTS planner_bases (
+    id UUID PRIMARY KEY,
+    import_version_id UUID NOT NULL REFERENCES save_import_versions(id) ON DELETE RESTRICT,
+    base_id TEXT NOT NULL,
+    base_name TEXT,
+    state INTEGER,
+    area_range DOUBLE PRECISION,
+    group_id TEXT,
+    worker_container_id TEXT,
+    worker_order_type INTEGER,
+    location_x DOUBLE PRECISION,
+    location_y DOUBLE PRECISION,
+    location_z DOUBLE PRECISION,
+    transform JSONB,
+    worker_count INTEGER NOT NULL DEFAULT 0,
+    raw_file_ref UUID REFERENCES save_files(id) ON DELETE RESTRICT,
+    raw_entity_path TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (import_version_id, base_id)
+);
+
+CREATE TABLE IF NOT EXISTS planner_base_links (
+    id UUID PRIMARY KEY,
+    planner_base_id UUID NOT NULL REFERENCES planner_bases(id) ON DELETE RESTRICT,
+    save_file_id UUID NOT NULL REFERENCES save_files(id) ON DELETE RESTRICT,
+    raw_entity_path TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (planner_base_id, save_file_id, raw_entity_path)
+);
@@ -0,0 +1,8 @@
+ALTER TABLE planner_pals
+ADD COLUMN IF NOT EXISTS location_kind TEXT NOT NULL DEFAULT 'unknown';
+
+ALTER TABLE planner_pals
+ADD COLUMN IF NOT EXISTS container_id TEXT;
+
+ALTER TABLE planner_pals
+ADD COLUMN IF NOT EXISTS slot_index INTEGER;
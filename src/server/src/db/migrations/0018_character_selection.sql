@@ -0,0 +1,8 @@
+ALTER TABLE save_import_versions
+ADD COLUMN IF NOT EXISTS selection_policy TEXT;
+
+ALTER TABLE planner_players
+ADD COLUMN IF NOT EXISTS selection_reason TEXT;
+
+ALTER TABLE planner_pals
+ADD COLUMN IF NOT EXISTS selection_reason TEXT;
@@ -0,0 +1,2 @@
+ALTER TABLE planner_pals
+ADD COLUMN IF NOT EXISTS is_lucky BOOLEAN NOT NULL DEFAULT FALSE;
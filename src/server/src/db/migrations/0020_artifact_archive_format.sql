@@ -0,0 +1,25 @@
+ALTER TABLE save_zip_artifacts
+DROP CONSTRAINT IF EXISTS save_zip_artifacts_kind_check;
+
+ALTER TABLE save_zip_artifacts
+ADD COLUMN IF NOT EXISTS archive_format TEXT NOT NULL DEFAULT 'zip'
+CHECK (archive_format IN ('zip', 'tar', 'tar_gz', 'seven_zip', 'directory'));
+
+-- Backfill from the storage key extension, which has always followed the
+-- detected format; directory sources were stored as a synthesized ZIP.
+UPDATE save_zip_artifacts AS za
+SET archive_format = CASE
+        WHEN za.storage_key LIKE '%.tar.gz' THEN 'tar_gz'
+        WHEN za.storage_key LIKE '%.tar' THEN 'tar'
+        WHEN za.storage_key LIKE '%.7z' THEN 'seven_zip'
+        WHEN iv.source_kind = 'directory' THEN 'directory'
+        ELSE 'zip'
+    END,
+    kind = 'import_source_archive'
+FROM save_import_versions AS iv
+WHERE za.kind = 'import_source_zip'
+  AND iv.id = za.import_version_id;
+
+ALTER TABLE save_zip_artifacts
+ADD CONSTRAINT save_zip_artifacts_kind_check
+CHECK (kind IN ('import_source_archive', 'export_zip'));
@@ -0,0 +1,2 @@
+ALTER TABLE planner_pals
+ADD COLUMN IF NOT EXISTS work_suitability_add_ranks JSONB NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,103 @@
+use crate::AppState;
+use crate::api::handlers::import_directory::import_world_directory;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{error, info, warn};
+
+#[derive(Clone, Debug)]
+pub struct ImportWatchSettings {
+    pub world_paths: Vec<PathBuf>,
+    pub poll_interval: Duration,
+    pub settle_delay: Duration,
+    pub min_import_interval: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct LevelSignature {
+    modified: SystemTime,
+    byte_size: u64,
+}
+
+pub fn spawn_import_watchers(state: AppState, settings: ImportWatchSettings) {
+    for world_path in settings.world_paths.clone() {
+        let state = state.clone();
+        let settings = settings.clone();
+        tokio::spawn(async move {
+            watch_world_directory(state, world_path, settings).await;
+        });
+    }
+}
+
+/// Polls `Level.sav` and imports the folder once a change has stayed untouched
+/// for `settle_delay`, so a save that is still being written is never read.
+async fn watch_world_directory(
+    state: AppState,
+    world_path: PathBuf,
+    settings: ImportWatchSettings,
+) {
+    let level_path = world_path.join("Level.sav");
+    let mut last_seen = read_level_signature(&level_path).await;
+    let mut last_imported = last_seen;
+    let mut changed_at = Instant::now();
+    let mut last_import_at: Option<Instant> = None;
+    let mut interval = tokio::time::interval(settings.poll_interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    info!(world_path = %world_path.display(), "watching world folder for Level.sav changes");
+
+    loop {
+        interval.tick().await;
+
+        let current = read_level_signature(&level_path).await;
+        if current != last_seen {
+            last_seen = current;
+            changed_at = Instant::now();
+            continue;
+        }
+
+        let Some(signature) = current else {
+            continue;
+        };
+        if last_imported == Some(signature) || changed_at.elapsed() < settings.settle_delay {
+            continue;
+        }
+        if last_import_at.is_some_and(|at| at.elapsed() < settings.min_import_interval) {
+            continue;
+        }
+
+        match import_world_directory(&state, &world_path, "watch").await {
+            Ok(response) => {
+                info!(
+                    world_path = %world_path.display(),
+                    import_version_id = %response.import_version_id,
+                    "created import version from watched world folder"
+                );
+            }
+            Err(api_error) => {
+                error!(
+                    world_path = %world_path.display(),
+                    "watched world folder import failed: {}",
+                    api_error.message
+                );
+            }
+        }
+        // A failed import is not retried until Level.sav changes again; retrying
+        // the same broken snapshot every poll would only flood the import list.
+        last_imported = Some(signature);
+        last_import_at = Some(Instant::now());
+    }
+}
+
+async fn read_level_signature(level_path: &std::path::Path) -> Option<LevelSignature> {
+    match tokio::fs::metadata(level_path).await {
+        Ok(metadata) => Some(LevelSignature {
+            modified: metadata.modified().ok()?,
+            byte_size: metadata.len(),
+        }),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+        Err(error) => {
+            warn!(path = %level_path.display(), "failed to stat watched Level.sav: {}", error);
+            None
+        }
+    }
+}
@@ -0,0 +1,540 @@
+use crate::AppState;
+use crate::api::handlers::import_zip::{
+    ImportCancelled, mark_import_failed, run_post_import_processing, update_import_progress,
+};
+use anyhow::Context;
+use sqlx::{PgPool, Postgres, Row};
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const MAX_RETRY_DELAY_SECS: u64 = 600;
+
+#[derive(Clone, Debug)]
+pub struct JobQueueSettings {
+    pub worker_concurrency: usize,
+    pub lease_duration: Duration,
+    pub poll_interval: Duration,
+    pub retry_base_delay: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobKind {
+    PostImport,
+}
+
+impl JobKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PostImport => "post_import",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "post_import" => Some(Self::PostImport),
+            _ => None,
+        }
+    }
+}
+
+struct ClaimedJob {
+    id: Uuid,
+    kind: JobKind,
+    import_version_id: Uuid,
+    attempts: i32,
+    max_attempts: i32,
+}
+
+/// Queues a job inside the caller's transaction so the job exists exactly when
+/// the rows it processes are committed. Notify `AppState::job_wakeup` after the
+/// commit so an idle worker picks it up without waiting for the next poll.
+pub async fn enqueue_job(
+    executor: impl sqlx::Executor<'_, Database = Postgres>,
+    kind: JobKind,
+    import_version_id: Uuid,
+    max_attempts: i32,
+) -> anyhow::Result<Uuid> {
+    let job_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO background_jobs (id, kind, import_version_id, status, max_attempts)
+         VALUES ($1, $2, $3, 'queued', $4)",
+    )
+    .bind(job_id)
+    .bind(kind.as_str())
+    .bind(import_version_id)
+    .bind(max_attempts)
+    .execute(executor)
+    .await
+    .with_context(|| {
+        format!(
+            "failed to enqueue {} job for import {}",
+            kind.as_str(),
+            import_version_id
+        )
+    })?;
+    Ok(job_id)
+}
+
+/// Requeues imports left in `processing` without an active job, which happens
+/// when the server stopped before this queue existed or a job row was lost.
+/// Jobs still marked `running` by a dead worker are reclaimed once their lease
+/// expires, so they need no startup handling.
+pub async fn recover_orphaned_imports(pool: &PgPool, max_attempts: i32) -> anyhow::Result<()> {
+    let rows = sqlx::query(
+        "SELECT iv.id
+         FROM save_import_versions iv
+         WHERE iv.status = 'processing'
+           AND NOT EXISTS (
+               SELECT 1 FROM background_jobs bj
+               WHERE bj.import_version_id = iv.id
+                 AND bj.kind = $1
+                 AND bj.status IN ('queued', 'running')
+           )",
+    )
+    .bind(JobKind::PostImport.as_str())
+    .fetch_all(pool)
+    .await
+    .with_context(|| "failed to load orphaned processing imports")?;
+
+    for row in rows {
+        let import_version_id: Uuid = row.get("id");
+        enqueue_job(pool, JobKind::PostImport, import_version_id, max_attempts).await?;
+        warn!(
+            import_version_id = %import_version_id,
+            "requeued orphaned processing import"
+        );
+    }
+
+    Ok(())
+}
+
+pub fn spawn_job_workers(state: AppState, settings: JobQueueSettings) {
+    for worker_index in 0..settings.worker_concurrency.max(1) {
+        let state = state.clone();
+        let settings = settings.clone();
+        let worker_id = format!("worker-{}-{}", worker_index, Uuid::new_v4());
+        tokio::spawn(async move {
+            run_worker(state, settings, worker_id).await;
+        });
+    }
+}
+
+async fn run_worker(state: AppState, settings: JobQueueSettings, worker_id: String) {
+    info!(worker_id = %worker_id, "background job worker started");
+
+    loop {
+        if let Err(error) = fail_exhausted_jobs(&state).await {
+            error!(worker_id = %worker_id, "failed to expire exhausted jobs: {error:#}");
+        }
+
+        let claimed = match claim_next_job(&state.pool, &worker_id, settings.lease_duration).await {
+            Ok(claimed) => claimed,
+            Err(error) => {
+                error!(worker_id = %worker_id, "failed to claim background job: {error:#}");
+                None
+            }
+        };
+
+        let Some(job) = claimed else {
+            tokio::select! {
+                _ = state.job_wakeup.notified() => {}
+                _ = tokio::time::sleep(settings.poll_interval) => {}
+            }
+            continue;
+        };
+
+        run_claimed_job(&state, &settings, &worker_id, job).await;
+    }
+}
+
+/// Claims the oldest due job. A `running` job whose lease expired belongs to a
+/// worker that died, so it is claimed again as the next attempt.
+async fn claim_next_job(
+    pool: &PgPool,
+    worker_id: &str,
+    lease_duration: Duration,
+) -> anyhow::Result<Option<ClaimedJob>> {
+    let row = sqlx::query(
+        "UPDATE background_jobs
+         SET status = 'running',
+             attempts = attempts + 1,
+             lease_owner = $1,
+             lease_expires_at = NOW() + ($2::float8 * INTERVAL '1 second'),
+             updated_at = NOW()
+         WHERE id = (
+             SELECT id FROM background_jobs
+             WHERE attempts < max_attempts
+               AND (
+                   (status = 'queued' AND run_after <= NOW())
+                   OR (status = 'running' AND lease_expires_at < NOW())
+               )
+             ORDER BY run_after ASC
+             LIMIT 1
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING id, kind, import_version_id, attempts, max_attempts",
+    )
+    .bind(worker_id)
+    .bind(lease_duration.as_secs_f64())
+    .fetch_optional(pool)
+    .await
+    .with_context(|| "failed to claim background job")?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let kind: String = row.get("kind");
+    let kind = JobKind::parse(&kind)
+        .ok_or_else(|| anyhow::anyhow!("unknown background job kind {}", kind))?;
+
+    Ok(Some(ClaimedJob {
+        id: row.get("id"),
+        kind,
+        import_version_id: row.get("import_version_id"),
+        attempts: row.get("attempts"),
+        max_attempts: row.get("max_attempts"),
+    }))
+}
+
+async fn run_claimed_job(
+    state: &AppState,
+    settings: &JobQueueSettings,
+    worker_id: &str,
+    job: ClaimedJob,
+) {
+    info!(
+        job_id = %job.id,
+        job_kind = job.kind.as_str(),
+        import_version_id = %job.import_version_id,
+        attempt = job.attempts,
+        "running background job"
+    );
+
+    let heartbeat_interval = (settings.lease_duration / 3).max(Duration::from_secs(1));
+    let mut heartbeat = tokio::time::interval_at(
+        tokio::time::Instant::now() + heartbeat_interval,
+        heartbeat_interval,
+    );
+    let work = execute_job(state, &job);
+    tokio::pin!(work);
+
+    let result = loop {
+        tokio::select! {
+            result = &mut work => break result,
+            _ = heartbeat.tick() => {
+                match renew_lease(&state.pool, job.id, worker_id, settings.lease_duration).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        // Another worker reclaimed the job after our lease lapsed;
+                        // stop so two workers never write the same import.
+                        // Dropping `work` also stops its blocking normalize worker.
+                        warn!(job_id = %job.id, "background job lease lost; abandoning attempt");
+                        return;
+                    }
+                    Err(error) => {
+                        warn!(job_id = %job.id, "failed to renew background job lease: {error:#}");
+                    }
+                }
+            }
+        }
+    };
+
+    let outcome = match result {
+        Ok(()) => complete_job(&state.pool, job.id, worker_id).await,
+        Err(job_error) if job_error.downcast_ref::<ImportCancelled>().is_some() => {
+            info!(
+                job_id = %job.id,
+                import_version_id = %job.import_version_id,
+                "background job stopped because its import was cancelled"
+            );
+            cancel_job(&state.pool, job.id, worker_id).await
+        }
+        Err(job_error) if is_transient_error(&job_error) && job.attempts < job.max_attempts => {
+            let delay = retry_delay(settings.retry_base_delay, job.attempts);
+            warn!(
+                job_id = %job.id,
+                import_version_id = %job.import_version_id,
+                attempt = job.attempts,
+                retry_in_secs = delay.as_secs(),
+                "background job hit a transient error; retrying: {job_error:#}"
+            );
+            retry_job(state, &job, worker_id, delay, &job_error).await
+        }
+        Err(job_error) => {
+            error!(
+                job_id = %job.id,
+                import_version_id = %job.import_version_id,
+                attempt = job.attempts,
+                "background job failed: {job_error:#}"
+            );
+            fail_job(state, &job, worker_id, &job_error.to_string()).await
+        }
+    };
+
+    if let Err(error) = outcome {
+        error!(job_id = %job.id, "failed to record background job outcome: {error:#}");
+    }
+}
+
+async fn execute_job(state: &AppState, job: &ClaimedJob) -> anyhow::Result<()> {
+    match job.kind {
+        JobKind::PostImport => {
+            run_post_import_processing(state.clone(), job.import_version_id).await
+        }
+    }
+}
+
+async fn renew_lease(
+    pool: &PgPool,
+    job_id: Uuid,
+    worker_id: &str,
+    lease_duration: Duration,
+) -> anyhow::Result<bool> {
+    let result = sqlx::query(
+        "UPDATE background_jobs
+         SET lease_expires_at = NOW() + ($3::float8 * INTERVAL '1 second'),
+             updated_at = NOW()
+         WHERE id = $1 AND lease_owner = $2 AND status = 'running'",
+    )
+    .bind(job_id)
+    .bind(worker_id)
+    .bind(lease_duration.as_secs_f64())
+    .execute(pool)
+    .await
+    .with_context(|| format!("failed to renew lease for job {}", job_id))?;
+    Ok(result.rows_affected() == 1)
+}
+
+async fn complete_job(pool: &PgPool, job_id: Uuid, worker_id: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE background_jobs
+         SET status = 'succeeded',
+             lease_owner = NULL,
+             lease_expires_at = NULL,
+             last_error = NULL,
+             updated_at = NOW(),
+             completed_at = NOW()
+         WHERE id = $1 AND lease_owner = $2",
+    )
+    .bind(job_id)
+    .bind(worker_id)
+    .execute(pool)
+    .await
+    .with_context(|| format!("failed to complete job {}", job_id))?;
+    Ok(())
+}
+
+async fn cancel_job(pool: &PgPool, job_id: Uuid, worker_id: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE background_jobs
+         SET status = 'cancelled',
+             lease_owner = NULL,
+             lease_expires_at = NULL,
+             updated_at = NOW(),
+             completed_at = NOW()
+         WHERE id = $1 AND lease_owner = $2",
+    )
+    .bind(job_id)
+    .bind(worker_id)
+    .execute(pool)
+    .await
+    .with_context(|| format!("failed to mark job {} as cancelled", job_id))?;
+    Ok(())
+}
+
+/// Cancels queued jobs for an import. Running jobs notice the import left
+/// `processing` at their next progress check and cancel themselves.
+pub async fn cancel_queued_jobs(
+    executor: impl sqlx::Executor<'_, Database = Postgres>,
+    import_version_id: Uuid,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE background_jobs
+         SET status = 'cancelled',
+             updated_at = NOW(),
+             completed_at = NOW()
+         WHERE import_version_id = $1 AND status = 'queued'",
+    )
+    .bind(import_version_id)
+    .execute(executor)
+    .await
+    .with_context(|| {
+        format!(
+            "failed to cancel queued jobs for import {}",
+            import_version_id
+        )
+    })?;
+    Ok(())
+}
+
+async fn retry_job(
+    state: &AppState,
+    job: &ClaimedJob,
+    worker_id: &str,
+    delay: Duration,
+    job_error: &anyhow::Error,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE background_jobs
+         SET status = 'queued',
+             run_after = NOW() + ($3::float8 * INTERVAL '1 second'),
+             lease_owner = NULL,
+             lease_expires_at = NULL,
+             last_error = $4,
+             updated_at = NOW()
+         WHERE id = $1 AND lease_owner = $2",
+    )
+    .bind(job.id)
+    .bind(worker_id)
+    .bind(delay.as_secs_f64())
+    .bind(job_error.to_string())
+    .execute(&state.pool)
+    .await
+    .with_context(|| format!("failed to requeue job {}", job.id))?;
+
+    match job.kind {
+        JobKind::PostImport => {
+            update_import_progress(
+                state,
+                job.import_version_id,
+                "queued_decode",
+                35,
+                &format!(
+                    "Retrying decode and normalization after a transient error (attempt {} of {})",
+                    job.attempts + 1,
+                    job.max_attempts
+                ),
+            )
+            .await
+        }
+    }
+}
+
+async fn fail_job(
+    state: &AppState,
+    job: &ClaimedJob,
+    worker_id: &str,
+    error_text: &str,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE background_jobs
+         SET status = 'failed',
+             lease_owner = NULL,
+             lease_expires_at = NULL,
+             last_error = $3,
+             updated_at = NOW(),
+             completed_at = NOW()
+         WHERE id = $1 AND lease_owner = $2",
+    )
+    .bind(job.id)
+    .bind(worker_id)
+    .bind(error_text)
+    .execute(&state.pool)
+    .await
+    .with_context(|| format!("failed to mark job {} as failed", job.id))?;
+
+    mark_job_subject_failed(state, job.kind, job.import_version_id, error_text).await
+}
+
+/// Fails jobs whose workers kept dying mid-run (lease expired on the final
+/// attempt); the claim query skips them, so nothing else would finish them.
+async fn fail_exhausted_jobs(state: &AppState) -> anyhow::Result<()> {
+    let rows = sqlx::query(
+        "UPDATE background_jobs
+         SET status = 'failed',
+             lease_owner = NULL,
+             lease_expires_at = NULL,
+             last_error = 'worker stopped during the final attempt',
+             updated_at = NOW(),
+             completed_at = NOW()
+         WHERE status = 'running'
+           AND lease_expires_at < NOW()
+           AND attempts >= max_attempts
+         RETURNING id, kind, import_version_id, attempts",
+    )
+    .fetch_all(&state.pool)
+    .await
+    .with_context(|| "failed to fail exhausted background jobs")?;
+
+    for row in rows {
+        let job_id: Uuid = row.get("id");
+        let kind: String = row.get("kind");
+        let import_version_id: Uuid = row.get("import_version_id");
+        let attempts: i32 = row.get("attempts");
+        let Some(kind) = JobKind::parse(&kind) else {
+            continue;
+        };
+        warn!(
+            job_id = %job_id,
+            import_version_id = %import_version_id,
+            "background job abandoned after {} interrupted attempts",
+            attempts
+        );
+        mark_job_subject_failed(
+            state,
+            kind,
+            import_version_id,
+            &format!(
+                "processing was interrupted {} times by server restarts",
+                attempts
+            ),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn mark_job_subject_failed(
+    state: &AppState,
+    kind: JobKind,
+    import_version_id: Uuid,
+    error_text: &str,
+) -> anyhow::Result<()> {
+    match kind {
+        JobKind::PostImport => mark_import_failed(state, import_version_id, error_text).await,
+    }
+}
+
+/// Only infrastructure failures (lost connections, pool exhaustion,
+/// interrupted or timed-out I/O) are retried; decode errors, normalize
+/// timeouts, and I/O errors from missing, unreadable, or corrupt artifacts
+/// would fail the same way again.
+fn is_transient_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        if let Some(sqlx_error) = cause.downcast_ref::<sqlx::Error>() {
+            return match sqlx_error {
+                sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed => true,
+                sqlx::Error::Database(database_error) => database_error
+                    .code()
+                    .is_some_and(|code| code.starts_with("08") || code.starts_with("40")),
+                _ => false,
+            };
+        }
+        cause
+            .downcast_ref::<std::io::Error>()
+            .is_some_and(|io_error| {
+                matches!(
+                    io_error.kind(),
+                    std::io::ErrorKind::TimedOut
+                        | std::io::ErrorKind::Interrupted
+                        | std::io::ErrorKind::ConnectionReset
+                        | std::io::ErrorKind::ConnectionAborted
+                        | std::io::ErrorKind::WouldBlock
+                )
+            })
+    })
+}
+
+fn retry_delay(base_delay: Duration, attempts: i32) -> Duration {
+    let exponent = u32::try_from(attempts.saturating_sub(1))
+        .unwrap_or(0)
+        .min(16);
+    let delay_secs = base_delay
+        .as_secs()
+        .max(1)
+        .saturating_mul(1_u64 << exponent);
+    Duration::from_secs(delay_secs.min(MAX_RETRY_DELAY_SECS))
+}
@@ -1,15 +1,27 @@
 mod api;
+mod catalog;
 mod config;
 mod db;
+mod import_watch;
+mod jobs;
+mod planner;
 mod save;
 mod storage;
 
+use crate::catalog::GameCatalog;
+use crate::import_watch::ImportWatchSettings;
+use crate::jobs::JobQueueSettings;
+use crate::save::archive::ArchiveLimits;
+use crate::save::normalize::CharacterSelectionPolicy;
 use anyhow::Context;
 use axum::Router;
 use axum::extract::DefaultBodyLimit;
 use sqlx::PgPool;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing::info;
@@ -18,12 +30,18 @@ use tracing::info;
 pub struct AppSettings {
     pub artifact_storage_root: PathBuf,
     pub max_import_zip_bytes: usize,
+    pub archive_limits: ArchiveLimits,
+    pub import_directory_roots: Vec<PathBuf>,
+    pub job_max_attempts: i32,
+    pub character_selection_policy: CharacterSelectionPolicy,
 }
 
 #[derive(Clone)]
 pub struct AppState {
     pub pool: PgPool,
     pub settings: AppSettings,
+    pub catalog: Arc<GameCatalog>,
+    pub job_wakeup: Arc<Notify>,
 }
 
 #[tokio::main]
@@ -42,11 +60,93 @@ async fn main() -> anyhow::Result<()> {
             )
         })?;
 
+    let import_directory_roots = cfg
+        .import_directory_roots
+        .iter()
+        .map(|root| {
+            root.canonicalize().with_context(|| {
+                format!(
+                    "IMPORT_DIRECTORY_ROOTS entry {} does not exist",
+                    root.display()
+                )
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let mut import_watch_paths = Vec::with_capacity(cfg.import_watch_paths.len());
+    for watch_path in &cfg.import_watch_paths {
+        let canonical = watch_path.canonicalize().with_context(|| {
+            format!(
+                "IMPORT_WATCH_PATHS entry {} does not exist",
+                watch_path.display()
+            )
+        })?;
+        if !import_directory_roots
+            .iter()
+            .any(|root| canonical.starts_with(root))
+        {
+            anyhow::bail!(
+                "IMPORT_WATCH_PATHS entry {} is not inside IMPORT_DIRECTORY_ROOTS",
+                watch_path.display()
+            );
+        }
+        import_watch_paths.push(canonical);
+    }
+
     let settings = AppSettings {
         artifact_storage_root: cfg.artifact_storage_root.clone(),
         max_import_zip_bytes: cfg.max_import_zip_bytes,
+        archive_limits: ArchiveLimits {
+            max_entries: cfg.max_import_zip_entries,
+            max_entry_uncompressed_bytes: cfg.max_import_entry_uncompressed_bytes,
+            max_total_uncompressed_bytes: cfg.max_import_total_uncompressed_bytes,
+            max_compression_ratio: cfg.max_import_compression_ratio,
+        },
+        import_directory_roots,
+        job_max_attempts: cfg.job_max_attempts.max(1),
+        character_selection_policy: cfg.character_selection_policy,
     };
-    let state = AppState { pool, settings };
+    let catalog = GameCatalog::load(&cfg.game_data_dir).with_context(|| {
+        format!(
+            "failed to load game data from GAME_DATA_DIR {}",
+            cfg.game_data_dir.display()
+        )
+    })?;
+    info!(
+        "loaded game catalog: {} pal species, {} passives, {} active skills, {} breeding combos",
+        catalog.pals().len(),
+        catalog.passives().len(),
+        catalog.active_skills().len(),
+        catalog.breeding().len()
+    );
+    let state = AppState {
+        pool,
+        settings,
+        catalog: Arc::new(catalog),
+        job_wakeup: Arc::new(Notify::new()),
+    };
+
+    jobs::recover_orphaned_imports(&state.pool, state.settings.job_max_attempts).await?;
+    jobs::spawn_job_workers(
+        state.clone(),
+        JobQueueSettings {
+            worker_concurrency: cfg.job_worker_concurrency,
+            lease_duration: Duration::from_secs(cfg.job_lease_secs.max(3)),
+            poll_interval: Duration::from_secs(cfg.job_poll_secs.max(1)),
+            retry_base_delay: Duration::from_secs(cfg.job_retry_base_secs),
+        },
+    );
+
+    if !import_watch_paths.is_empty() {
+        import_watch::spawn_import_watchers(
+            state.clone(),
+            ImportWatchSettings {
+                world_paths: import_watch_paths,
+                poll_interval: Duration::from_secs(cfg.import_watch_poll_secs.max(1)),
+                settle_delay: Duration::from_secs(cfg.import_watch_settle_secs),
+                min_import_interval: Duration::from_secs(cfg.import_watch_min_interval_secs),
+            },
+        );
+    }
 
     let app: Router = api::routes::router(state)
         .layer(DefaultBodyLimit::disable())
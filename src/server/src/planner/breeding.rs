@@ -0,0 +1,233 @@
+use crate::catalog::GameCatalog;
+use crate::save::rules::MAX_PASSIVES;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+
+/// Chance, in percent, that a child inherits this many passives from the
+/// combined parent pool (fewer when the pool is smaller). Community
+/// datamined values; the game data snapshots do not include them.
+pub const INHERITED_PASSIVE_COUNT_PCT: [(usize, f64); 4] =
+    [(1, 40.0), (2, 30.0), (3, 20.0), (4, 10.0)];
+/// Chance, in percent, that this many random passives are added on top of
+/// the inherited ones, up to `MAX_PASSIVES` in total.
+pub const RANDOM_PASSIVE_COUNT_PCT: [(usize, f64); 4] =
+    [(0, 40.0), (1, 30.0), (2, 20.0), (3, 10.0)];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BreedingPair {
+    pub parent_a: String,
+    pub parent_b: String,
+    /// One child per record; two when the result depends on which parent is
+    /// the female.
+    pub children: Vec<String>,
+    pub gender_dependent: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BreedingStep {
+    pub parent_a: String,
+    pub parent_b: String,
+    pub child: String,
+    pub gender_dependent: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PassiveInheritance {
+    /// Distinct passives of both parents.
+    pub parent_pool: Vec<String>,
+    pub desired_passive_ids: Vec<String>,
+    /// Desired passives neither parent has; they can only come from a random
+    /// roll, which these odds do not count.
+    pub missing_from_parents: Vec<String>,
+    /// Chance the child inherits every desired passive.
+    pub all_desired_pct: f64,
+    /// Chance the child has exactly the desired passives and nothing else.
+    pub exact_pct: f64,
+}
+
+/// Children of a pair of species ids, in either order.
+pub fn pair_offspring(
+    catalog: &GameCatalog,
+    parent_a: &str,
+    parent_b: &str,
+) -> Option<BreedingPair> {
+    let records = catalog.breeding_index().pair(parent_a, parent_b);
+    let first = &catalog.breeding()[*records.first()?];
+    Some(BreedingPair {
+        parent_a: first.parent_a.clone(),
+        parent_b: first.parent_b.clone(),
+        children: records
+            .iter()
+            .map(|index| catalog.breeding()[*index].child.clone())
+            .collect(),
+        gender_dependent: records.len() > 1,
+    })
+}
+
+/// Every pair that can produce `child`, sorted by parents.
+pub fn parent_pairs(catalog: &GameCatalog, child: &str) -> Vec<BreedingStep> {
+    catalog
+        .breeding_index()
+        .child(child)
+        .iter()
+        .map(|index| breeding_step(catalog, *index))
+        .collect()
+}
+
+fn breeding_step(catalog: &GameCatalog, index: usize) -> BreedingStep {
+    let combo = &catalog.breeding()[index];
+    BreedingStep {
+        parent_a: combo.parent_a.clone(),
+        parent_b: combo.parent_b.clone(),
+        child: combo.child.clone(),
+        gender_dependent: catalog
+            .breeding_index()
+            .pair(&combo.parent_a, &combo.parent_b)
+            .len()
+            > 1,
+    }
+}
+
+/// Breedings that produce `target` from `owned` species, ordered so every
+/// step's parents are owned or bred by an earlier step; `None` when `target`
+/// cannot be reached. The search minimizes the size of the breeding tree; a
+/// species the tree needs twice is then listed once, so the step count can
+/// be lower than the tree size. Gender and the need for two individuals of a
+/// species to breed it with itself are not modelled.
+pub fn shortest_breeding_path(
+    catalog: &GameCatalog,
+    owned: &BTreeSet<String>,
+    target: &str,
+) -> Option<Vec<BreedingStep>> {
+    if owned.contains(target) {
+        return Some(Vec::new());
+    }
+
+    // Knuth's generalization of Dijkstra: a child costs both parents' costs
+    // plus one, which never undercuts either parent, so the first time a
+    // species is popped its cost is final.
+    let combos = catalog.breeding();
+    let mut cost: HashMap<&str, usize> = HashMap::new();
+    let mut bred_by: HashMap<&str, usize> = HashMap::new();
+    let mut done: HashSet<&str> = HashSet::new();
+    let mut queue = BinaryHeap::new();
+    for species in owned {
+        cost.insert(species.as_str(), 0);
+        queue.push(Reverse((0, species.as_str())));
+    }
+    while let Some(Reverse((species_cost, species))) = queue.pop() {
+        if !done.insert(species) {
+            continue;
+        }
+        if species == target {
+            break;
+        }
+        for index in catalog.breeding_index().parent(species) {
+            let combo = &combos[*index];
+            let other = if combo.parent_a == species {
+                combo.parent_b.as_str()
+            } else {
+                combo.parent_a.as_str()
+            };
+            if !done.contains(other) {
+                continue;
+            }
+            let child_cost = species_cost + cost[other] + 1;
+            let child = combo.child.as_str();
+            if cost.get(child).is_none_or(|current| child_cost < *current) {
+                cost.insert(child, child_cost);
+                bred_by.insert(child, *index);
+                queue.push(Reverse((child_cost, child)));
+            }
+        }
+    }
+    if !done.contains(target) {
+        return None;
+    }
+
+    let mut steps = Vec::new();
+    let mut visited = HashSet::new();
+    collect_steps(catalog, &bred_by, target, &mut visited, &mut steps);
+    Some(steps)
+}
+
+fn collect_steps<'a>(
+    catalog: &'a GameCatalog,
+    bred_by: &HashMap<&'a str, usize>,
+    species: &'a str,
+    visited: &mut HashSet<&'a str>,
+    steps: &mut Vec<BreedingStep>,
+) {
+    let Some(index) = bred_by.get(species) else {
+        return;
+    };
+    if !visited.insert(species) {
+        return;
+    }
+    let combo = &catalog.breeding()[*index];
+    collect_steps(catalog, bred_by, &combo.parent_a, visited, steps);
+    collect_steps(catalog, bred_by, &combo.parent_b, visited, steps);
+    steps.push(breeding_step(catalog, *index));
+}
+
+/// Odds that a child of parents with these passives gets `desired`. The
+/// inherited passives are drawn uniformly from the parents' combined pool.
+pub fn passive_inheritance(
+    parent_a: &[String],
+    parent_b: &[String],
+    desired: &[String],
+) -> PassiveInheritance {
+    let parent_pool: Vec<String> = parent_a
+        .iter()
+        .chain(parent_b)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .cloned()
+        .collect();
+    let desired: Vec<String> = desired
+        .iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .cloned()
+        .collect();
+    let missing_from_parents: Vec<String> = desired
+        .iter()
+        .filter(|passive| !parent_pool.contains(passive))
+        .cloned()
+        .collect();
+
+    let pool = parent_pool.len();
+    let wanted = desired.len();
+    let mut all_desired_pct = 0.0;
+    let mut exact_inherited_pct = 0.0;
+    if missing_from_parents.is_empty() && wanted <= MAX_PASSIVES {
+        for (count, pct) in INHERITED_PASSIVE_COUNT_PCT {
+            let drawn = count.min(pool);
+            if drawn < wanted {
+                continue;
+            }
+            all_desired_pct += pct * choose(pool - wanted, drawn - wanted) / choose(pool, drawn);
+            if drawn == wanted {
+                exact_inherited_pct += pct / choose(pool, drawn);
+            }
+        }
+    }
+    let no_random_share = if wanted >= MAX_PASSIVES {
+        1.0
+    } else {
+        RANDOM_PASSIVE_COUNT_PCT[0].1 / 100.0
+    };
+
+    PassiveInheritance {
+        parent_pool,
+        desired_passive_ids: desired,
+        missing_from_parents,
+        all_desired_pct,
+        exact_pct: exact_inherited_pct * no_random_share,
+    }
+}
+
+fn choose(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |total, i| total * (n - i) as f64 / (i + 1) as f64)
+}
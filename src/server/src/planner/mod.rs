@@ -0,0 +1,4 @@
+pub mod breeding;
+pub mod production;
+pub mod solver;
+pub mod stats;
@@ -0,0 +1,259 @@
+use crate::catalog::GameCatalog;
+use crate::catalog::facilities::{CatalogProductionCycle, CatalogProductionFacility};
+use crate::save::rules::species_work_levels;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Work speed added by each Statue of Power upgrade (`Rank_CraftSpeed`), in
+/// percent.
+pub const SOUL_WORK_SPEED_PCT: f64 = 3.0;
+/// Species `craft_speed` the reference work rates are measured at.
+const REFERENCE_CRAFT_SPEED: f64 = 100.0;
+const SECONDS_PER_HOUR: f64 = 3600.0;
+
+/// A base worker as stored in `planner_pals`.
+pub struct WorkerInput {
+    pub pal_instance_id: String,
+    pub species_id: Option<String>,
+    pub rank: Option<i32>,
+    pub rank_craftspeed: Option<i32>,
+    pub passive_skill_ids: Vec<String>,
+    /// Stored `work_suitability_ranks`. When non-empty these are taken as the
+    /// pal's effective levels; otherwise levels come from the species.
+    pub work_suitability_ranks: BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerContribution {
+    pub pal_instance_id: String,
+    pub species_id: Option<String>,
+    pub display_name: Option<String>,
+    pub passive_work_speed_pct: f64,
+    /// Passive ids the catalog cannot resolve; they count as no modifier.
+    pub unresolved_passive_ids: Vec<String>,
+    pub work_speed_multiplier: f64,
+    pub work_suitability: BTreeMap<String, i64>,
+    /// Workload per second by work type.
+    pub work_rates: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkTypePower {
+    pub work_suitability: String,
+    pub worker_count: usize,
+    /// Workload per second of all workers with this suitability.
+    pub work_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProductionNode {
+    pub building_id: String,
+    pub name: String,
+    /// Copies of the facility sharing the same workers.
+    pub stations: u32,
+    pub steps: Vec<ProductionNodeStep>,
+    pub growth_seconds: Option<f64>,
+    /// One station's cycle; null when a step has no workers.
+    pub cycle_seconds: Option<f64>,
+    pub cycles_per_hour: f64,
+    pub products: Vec<ProductRate>,
+    /// Work types no worker can do, which stall the node.
+    pub missing_work: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProductionNodeStep {
+    pub work_suitability: String,
+    pub workload: i64,
+    pub work_rate: f64,
+    pub seconds: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProductRate {
+    pub item_id: String,
+    pub count: i64,
+    pub per_hour: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BaseProduction {
+    /// Workload per second at each work suitability level, `Lv.1` first.
+    pub reference_work_rates: Vec<f64>,
+    pub workers: Vec<WorkerContribution>,
+    pub work_power: Vec<WorkTypePower>,
+    pub nodes: Vec<ProductionNode>,
+}
+
+/// Effective work suitability levels. Stored levels win; otherwise they are
+/// derived from the species levels with `species_work_levels`.
+pub fn effective_work_levels(
+    species_levels: &BTreeMap<String, i64>,
+    stored_levels: &BTreeMap<String, i64>,
+    rank: Option<i32>,
+) -> BTreeMap<String, i64> {
+    if !stored_levels.is_empty() {
+        return stored_levels.clone();
+    }
+    species_work_levels(species_levels, &BTreeMap::new(), rank)
+}
+
+pub fn worker_contribution(catalog: &GameCatalog, worker: &WorkerInput) -> WorkerContribution {
+    let rates = catalog.reference_work_rates();
+    let species = worker
+        .species_id
+        .as_deref()
+        .and_then(|species_id| catalog.species(species_id));
+
+    let mut passive_work_speed_pct = 0.0;
+    let mut unresolved_passive_ids = Vec::new();
+    for passive_id in &worker.passive_skill_ids {
+        match catalog.passive(passive_id) {
+            Some(passive) => passive_work_speed_pct += passive.modifiers.work_speed_pct,
+            None => unresolved_passive_ids.push(passive_id.clone()),
+        }
+    }
+
+    let craft_speed = species.as_ref().map_or(REFERENCE_CRAFT_SPEED, |species| {
+        species.pal.stats.craft_speed as f64
+    });
+    let soul_pct = f64::from(worker.rank_craftspeed.unwrap_or(0)) * SOUL_WORK_SPEED_PCT;
+    let work_speed_multiplier = craft_speed / REFERENCE_CRAFT_SPEED
+        * (1.0 + passive_work_speed_pct / 100.0).max(0.0)
+        * (1.0 + soul_pct / 100.0);
+
+    let species_levels = species
+        .as_ref()
+        .map(|species| species.pal.work_suitability.clone())
+        .unwrap_or_default();
+    let work_suitability =
+        effective_work_levels(&species_levels, &worker.work_suitability_ranks, worker.rank);
+    let work_rates = work_suitability
+        .iter()
+        .filter(|(_, level)| **level > 0)
+        .map(|(work, level)| {
+            let index = (*level as usize).min(rates.len()) - 1;
+            (work.clone(), rates[index] * work_speed_multiplier)
+        })
+        .collect();
+
+    WorkerContribution {
+        pal_instance_id: worker.pal_instance_id.clone(),
+        species_id: worker.species_id.clone(),
+        display_name: species.map(|species| species.pal.name.clone()),
+        passive_work_speed_pct,
+        unresolved_passive_ids,
+        work_speed_multiplier,
+        work_suitability,
+        work_rates,
+    }
+}
+
+/// Throughput of `stations` copies of a facility worked by `workers`. Steps
+/// run one after the other, and every worker with a step's suitability works
+/// that step. Stations cycle in parallel (crops grow side by side), but each
+/// step's total work per hour is limited by the workers' rate for it.
+pub fn production_node(
+    facility: &CatalogProductionFacility,
+    workers: &[WorkerContribution],
+    stations: u32,
+) -> Option<ProductionNode> {
+    let production = facility.production.as_ref()?;
+    Some(cycle_node(facility, production, workers, stations))
+}
+
+/// `production_node` for a facility whose production cycle is known.
+pub fn cycle_node(
+    facility: &CatalogProductionFacility,
+    production: &CatalogProductionCycle,
+    workers: &[WorkerContribution],
+    stations: u32,
+) -> ProductionNode {
+    let mut steps = Vec::with_capacity(production.steps.len());
+    let mut missing_work = Vec::new();
+    let mut cycle_seconds = production.growth_seconds.unwrap_or(0.0);
+    let mut work_cycles_per_hour = f64::INFINITY;
+    for step in &production.steps {
+        let work_rate = workers
+            .iter()
+            .filter_map(|worker| worker.work_rates.get(&step.work_suitability))
+            .fold(0.0, |total, rate| total + rate);
+        let seconds = (work_rate > 0.0).then(|| step.workload as f64 / work_rate);
+        match seconds {
+            Some(seconds) => {
+                cycle_seconds += seconds;
+                work_cycles_per_hour = work_cycles_per_hour.min(SECONDS_PER_HOUR / seconds);
+            }
+            None => missing_work.push(step.work_suitability.clone()),
+        }
+        steps.push(ProductionNodeStep {
+            work_suitability: step.work_suitability.clone(),
+            workload: step.workload,
+            work_rate,
+            seconds,
+        });
+    }
+
+    let cycle_seconds = missing_work.is_empty().then_some(cycle_seconds);
+    let cycles_per_hour = cycle_seconds.map_or(0.0, |cycle| {
+        (f64::from(stations) * SECONDS_PER_HOUR / cycle).min(work_cycles_per_hour)
+    });
+    let products = production
+        .products
+        .iter()
+        .map(|product| ProductRate {
+            item_id: product.item_id.clone(),
+            count: product.count,
+            per_hour: product.count as f64 * cycles_per_hour,
+        })
+        .collect();
+
+    ProductionNode {
+        building_id: facility.building_id.clone(),
+        name: facility.name.clone(),
+        stations,
+        steps,
+        growth_seconds: production.growth_seconds,
+        cycle_seconds,
+        cycles_per_hour,
+        products,
+        missing_work,
+    }
+}
+
+/// Work power per work type and, for every catalog production facility, the
+/// throughput if all of `workers` staffed one station of it alone.
+pub fn compute_base_production(catalog: &GameCatalog, workers: &[WorkerInput]) -> BaseProduction {
+    let workers: Vec<WorkerContribution> = workers
+        .iter()
+        .map(|worker| worker_contribution(catalog, worker))
+        .collect();
+
+    let mut work_power: BTreeMap<&str, WorkTypePower> = BTreeMap::new();
+    for worker in &workers {
+        for (work, rate) in &worker.work_rates {
+            let power = work_power
+                .entry(work.as_str())
+                .or_insert_with(|| WorkTypePower {
+                    work_suitability: work.clone(),
+                    worker_count: 0,
+                    work_rate: 0.0,
+                });
+            power.worker_count += 1;
+            power.work_rate += rate;
+        }
+    }
+
+    let nodes = catalog
+        .production_facilities()
+        .iter()
+        .filter_map(|facility| production_node(facility, &workers, 1))
+        .collect();
+
+    BaseProduction {
+        reference_work_rates: catalog.reference_work_rates().to_vec(),
+        work_power: work_power.into_values().collect(),
+        workers,
+        nodes,
+    }
+}
@@ -0,0 +1,283 @@
+use crate::catalog::GameCatalog;
+use crate::catalog::facilities::{CatalogProductionCycle, CatalogProductionFacility};
+use crate::planner::production::{ProductionNode, WorkerContribution, cycle_node};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Most copies of one facility the solver recommends.
+pub const MAX_STATIONS_PER_FACILITY: u32 = 20;
+const SECONDS_PER_HOUR: f64 = 3600.0;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProductionTarget {
+    pub item_id: String,
+    pub per_hour: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedWorker {
+    pub pal_instance_id: String,
+    pub species_id: Option<String>,
+    pub display_name: Option<String>,
+    pub building_id: String,
+    pub work_rates: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedStation {
+    pub building_id: String,
+    pub name: String,
+    pub pal_instance_ids: Vec<String>,
+    pub node: ProductionNode,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetOutcome {
+    pub item_id: String,
+    pub building_id: String,
+    pub target_per_hour: f64,
+    pub expected_per_hour: f64,
+    pub shortfall_per_hour: f64,
+    /// Why the target is not met: `worker_cap`, `no_capable_pals`, or
+    /// `station_limit`. Null when it is met.
+    pub limiting_factor: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SolvePlan {
+    /// In the order they were picked.
+    pub workers: Vec<PlannedWorker>,
+    pub stations: Vec<PlannedStation>,
+    pub targets: Vec<TargetOutcome>,
+}
+
+struct Station<'a> {
+    facility: &'a CatalogProductionFacility,
+    production: &'a CatalogProductionCycle,
+    workers: Vec<usize>,
+    stations: u32,
+}
+
+impl Station<'_> {
+    fn node(&self, candidates: &[WorkerContribution]) -> ProductionNode {
+        let workers: Vec<WorkerContribution> = self
+            .workers
+            .iter()
+            .map(|index| candidates[*index].clone())
+            .collect();
+        cycle_node(self.facility, self.production, &workers, self.stations)
+    }
+}
+
+fn produced(node: &ProductionNode, item_id: &str) -> f64 {
+    node.products
+        .iter()
+        .filter(|product| product.item_id == item_id)
+        .map(|product| product.per_hour)
+        .fold(0.0, |total, rate| total + rate)
+}
+
+/// Greedy assignment: the target furthest below its rate gets the next pick,
+/// which is either another station (when every station already waits on
+/// growth rather than work) or the unused candidate with the highest rate
+/// for the station's slowest step (the first step nobody works yet). A
+/// target with a step no remaining candidate can do gets no picks. Each pal
+/// works one facility. Ties go to the earlier target and the earlier
+/// candidate, so results are deterministic for a given candidate order.
+pub fn solve(
+    catalog: &GameCatalog,
+    candidates: &[WorkerContribution],
+    targets: &[ProductionTarget],
+    worker_cap: usize,
+) -> Result<SolvePlan, String> {
+    if targets.is_empty() {
+        return Err("targets must not be empty".to_string());
+    }
+
+    let mut stations: Vec<Station> = Vec::new();
+    let mut target_station = Vec::with_capacity(targets.len());
+    for target in targets {
+        if !target.per_hour.is_finite() || target.per_hour <= 0.0 {
+            return Err(format!(
+                "target per_hour for {} must be a positive number",
+                target.item_id
+            ));
+        }
+        let (facility, production) = catalog
+            .production_facilities()
+            .iter()
+            .find_map(|facility| {
+                let production = facility.production.as_ref()?;
+                production
+                    .products
+                    .iter()
+                    .any(|product| product.item_id == target.item_id)
+                    .then_some((facility, production))
+            })
+            .ok_or_else(|| format!("no production facility produces {}", target.item_id))?;
+        let index = match stations
+            .iter()
+            .position(|station| station.facility.building_id == facility.building_id)
+        {
+            Some(index) => index,
+            None => {
+                stations.push(Station {
+                    facility,
+                    production,
+                    workers: Vec::new(),
+                    stations: 1,
+                });
+                stations.len() - 1
+            }
+        };
+        target_station.push(index);
+    }
+
+    let mut used = vec![false; candidates.len()];
+    let mut picked: Vec<(usize, usize)> = Vec::new();
+    let mut limits: Vec<Option<&'static str>> = vec![None; targets.len()];
+    loop {
+        let mut pick: Option<(usize, f64)> = None;
+        for (index, target) in targets.iter().enumerate() {
+            if limits[index].is_some() {
+                continue;
+            }
+            let node = stations[target_station[index]].node(candidates);
+            let ratio = produced(&node, &target.item_id) / target.per_hour;
+            if ratio < 1.0 && pick.is_none_or(|(_, best)| ratio < best) {
+                pick = Some((index, ratio));
+            }
+        }
+        let Some((target_index, _)) = pick else {
+            break;
+        };
+
+        let station_index = target_station[target_index];
+        let station = &mut stations[station_index];
+        let node = station.node(candidates);
+        if let Some(cycle_seconds) = node.cycle_seconds {
+            let work_cycles_per_hour = node
+                .steps
+                .iter()
+                .filter_map(|step| step.seconds)
+                .fold(f64::INFINITY, |bound, seconds| {
+                    bound.min(SECONDS_PER_HOUR / seconds)
+                });
+            let station_cycles_per_hour =
+                f64::from(station.stations) * SECONDS_PER_HOUR / cycle_seconds;
+            if station_cycles_per_hour < work_cycles_per_hour {
+                if station.stations < MAX_STATIONS_PER_FACILITY {
+                    station.stations += 1;
+                } else {
+                    limits[target_index] = Some("station_limit");
+                }
+                continue;
+            }
+        }
+
+        if picked.len() >= worker_cap {
+            limits[target_index] = Some("worker_cap");
+            continue;
+        }
+        let stalled = node.missing_work.iter().any(|work| {
+            !candidates
+                .iter()
+                .enumerate()
+                .any(|(index, candidate)| !used[index] && candidate.work_rates.contains_key(work))
+        });
+        if stalled {
+            limits[target_index] = Some("no_capable_pals");
+            continue;
+        }
+        let Some(bottleneck) = node.steps.iter().reduce(|slowest, step| {
+            let slowest_seconds = slowest.seconds.unwrap_or(f64::INFINITY);
+            if step.seconds.unwrap_or(f64::INFINITY) > slowest_seconds {
+                step
+            } else {
+                slowest
+            }
+        }) else {
+            limits[target_index] = Some("no_capable_pals");
+            continue;
+        };
+        let mut best: Option<(usize, f64)> = None;
+        for (index, candidate) in candidates.iter().enumerate() {
+            if used[index] {
+                continue;
+            }
+            let rate = candidate
+                .work_rates
+                .get(&bottleneck.work_suitability)
+                .copied()
+                .unwrap_or(0.0);
+            if rate > 0.0 && best.is_none_or(|(_, best_rate)| rate > best_rate) {
+                best = Some((index, rate));
+            }
+        }
+        match best {
+            Some((index, _)) => {
+                used[index] = true;
+                station.workers.push(index);
+                picked.push((index, station_index));
+            }
+            None => limits[target_index] = Some("no_capable_pals"),
+        }
+    }
+
+    let nodes: Vec<ProductionNode> = stations
+        .iter()
+        .map(|station| station.node(candidates))
+        .collect();
+    let targets = targets
+        .iter()
+        .enumerate()
+        .map(|(index, target)| {
+            let node = &nodes[target_station[index]];
+            let expected_per_hour = produced(node, &target.item_id);
+            let shortfall_per_hour = (target.per_hour - expected_per_hour).max(0.0);
+            TargetOutcome {
+                item_id: target.item_id.clone(),
+                building_id: node.building_id.clone(),
+                target_per_hour: target.per_hour,
+                expected_per_hour,
+                shortfall_per_hour,
+                limiting_factor: (shortfall_per_hour > 0.0)
+                    .then_some(limits[index])
+                    .flatten(),
+            }
+        })
+        .collect();
+    let workers = picked
+        .iter()
+        .map(|(index, station_index)| {
+            let candidate = &candidates[*index];
+            PlannedWorker {
+                pal_instance_id: candidate.pal_instance_id.clone(),
+                species_id: candidate.species_id.clone(),
+                display_name: candidate.display_name.clone(),
+                building_id: stations[*station_index].facility.building_id.clone(),
+                work_rates: candidate.work_rates.clone(),
+            }
+        })
+        .collect();
+    let stations = stations
+        .iter()
+        .zip(nodes)
+        .map(|(station, node)| PlannedStation {
+            building_id: station.facility.building_id.clone(),
+            name: station.facility.name.clone(),
+            pal_instance_ids: station
+                .workers
+                .iter()
+                .map(|index| candidates[*index].pal_instance_id.clone())
+                .collect(),
+            node,
+        })
+        .collect();
+
+    Ok(SolvePlan {
+        workers,
+        stations,
+        targets,
+    })
+}
@@ -0,0 +1,134 @@
+use crate::catalog::GameCatalog;
+use serde::Serialize;
+
+/// Stat bonus per condenser star (`Rank` above 1), in percent.
+pub const CONDENSER_STAT_PCT: f64 = 5.0;
+/// Stat bonus per Statue of Power upgrade (`Rank_HP`, `Rank_Attack`,
+/// `Rank_Defence`), in percent.
+pub const SOUL_STAT_PCT: f64 = 3.0;
+/// Share of the level-scaled stat a talent of 100 adds.
+const TALENT_SCALE: f64 = 0.3;
+const MAX_TALENT: f64 = 100.0;
+
+/// A pal's stat inputs as stored in `planner_pals`.
+pub struct StatInput {
+    pub pal_instance_id: String,
+    pub owner_player_uid: Option<String>,
+    pub species_id: Option<String>,
+    pub nickname: Option<String>,
+    pub level: Option<i32>,
+    pub rank: Option<i32>,
+    pub rank_hp: Option<i32>,
+    pub rank_attack: Option<i32>,
+    pub rank_defense: Option<i32>,
+    pub talent_hp: Option<i32>,
+    pub talent_shot: Option<i32>,
+    pub talent_defense: Option<i32>,
+    pub passive_skill_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PalStats {
+    pub pal_instance_id: String,
+    pub owner_player_uid: Option<String>,
+    pub species_id: Option<String>,
+    pub display_name: Option<String>,
+    pub nickname: Option<String>,
+    pub level: Option<i32>,
+    pub passive_hp_pct: f64,
+    pub passive_attack_pct: f64,
+    pub passive_defense_pct: f64,
+    /// Passive ids the catalog cannot resolve; they count as no modifier.
+    pub unresolved_passive_ids: Vec<String>,
+    /// Null when the species is not in the catalog.
+    pub max_hp: Option<i64>,
+    pub attack: Option<i64>,
+    pub defense: Option<i64>,
+    /// Sorting score, not a game value: `max_hp / 10 + attack + defense`.
+    pub combat_power: Option<i64>,
+}
+
+/// Effective max HP, attack, and defense with the community-datamined
+/// formula:
+///
+/// - HP: `500 + 5 * level + base_hp * 0.5 * level * (1 + 0.3 * talent_hp / 100)`
+/// - attack: `100 + base_shot * 0.075 * level * (1 + 0.3 * talent_shot / 100)`
+/// - defense: `50 + base_defense * 0.075 * level * (1 + 0.3 * talent_defense / 100)`
+///
+/// each multiplied by the condenser bonus, the summed passive bonus, and the
+/// Statue of Power bonus, then floored. Pal attacks scale with the species'
+/// ranged attack (`Talent_Shot`); melee attack only matters for players.
+pub fn pal_stats(catalog: &GameCatalog, input: &StatInput) -> PalStats {
+    let species = input
+        .species_id
+        .as_deref()
+        .and_then(|species_id| catalog.species(species_id));
+
+    let mut passive_hp_pct = 0.0;
+    let mut passive_attack_pct = 0.0;
+    let mut passive_defense_pct = 0.0;
+    let mut unresolved_passive_ids = Vec::new();
+    for passive_id in &input.passive_skill_ids {
+        match catalog.passive(passive_id) {
+            Some(passive) => {
+                passive_hp_pct += passive.modifiers.max_hp_pct;
+                passive_attack_pct += passive.modifiers.attack_pct;
+                passive_defense_pct += passive.modifiers.defense_pct;
+            }
+            None => unresolved_passive_ids.push(passive_id.clone()),
+        }
+    }
+
+    let level = f64::from(input.level.unwrap_or(1).max(1));
+    let condenser_pct = f64::from((input.rank.unwrap_or(1) - 1).max(0)) * CONDENSER_STAT_PCT;
+    let bonus = |passive_pct: f64, soul: Option<i32>| {
+        (1.0 + condenser_pct / 100.0)
+            * (1.0 + passive_pct / 100.0).max(0.0)
+            * (1.0 + f64::from(soul.unwrap_or(0).max(0)) * SOUL_STAT_PCT / 100.0)
+    };
+    let (max_hp, attack, defense) = match &species {
+        Some(species) => {
+            let stats = &species.pal.stats;
+            let max_hp =
+                (500.0 + 5.0 * level + level_scaled(stats.hp, 0.5, level, input.talent_hp))
+                    * bonus(passive_hp_pct, input.rank_hp);
+            let attack = (100.0
+                + level_scaled(stats.ranged_attack, 0.075, level, input.talent_shot))
+                * bonus(passive_attack_pct, input.rank_attack);
+            let defense = (50.0 + level_scaled(stats.defense, 0.075, level, input.talent_defense))
+                * bonus(passive_defense_pct, input.rank_defense);
+            (
+                Some(max_hp.floor() as i64),
+                Some(attack.floor() as i64),
+                Some(defense.floor() as i64),
+            )
+        }
+        None => (None, None, None),
+    };
+    let combat_power = max_hp
+        .zip(attack)
+        .zip(defense)
+        .map(|((max_hp, attack), defense)| max_hp / 10 + attack + defense);
+
+    PalStats {
+        pal_instance_id: input.pal_instance_id.clone(),
+        owner_player_uid: input.owner_player_uid.clone(),
+        species_id: input.species_id.clone(),
+        display_name: species.map(|species| species.pal.name.clone()),
+        nickname: input.nickname.clone(),
+        level: input.level,
+        passive_hp_pct,
+        passive_attack_pct,
+        passive_defense_pct,
+        unresolved_passive_ids,
+        max_hp,
+        attack,
+        defense,
+        combat_power,
+    }
+}
+
+fn level_scaled(base: i64, scale: f64, level: f64, talent: Option<i32>) -> f64 {
+    let talent = f64::from(talent.unwrap_or(0)).clamp(0.0, MAX_TALENT);
+    base as f64 * scale * level * (1.0 + TALENT_SCALE * talent / MAX_TALENT)
+}
@@ -0,0 +1,719 @@
+use anyhow::{Context, Result, bail};
+use flate2::read::GzDecoder;
+use serde::Serialize;
+use sevenz_rust::{Archive as SevenZipArchive, BlockDecoder, SevenZMethod};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::path::{Component, Path, PathBuf};
+use tar::{Archive as TarArchive, EntryType};
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+const UNIX_FILE_TYPE_MASK: u32 = 0o170000;
+const UNIX_SYMLINK_TYPE: u32 = 0o120000;
+const WINDOWS_REPARSE_POINT_ATTRIBUTE: u32 = 0x400;
+const WINDOWS_UNIX_EXTENSION_ATTRIBUTE: u32 = 0x8000;
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const ZIP_EMPTY_MAGIC: &[u8] = b"PK\x05\x06";
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const SEVEN_ZIP_MAGIC: &[u8] = &[b'7', b'z', 0xbc, 0xaf, 0x27, 0x1c];
+const TAR_MAGIC_OFFSET: usize = 257;
+const TAR_MAGIC: &[u8] = b"ustar";
+// Small entries are exempt from the ratio check; a few KB of padding compresses
+// far beyond any sane bomb ratio without posing a resource risk.
+const COMPRESSION_RATIO_MIN_BYTES: u64 = 1_048_576;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    SevenZip,
+    /// World folder read from the server filesystem; persisted as a synthesized ZIP.
+    Directory,
+}
+
+impl ArchiveFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Zip => "zip",
+            Self::Tar => "tar",
+            Self::TarGz => "tar_gz",
+            Self::SevenZip => "seven_zip",
+            Self::Directory => "directory",
+        }
+    }
+
+    pub fn file_extension(self) -> &'static str {
+        match self {
+            Self::Zip => "zip",
+            Self::Tar => "tar",
+            Self::TarGz => "tar.gz",
+            Self::SevenZip => "7z",
+            Self::Directory => "zip",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedArchiveEntry {
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchiveExtraction {
+    pub format: ArchiveFormat,
+    pub entries: Vec<ArchiveEntry>,
+    pub skipped: Vec<SkippedArchiveEntry>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ArchiveLimits {
+    pub max_entries: usize,
+    pub max_entry_uncompressed_bytes: u64,
+    pub max_total_uncompressed_bytes: u64,
+    pub max_compression_ratio: u64,
+}
+
+impl Default for ArchiveLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: 4096,
+            max_entry_uncompressed_bytes: 536_870_912,
+            max_total_uncompressed_bytes: 2_147_483_648,
+            max_compression_ratio: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ArchiveLimitError {
+    TooManyEntries {
+        entry_count: usize,
+        limit: usize,
+    },
+    EntryTooLarge {
+        path: String,
+        byte_size: u64,
+        limit: u64,
+    },
+    TotalTooLarge {
+        byte_size: u64,
+        limit: u64,
+    },
+    CompressionRatioExceeded {
+        path: String,
+        ratio: u64,
+        limit: u64,
+    },
+}
+
+impl fmt::Display for ArchiveLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyEntries { entry_count, limit } => {
+                write!(
+                    f,
+                    "archive has too many entries ({} > {})",
+                    entry_count, limit
+                )
+            }
+            Self::EntryTooLarge {
+                path,
+                byte_size,
+                limit,
+            } => write!(
+                f,
+                "archive entry {} is too large when uncompressed ({} bytes > {} bytes)",
+                path, byte_size, limit
+            ),
+            Self::TotalTooLarge { byte_size, limit } => write!(
+                f,
+                "archive is too large when uncompressed ({} bytes > {} bytes)",
+                byte_size, limit
+            ),
+            Self::CompressionRatioExceeded { path, ratio, limit } => write!(
+                f,
+                "archive entry {} exceeds the compression ratio limit ({}:1 > {}:1)",
+                path, ratio, limit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ArchiveLimitError {}
+
+pub fn detect_archive_format(bytes: &[u8]) -> Option<ArchiveFormat> {
+    if bytes.starts_with(ZIP_MAGIC) || bytes.starts_with(ZIP_EMPTY_MAGIC) {
+        Some(ArchiveFormat::Zip)
+    } else if bytes.starts_with(SEVEN_ZIP_MAGIC) {
+        Some(ArchiveFormat::SevenZip)
+    } else if bytes.starts_with(GZIP_MAGIC) {
+        Some(ArchiveFormat::TarGz)
+    } else if bytes
+        .get(TAR_MAGIC_OFFSET..TAR_MAGIC_OFFSET + TAR_MAGIC.len())
+        .is_some_and(|magic| magic == TAR_MAGIC)
+    {
+        Some(ArchiveFormat::Tar)
+    } else {
+        None
+    }
+}
+
+pub fn parse_archive_entries(
+    archive_bytes: &[u8],
+    limits: &ArchiveLimits,
+) -> Result<ArchiveExtraction> {
+    let Some(format) = detect_archive_format(archive_bytes) else {
+        bail!("uploaded file is not a ZIP, tar, tar.gz, or 7z archive");
+    };
+
+    let mut collector = EntryCollector::new(limits);
+    match format {
+        ArchiveFormat::Zip => read_zip_entries(archive_bytes, &mut collector)?,
+        ArchiveFormat::Tar => read_tar_entries(archive_bytes, None, &mut collector)?,
+        ArchiveFormat::TarGz => read_tar_entries(
+            GzDecoder::new(archive_bytes),
+            Some(archive_bytes.len() as u64),
+            &mut collector,
+        )?,
+        ArchiveFormat::SevenZip => read_seven_zip_entries(archive_bytes, &mut collector)?,
+        ArchiveFormat::Directory => unreachable!("directories are never detected from bytes"),
+    }
+
+    if collector.entries.is_empty() {
+        bail!("archive contains no files");
+    }
+
+    Ok(ArchiveExtraction {
+        format,
+        entries: collector.entries,
+        skipped: collector.skipped,
+    })
+}
+
+pub fn read_directory_entries(
+    directory: &Path,
+    limits: &ArchiveLimits,
+) -> Result<ArchiveExtraction> {
+    let mut collector = EntryCollector::new(limits);
+    read_directory_level(directory, "", &mut collector)?;
+
+    if collector.entries.is_empty() {
+        bail!("directory contains no files");
+    }
+
+    Ok(ArchiveExtraction {
+        format: ArchiveFormat::Directory,
+        entries: collector.entries,
+        skipped: collector.skipped,
+    })
+}
+
+struct EntryCollector<'a> {
+    limits: &'a ArchiveLimits,
+    entry_count: usize,
+    total_uncompressed_bytes: u64,
+    entries: Vec<ArchiveEntry>,
+    skipped: Vec<SkippedArchiveEntry>,
+}
+
+impl<'a> EntryCollector<'a> {
+    fn new(limits: &'a ArchiveLimits) -> Self {
+        Self {
+            limits,
+            entry_count: 0,
+            total_uncompressed_bytes: 0,
+            entries: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+
+    fn count_entry(&mut self) -> Result<(), ArchiveLimitError> {
+        self.entry_count += 1;
+        check_entry_count(self.entry_count, self.limits)
+    }
+
+    fn skip(&mut self, path: String, reason: &str) {
+        self.skipped.push(SkippedArchiveEntry {
+            path,
+            reason: reason.to_string(),
+        });
+    }
+
+    fn check_declared(
+        &self,
+        path: &str,
+        declared_size: u64,
+        compressed_size: Option<u64>,
+    ) -> Result<(), ArchiveLimitError> {
+        check_entry_size(path, declared_size, compressed_size, self.limits)?;
+        check_total_size(
+            self.total_uncompressed_bytes.saturating_add(declared_size),
+            self.limits,
+        )
+    }
+
+    // Declared sizes are attacker-controlled, so the inflated stream is capped
+    // one byte past the limit and re-checked against what was actually read.
+    fn read_entry(
+        &mut self,
+        path: String,
+        reader: impl Read,
+        compressed_size: Option<u64>,
+    ) -> Result<()> {
+        let mut bytes = Vec::new();
+        reader
+            .take(self.limits.max_entry_uncompressed_bytes.saturating_add(1))
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("failed to read archive entry bytes for {}", path))?;
+        let actual_size = bytes.len() as u64;
+        check_entry_size(&path, actual_size, compressed_size, self.limits)?;
+        self.total_uncompressed_bytes = self.total_uncompressed_bytes.saturating_add(actual_size);
+        check_total_size(self.total_uncompressed_bytes, self.limits)?;
+
+        self.entries.push(ArchiveEntry { path, bytes });
+        Ok(())
+    }
+}
+
+fn read_zip_entries(zip_bytes: &[u8], collector: &mut EntryCollector<'_>) -> Result<()> {
+    let reader = Cursor::new(zip_bytes);
+    let mut archive =
+        ZipArchive::new(reader).with_context(|| "uploaded file is not a readable ZIP archive")?;
+    check_entry_count(archive.len(), collector.limits)?;
+
+    for index in 0..archive.len() {
+        collector.count_entry()?;
+        let (raw_name, is_dir, is_symlink, declared_size, compressed_size) = {
+            let file = archive
+                .by_index_raw(index)
+                .with_context(|| format!("failed to read ZIP entry at index {}", index))?;
+            (
+                file.name().to_string(),
+                file.is_dir(),
+                file.unix_mode()
+                    .is_some_and(|mode| mode & UNIX_FILE_TYPE_MASK == UNIX_SYMLINK_TYPE),
+                file.size(),
+                file.compressed_size(),
+            )
+        };
+        if is_dir {
+            continue;
+        }
+
+        let path = sanitize_archive_path(&raw_name)?;
+        if is_symlink {
+            collector.skip(path, "symlink");
+            continue;
+        }
+
+        collector.check_declared(&path, declared_size, Some(compressed_size))?;
+
+        let file = match archive.by_index(index) {
+            Ok(file) => file,
+            Err(ZipError::UnsupportedArchive(message))
+                if message == ZipError::PASSWORD_REQUIRED =>
+            {
+                collector.skip(path, "encrypted");
+                continue;
+            }
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("failed to read ZIP entry at index {}", index));
+            }
+        };
+        collector.read_entry(path, file, Some(compressed_size))?;
+    }
+
+    Ok(())
+}
+
+fn read_tar_entries(
+    reader: impl Read,
+    compressed_stream_size: Option<u64>,
+    collector: &mut EntryCollector<'_>,
+) -> Result<()> {
+    let mut archive = TarArchive::new(reader);
+    let tar_entries = archive
+        .entries()
+        .with_context(|| "uploaded file is not a readable tar archive")?;
+
+    for tar_entry in tar_entries {
+        let tar_entry = tar_entry.with_context(|| "failed to read tar entry header")?;
+        collector.count_entry()?;
+
+        let entry_type = tar_entry.header().entry_type();
+        if entry_type.is_dir() {
+            continue;
+        }
+
+        let raw_name = String::from_utf8_lossy(&tar_entry.path_bytes()).to_string();
+        let path = sanitize_archive_path(&raw_name)?;
+        match entry_type {
+            EntryType::Regular | EntryType::Continuous => {}
+            EntryType::Symlink => {
+                collector.skip(path, "symlink");
+                continue;
+            }
+            EntryType::Link => {
+                collector.skip(path, "hardlink");
+                continue;
+            }
+            _ => {
+                collector.skip(path, "unsupported_entry_type");
+                continue;
+            }
+        }
+
+        collector.check_declared(&path, tar_entry.size(), None)?;
+        collector.read_entry(path.clone(), tar_entry, None)?;
+
+        // A gzip stream only exposes its ratio as a whole, so it is checked against
+        // everything inflated so far rather than per entry.
+        if let Some(compressed_stream_size) = compressed_stream_size {
+            check_compression_ratio(
+                &path,
+                collector.total_uncompressed_bytes,
+                compressed_stream_size,
+                collector.limits,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_seven_zip_entries(archive_bytes: &[u8], collector: &mut EntryCollector<'_>) -> Result<()> {
+    let mut source = Cursor::new(archive_bytes);
+    let archive = match SevenZipArchive::read(&mut source, archive_bytes.len() as u64, &[]) {
+        Ok(archive) => archive,
+        Err(sevenz_rust::Error::PasswordRequired) => {
+            bail!("7z archive has encrypted headers; password-protected archives are not supported")
+        }
+        Err(error) => {
+            return Err(anyhow::anyhow!(error.to_string()))
+                .with_context(|| "uploaded file is not a readable 7z archive");
+        }
+    };
+    check_entry_count(archive.files.len(), collector.limits)?;
+
+    for (file_index, file) in archive.files.iter().enumerate() {
+        if archive.stream_map.file_folder_index[file_index].is_some() {
+            continue;
+        }
+        collector.count_entry()?;
+        if file.is_directory() || file.is_anti_item() {
+            continue;
+        }
+        let path = sanitize_archive_path(file.name())?;
+        if is_seven_zip_symlink(file.windows_attributes()) {
+            collector.skip(path, "symlink");
+            continue;
+        }
+        collector.read_entry(path, std::io::empty(), None)?;
+    }
+
+    for folder_index in 0..archive.folders.len() {
+        let is_encrypted = archive.folders[folder_index]
+            .coders
+            .iter()
+            .any(|coder| coder.decompression_method_id() == SevenZMethod::ID_AES256SHA256);
+        if is_encrypted {
+            let first_file_index = archive.stream_map.folder_first_file_index[folder_index];
+            let file_count = archive.folders[folder_index].num_unpack_sub_streams;
+            for file in &archive.files[first_file_index..first_file_index + file_count] {
+                collector.count_entry()?;
+                if !file.is_directory() {
+                    let path = sanitize_archive_path(file.name())?;
+                    collector.skip(path, "encrypted");
+                }
+            }
+            continue;
+        }
+
+        // A folder (solid block) only exposes its ratio as a whole: its packed
+        // streams against everything it unpacks. The declared size is checked
+        // up front, then the bytes actually inflated as the block is read.
+        let first_pack_index = archive.stream_map.folder_first_pack_stream_index[folder_index];
+        let pack_count = archive.folders[folder_index].packed_streams.len();
+        let packed_size: u64 = archive
+            .pack_sizes
+            .get(first_pack_index..first_pack_index + pack_count)
+            .map(|sizes| sizes.iter().sum())
+            .unwrap_or_default();
+        check_compression_ratio(
+            &format!("7z block {}", folder_index),
+            archive.folders[folder_index].get_unpack_size(),
+            packed_size,
+            collector.limits,
+        )?;
+        let unpacked_before_folder = collector.total_uncompressed_bytes;
+
+        // Solid blocks must be decoded front to back, so entries that are skipped
+        // still have their bytes drained from the block stream.
+        let decoder = BlockDecoder::new(folder_index, &archive, &[], &mut source);
+        let mut failure: Option<anyhow::Error> = None;
+        let block_result = decoder.for_each_entries(&mut |file, reader| {
+            let outcome = (|| -> Result<()> {
+                collector.count_entry()?;
+                if file.is_directory() || file.is_anti_item() {
+                    return Ok(());
+                }
+                let path = sanitize_archive_path(file.name())?;
+                if is_seven_zip_symlink(file.windows_attributes()) {
+                    std::io::copy(reader, &mut std::io::sink())
+                        .with_context(|| format!("failed to skip 7z entry {}", path))?;
+                    collector.skip(path, "symlink");
+                    return Ok(());
+                }
+                collector.check_declared(&path, file.size(), None)?;
+                collector.read_entry(path.clone(), reader, None)?;
+                check_compression_ratio(
+                    &path,
+                    collector.total_uncompressed_bytes - unpacked_before_folder,
+                    packed_size,
+                    collector.limits,
+                )?;
+                Ok(())
+            })();
+
+            match outcome {
+                Ok(()) => Ok(true),
+                Err(error) => {
+                    failure = Some(error);
+                    Ok(false)
+                }
+            }
+        });
+
+        if let Some(error) = failure {
+            return Err(error);
+        }
+        block_result
+            .map_err(|error| anyhow::anyhow!(error.to_string()))
+            .with_context(|| format!("failed to decode 7z block {}", folder_index))?;
+    }
+
+    Ok(())
+}
+
+fn read_directory_level(
+    directory: &Path,
+    relative_prefix: &str,
+    collector: &mut EntryCollector<'_>,
+) -> Result<()> {
+    let mut children = std::fs::read_dir(directory)
+        .with_context(|| format!("failed to list directory {}", directory.display()))?
+        .collect::<std::io::Result<Vec<_>>>()
+        .with_context(|| format!("failed to list directory {}", directory.display()))?;
+    children.sort_by_key(|child| child.file_name());
+
+    for child in children {
+        collector.count_entry()?;
+        let name = child.file_name().to_string_lossy().to_string();
+        let relative_path = if relative_prefix.is_empty() {
+            name
+        } else {
+            format!("{}/{}", relative_prefix, name)
+        };
+        let path = sanitize_archive_path(&relative_path)?;
+
+        // symlink_metadata does not follow links, so a link can never pull files
+        // from outside the allow-listed root into the import.
+        let metadata = std::fs::symlink_metadata(child.path())
+            .with_context(|| format!("failed to stat {}", child.path().display()))?;
+        let file_type = metadata.file_type();
+        if file_type.is_symlink() {
+            collector.skip(path, "symlink");
+        } else if file_type.is_dir() {
+            read_directory_level(&child.path(), &path, collector)?;
+        } else if file_type.is_file() {
+            collector.check_declared(&path, metadata.len(), None)?;
+            let file = File::open(child.path())
+                .with_context(|| format!("failed to open {}", child.path().display()))?;
+            collector.read_entry(path, file, None)?;
+        } else {
+            collector.skip(path, "unsupported_entry_type");
+        }
+    }
+
+    Ok(())
+}
+
+fn is_seven_zip_symlink(windows_attributes: u32) -> bool {
+    if windows_attributes & WINDOWS_REPARSE_POINT_ATTRIBUTE != 0 {
+        return true;
+    }
+    windows_attributes & WINDOWS_UNIX_EXTENSION_ATTRIBUTE != 0
+        && (windows_attributes >> 16) & UNIX_FILE_TYPE_MASK == UNIX_SYMLINK_TYPE
+}
+
+fn check_entry_count(entry_count: usize, limits: &ArchiveLimits) -> Result<(), ArchiveLimitError> {
+    if entry_count > limits.max_entries {
+        return Err(ArchiveLimitError::TooManyEntries {
+            entry_count,
+            limit: limits.max_entries,
+        });
+    }
+    Ok(())
+}
+
+fn check_entry_size(
+    path: &str,
+    uncompressed_size: u64,
+    compressed_size: Option<u64>,
+    limits: &ArchiveLimits,
+) -> Result<(), ArchiveLimitError> {
+    if uncompressed_size > limits.max_entry_uncompressed_bytes {
+        return Err(ArchiveLimitError::EntryTooLarge {
+            path: path.to_string(),
+            byte_size: uncompressed_size,
+            limit: limits.max_entry_uncompressed_bytes,
+        });
+    }
+
+    match compressed_size {
+        Some(compressed_size) => {
+            check_compression_ratio(path, uncompressed_size, compressed_size, limits)
+        }
+        None => Ok(()),
+    }
+}
+
+fn check_compression_ratio(
+    path: &str,
+    uncompressed_size: u64,
+    compressed_size: u64,
+    limits: &ArchiveLimits,
+) -> Result<(), ArchiveLimitError> {
+    let ratio = uncompressed_size / compressed_size.max(1);
+    if uncompressed_size >= COMPRESSION_RATIO_MIN_BYTES && ratio > limits.max_compression_ratio {
+        return Err(ArchiveLimitError::CompressionRatioExceeded {
+            path: path.to_string(),
+            ratio,
+            limit: limits.max_compression_ratio,
+        });
+    }
+    Ok(())
+}
+
+fn check_total_size(total_size: u64, limits: &ArchiveLimits) -> Result<(), ArchiveLimitError> {
+    if total_size > limits.max_total_uncompressed_bytes {
+        return Err(ArchiveLimitError::TotalTooLarge {
+            byte_size: total_size,
+            limit: limits.max_total_uncompressed_bytes,
+        });
+    }
+    Ok(())
+}
+
+pub fn detect_world_root(entries: &[ArchiveEntry]) -> Result<String> {
+    let paths: BTreeSet<String> = entries.iter().map(|entry| entry.path.clone()).collect();
+    let mut candidates = BTreeSet::new();
+
+    for path in &paths {
+        if path.ends_with("Level.sav") {
+            let root = Path::new(path)
+                .parent()
+                .map(|value| value.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            candidates.insert(root);
+        }
+    }
+
+    if candidates.is_empty() {
+        bail!("archive does not contain Level.sav in any path");
+    }
+
+    let mut ordered: Vec<String> = candidates.into_iter().collect();
+    ordered.sort_by(|a, b| {
+        let depth_a = a.matches('/').count();
+        let depth_b = b.matches('/').count();
+        depth_a.cmp(&depth_b).then_with(|| a.cmp(b))
+    });
+
+    let mut found_player_singular_only = false;
+    for root in ordered {
+        let level_path = prefixed(&root, "Level.sav");
+        let players_prefix = prefixed(&root, "Players/");
+        let player_prefix = prefixed(&root, "Player/");
+
+        let has_level = paths.contains(&level_path);
+        let has_players = paths
+            .iter()
+            .any(|path| path.starts_with(&players_prefix) && path.ends_with(".sav"));
+        let has_player_singular = paths
+            .iter()
+            .any(|path| path.starts_with(&player_prefix) && path.ends_with(".sav"));
+
+        if has_level && has_players && !has_player_singular {
+            return Ok(root);
+        }
+        if has_level && has_player_singular && !has_players {
+            found_player_singular_only = true;
+        }
+    }
+
+    if found_player_singular_only {
+        bail!("archive uses Player/ directory; only Players/ is supported");
+    }
+    bail!("archive does not contain a valid world root with Level.sav and Players/*.sav");
+}
+
+pub fn strip_root_prefix(root: &str, full_path: &str) -> Option<String> {
+    if root.is_empty() {
+        return Some(full_path.to_string());
+    }
+
+    let prefix = format!("{}/", root);
+    full_path
+        .strip_prefix(&prefix)
+        .map(std::string::ToString::to_string)
+}
+
+pub fn is_supported_world_file(relative_path: &str) -> bool {
+    matches!(
+        relative_path,
+        "Level.sav" | "LevelMeta.sav" | "LocalData.sav" | "WorldOption.sav"
+    ) || (relative_path.starts_with("Players/") && relative_path.ends_with(".sav"))
+}
+
+fn sanitize_archive_path(raw: &str) -> Result<String> {
+    let normalized = raw.replace('\\', "/");
+    let mut cleaned = PathBuf::new();
+
+    for component in Path::new(&normalized).components() {
+        match component {
+            Component::Normal(value) => cleaned.push(value),
+            Component::CurDir => {}
+            Component::Prefix(_) | Component::RootDir | Component::ParentDir => {
+                bail!("archive entry path is unsafe: {}", raw)
+            }
+        }
+    }
+
+    if cleaned.as_os_str().is_empty() {
+        bail!("archive entry path is empty: {}", raw);
+    }
+
+    Ok(cleaned.to_string_lossy().replace('\\', "/"))
+}
+
+fn prefixed(root: &str, suffix: &str) -> String {
+    if root.is_empty() {
+        suffix.to_string()
+    } else {
+        format!("{}/{}", root, suffix)
+    }
+}
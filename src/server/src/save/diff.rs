@@ -0,0 +1,190 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Player fields compared between two normalized projections.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PlayerSnapshot {
+    pub player_uid: String,
+    pub player_name: Option<String>,
+    pub guild_id: Option<String>,
+    pub level: Option<i32>,
+}
+
+/// Pal fields compared between two normalized projections. `base_ids` holds
+/// every base the pal is assigned to, sorted.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PalSnapshot {
+    pub pal_instance_id: String,
+    pub owner_player_uid: Option<String>,
+    pub species_id: Option<String>,
+    pub nickname: Option<String>,
+    pub level: Option<i32>,
+    pub exp: Option<i64>,
+    pub passive_skill_ids: Vec<String>,
+    pub base_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FieldChange<T> {
+    pub from: T,
+    pub to: T,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ListChange {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GuildMembershipChange {
+    pub player_uid: String,
+    pub player_name: Option<String>,
+    pub guild_id: FieldChange<Option<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PalChange {
+    pub pal_instance_id: String,
+    pub species_id: Option<String>,
+    pub nickname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<FieldChange<Option<i32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<FieldChange<Option<i64>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passive_skill_ids: Option<ListChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_player_uid: Option<FieldChange<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_ids: Option<ListChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct PlayerDiff {
+    pub added: Vec<PlayerSnapshot>,
+    pub removed: Vec<PlayerSnapshot>,
+    pub guild_changes: Vec<GuildMembershipChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct PalDiff {
+    pub added: Vec<PalSnapshot>,
+    pub removed: Vec<PalSnapshot>,
+    pub changed: Vec<PalChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct NormalizedDiff {
+    pub players: PlayerDiff,
+    pub pals: PalDiff,
+}
+
+/// Compares two normalized projections keyed by `player_uid` and
+/// `pal_instance_id`. Output lists are ordered by key so repeated diffs of the
+/// same pair are byte-identical.
+pub fn diff_normalized(
+    from_players: Vec<PlayerSnapshot>,
+    to_players: Vec<PlayerSnapshot>,
+    from_pals: Vec<PalSnapshot>,
+    to_pals: Vec<PalSnapshot>,
+) -> NormalizedDiff {
+    NormalizedDiff {
+        players: diff_players(from_players, to_players),
+        pals: diff_pals(from_pals, to_pals),
+    }
+}
+
+fn diff_players(from: Vec<PlayerSnapshot>, to: Vec<PlayerSnapshot>) -> PlayerDiff {
+    let mut from_by_uid: BTreeMap<String, PlayerSnapshot> = from
+        .into_iter()
+        .map(|player| (player.player_uid.clone(), player))
+        .collect();
+    let mut diff = PlayerDiff::default();
+
+    let to_by_uid: BTreeMap<String, PlayerSnapshot> = to
+        .into_iter()
+        .map(|player| (player.player_uid.clone(), player))
+        .collect();
+    for (player_uid, after) in to_by_uid {
+        let Some(before) = from_by_uid.remove(&player_uid) else {
+            diff.added.push(after);
+            continue;
+        };
+        if before.guild_id != after.guild_id {
+            diff.guild_changes.push(GuildMembershipChange {
+                player_uid,
+                player_name: after.player_name.or(before.player_name),
+                guild_id: FieldChange {
+                    from: before.guild_id,
+                    to: after.guild_id,
+                },
+            });
+        }
+    }
+    diff.removed = from_by_uid.into_values().collect();
+
+    diff
+}
+
+fn diff_pals(from: Vec<PalSnapshot>, to: Vec<PalSnapshot>) -> PalDiff {
+    let mut from_by_id: BTreeMap<String, PalSnapshot> = from
+        .into_iter()
+        .map(|pal| (pal.pal_instance_id.clone(), pal))
+        .collect();
+    let mut diff = PalDiff::default();
+
+    let to_by_id: BTreeMap<String, PalSnapshot> = to
+        .into_iter()
+        .map(|pal| (pal.pal_instance_id.clone(), pal))
+        .collect();
+    for (pal_instance_id, after) in to_by_id {
+        let Some(before) = from_by_id.remove(&pal_instance_id) else {
+            diff.added.push(after);
+            continue;
+        };
+
+        let change = PalChange {
+            pal_instance_id,
+            level: field_change(&before.level, &after.level),
+            exp: field_change(&before.exp, &after.exp),
+            passive_skill_ids: list_change(&before.passive_skill_ids, &after.passive_skill_ids),
+            owner_player_uid: field_change(&before.owner_player_uid, &after.owner_player_uid),
+            base_ids: list_change(&before.base_ids, &after.base_ids),
+            species_id: after.species_id,
+            nickname: after.nickname,
+        };
+        let has_change = change.level.is_some()
+            || change.exp.is_some()
+            || change.passive_skill_ids.is_some()
+            || change.owner_player_uid.is_some()
+            || change.base_ids.is_some();
+        if has_change {
+            diff.changed.push(change);
+        }
+    }
+    diff.removed = from_by_id.into_values().collect();
+
+    diff
+}
+
+fn field_change<T: Clone + PartialEq>(from: &T, to: &T) -> Option<FieldChange<T>> {
+    (from != to).then(|| FieldChange {
+        from: from.clone(),
+        to: to.clone(),
+    })
+}
+
+/// Passive and base lists are compared as sets; reordering alone is not a
+/// change.
+fn list_change(from: &[String], to: &[String]) -> Option<ListChange> {
+    let from: BTreeSet<&String> = from.iter().collect();
+    let to: BTreeSet<&String> = to.iter().collect();
+    let added: Vec<String> = to.difference(&from).map(|value| (*value).clone()).collect();
+    let removed: Vec<String> = from.difference(&to).map(|value| (*value).clone()).collect();
+    if added.is_empty() && removed.is_empty() {
+        None
+    } else {
+        Some(ListChange { added, removed })
+    }
+}
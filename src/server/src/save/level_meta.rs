@@ -0,0 +1,52 @@
+use crate::save::detect::detect_save_variant;
+use crate::save::normalize::{
+    as_custom_struct, get_first_prop, get_i32, get_string, parse_gvas_with_hints,
+};
+use crate::save::parse::decode_to_gvas;
+use gvas::GvasHeader;
+use gvas::properties::Property;
+use serde::Serialize;
+
+/// World summary fields from `LevelMeta.sav`, the small file the game reads
+/// for its world selection list.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LevelMeta {
+    pub world_name: Option<String>,
+    pub host_player_name: Option<String>,
+    pub host_player_level: Option<i32>,
+    pub in_game_day: Option<i32>,
+    pub save_game_version: Option<i32>,
+    pub engine_version: String,
+}
+
+pub fn decode_level_meta(bytes: &[u8]) -> Result<LevelMeta, String> {
+    let variant = detect_save_variant(bytes);
+    let gvas_bytes =
+        decode_to_gvas(bytes, &variant).map_err(|error| format!("decode failed: {}", error))?;
+    let gvas = parse_gvas_with_hints(&gvas_bytes)?;
+
+    let engine_version = match &gvas.header {
+        GvasHeader::Version2 { engine_version, .. }
+        | GvasHeader::Version3 { engine_version, .. } => engine_version.to_string(),
+    };
+
+    let save_game_version = match gvas.properties.get("Version") {
+        Some(Property::IntProperty(value)) => Some(value.value),
+        _ => None,
+    };
+
+    let save_data = gvas
+        .properties
+        .get("SaveData")
+        .and_then(as_custom_struct)
+        .ok_or_else(|| "missing SaveData CustomStruct".to_string())?;
+
+    Ok(LevelMeta {
+        world_name: get_string(get_first_prop(save_data, "WorldName")),
+        host_player_name: get_string(get_first_prop(save_data, "HostPlayerName")),
+        host_player_level: get_i32(get_first_prop(save_data, "HostPlayerLevel")),
+        in_game_day: get_i32(get_first_prop(save_data, "InGameDay")),
+        save_game_version,
+        engine_version,
+    })
+}
@@ -1,11 +1,16 @@
+pub mod archive;
 pub mod custom_registry;
 pub mod detect;
+pub mod diff;
 pub mod export;
 pub mod hint_registry;
+pub mod level_meta;
 pub mod normalize;
 pub mod paltypes;
 pub mod parse;
 pub mod patch;
+pub mod player_save;
 pub mod rawdata;
 pub mod roundtrip;
-pub mod zip;
+pub mod rules;
+pub mod world_option;
@@ -3,6 +3,8 @@ use crate::save::detect::detect_save_variant;
 use crate::save::hint_registry::{cache_discovered_hint, merged_hints_with_cache};
 use crate::save::paltypes::DISABLED_PROPERTIES;
 use crate::save::parse::decode_to_gvas;
+use crate::save::player_save::{PlayerContainers, container_id};
+use crate::save::rawdata::group::decode_group;
 use gvas::cursor_ext::ReadExt;
 use gvas::error::{DeserializeError, Error as GvasError};
 use gvas::game_version::GameVersion;
@@ -12,10 +14,11 @@ use gvas::properties::array_property::ArrayProperty;
 use gvas::properties::int_property::BytePropertyValue;
 use gvas::properties::map_property::MapProperty;
 use gvas::properties::struct_property::StructPropertyValue;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::{Cursor, Read};
+use std::ops::ControlFlow;
 use std::time::Instant;
 use uuid::Uuid;
 
@@ -26,6 +29,52 @@ pub struct NormalizedPlannerSummary {
     pub base_assignment_count: usize,
 }
 
+const NORMALIZATION_CANCELLED: &str = "normalization cancelled";
+const ZERO_GUID: &str = "00000000000000000000000000000000";
+
+/// Which `CharacterSaveParameterMap` entries become normalized rows. Player
+/// characters are always kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CharacterSelectionPolicy {
+    /// Pals assigned to a base worker container.
+    #[default]
+    Planner,
+    /// Pals with an owner, a previous owner, or a known container.
+    Owned,
+    /// Every character, including wild and NPC entries.
+    All,
+}
+
+impl CharacterSelectionPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Planner => "planner",
+            Self::Owned => "owned",
+            Self::All => "all",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "planner" => Some(Self::Planner),
+            "owned" => Some(Self::Owned),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+/// Selection reason for players seeded from `Players/<uid>.sav` file names.
+pub const SELECTION_PLAYER_SAVE_FILE: &str = "player_save_file";
+const SELECTION_PLAYER: &str = "player";
+const SELECTION_BASE_ASSIGNMENT: &str = "base_assignment";
+const SELECTION_PLAYER_KEY: &str = "player_key";
+const SELECTION_OWNED: &str = "owned";
+const SELECTION_PREVIOUSLY_OWNED: &str = "previously_owned";
+const SELECTION_CONTAINER: &str = "container";
+const SELECTION_UNOWNED: &str = "unowned";
+
 pub fn empty_summary() -> NormalizedPlannerSummary {
     NormalizedPlannerSummary {
         player_count: 0,
@@ -41,6 +90,7 @@ pub struct ExtractedPlayer {
     pub player_name: Option<String>,
     pub guild_id: Option<String>,
     pub level: Option<i32>,
+    pub selection_reason: String,
     pub raw_file_ref: Uuid,
     pub raw_entity_path: String,
 }
@@ -54,9 +104,28 @@ pub struct ExtractedPal {
     pub gender: Option<String>,
     pub level: Option<i32>,
     pub exp: Option<i64>,
+    pub rank: Option<i32>,
+    /// Statue of Power upgrades.
+    pub rank_hp: Option<i32>,
+    pub rank_attack: Option<i32>,
+    pub rank_defense: Option<i32>,
+    pub rank_craftspeed: Option<i32>,
+    /// Individual values, 0-100.
+    pub talent_hp: Option<i32>,
+    pub talent_melee: Option<i32>,
+    pub talent_shot: Option<i32>,
+    pub talent_defense: Option<i32>,
+    pub is_lucky: bool,
+    /// Levels added by work suitability manuals (`GotWorkSuitabilityAddRankList`),
+    /// keyed by `EPalWorkSuitability` name without its prefix.
+    pub work_suitability_add_ranks: BTreeMap<String, i64>,
     pub passive_skill_ids: Vec<String>,
     pub mastered_waza_ids: Vec<String>,
     pub equip_waza_ids: Vec<String>,
+    pub location_kind: String,
+    pub container_id: Option<String>,
+    pub slot_index: Option<i32>,
+    pub selection_reason: String,
     pub raw_file_ref: Uuid,
     pub raw_entity_path: String,
 }
@@ -72,11 +141,54 @@ pub struct ExtractedAssignment {
     pub raw_entity_path: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct ExtractedBase {
+    pub base_id: String,
+    pub base_name: Option<String>,
+    pub state: Option<i32>,
+    pub area_range: Option<f64>,
+    pub group_id: Option<String>,
+    pub worker_container_id: Option<String>,
+    pub worker_order_type: Option<i32>,
+    pub location_x: Option<f64>,
+    pub location_y: Option<f64>,
+    pub location_z: Option<f64>,
+    pub transform: Option<Value>,
+    pub worker_count: i32,
+    pub raw_file_ref: Uuid,
+    pub raw_entity_path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExtractedGuildMember {
+    pub player_uid: String,
+    pub player_name: Option<String>,
+    pub last_online_ticks: Option<i64>,
+    pub offline_seconds: Option<i64>,
+    pub is_admin: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExtractedGuild {
+    pub group_id: String,
+    pub group_type: String,
+    pub guild_name: Option<String>,
+    pub admin_player_uid: Option<String>,
+    pub base_camp_level: Option<i32>,
+    pub base_ids: Vec<String>,
+    pub members: Vec<ExtractedGuildMember>,
+    pub raw_file_ref: Uuid,
+    pub raw_entity_path: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct ExtractedPlannerData {
     pub players: Vec<ExtractedPlayer>,
     pub pals: Vec<ExtractedPal>,
     pub assignments: Vec<ExtractedAssignment>,
+    pub bases: Vec<ExtractedBase>,
+    pub guilds: Vec<ExtractedGuild>,
+    pub in_game_day: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Default)]
@@ -91,6 +203,7 @@ pub struct ParseMetrics {
     pub character_map_decoded: usize,
     pub basecamp_count: usize,
     pub container_count: usize,
+    pub guild_count: usize,
     pub disabled_property_skips: usize,
 }
 
@@ -114,42 +227,56 @@ pub struct NormalizationProgress {
 pub fn extract_from_level_sav(
     level_sav_bytes: &[u8],
     raw_file_ref: Uuid,
+    player_containers: &[PlayerContainers],
+    policy: CharacterSelectionPolicy,
 ) -> Result<ExtractedPlannerData, String> {
-    extract_from_level_sav_with_progress(level_sav_bytes, raw_file_ref, |_| {})
-        .map(|result| result.data)
+    extract_from_level_sav_with_progress(
+        level_sav_bytes,
+        raw_file_ref,
+        player_containers,
+        policy,
+        |_| ControlFlow::Continue(()),
+    )
+    .map(|result| result.data)
 }
 
+/// Returning `ControlFlow::Break` from `on_progress` stops extraction at the
+/// next progress point with a cancellation error.
 pub fn extract_from_level_sav_with_progress<F>(
     level_sav_bytes: &[u8],
     raw_file_ref: Uuid,
+    player_containers: &[PlayerContainers],
+    policy: CharacterSelectionPolicy,
     mut on_progress: F,
 ) -> Result<NormalizationResult, String>
 where
-    F: FnMut(NormalizationProgress),
+    F: FnMut(NormalizationProgress) -> ControlFlow<()>,
 {
     let mut metrics = ParseMetrics {
         disabled_property_skips: DISABLED_PROPERTIES.len(),
         ..ParseMetrics::default()
     };
 
-    on_progress(make_stage_progress(
-        76,
-        "Decoding Level.sav wrapper payload",
-    ));
+    report_progress(
+        &mut on_progress,
+        make_stage_progress(76, "Decoding Level.sav wrapper payload"),
+    )?;
     let decode_start = Instant::now();
     let variant = detect_save_variant(level_sav_bytes);
     let level_gvas = decode_to_gvas(level_sav_bytes, &variant)
         .map_err(|error| format!("decode failed: {}", error))?;
     metrics.decode_wrapper_ms = decode_start.elapsed().as_millis() as u64;
 
-    on_progress(make_stage_progress(77, "Parsing Level.sav GVAS root"));
-    on_progress(make_stage_progress(
-        78,
-        "Applying mirrored PALWORLD_TYPE_HINTS",
-    ));
+    report_progress(
+        &mut on_progress,
+        make_stage_progress(77, "Parsing Level.sav GVAS root"),
+    )?;
+    report_progress(
+        &mut on_progress,
+        make_stage_progress(78, "Applying mirrored PALWORLD_TYPE_HINTS"),
+    )?;
     let parse_start = Instant::now();
-    let hint_outcome = parse_with_auto_hints(&level_gvas, &mut on_progress)
-        .map_err(|error| format!("gvas parse failed: {}", error))?;
+    let hint_outcome = parse_with_auto_hints(&level_gvas, &mut on_progress)?;
     metrics.parse_gvas_ms = parse_start.elapsed().as_millis() as u64;
     metrics.hint_pass_count = hint_outcome.hint_pass_count;
     metrics.hint_count_start = hint_outcome.hint_count_start;
@@ -158,17 +285,31 @@ where
     let gvas = hint_outcome.gvas;
     let expanded_hints = hint_outcome.hints;
 
-    on_progress(make_stage_progress(89, "Decoding planner raw domains"));
+    report_progress(
+        &mut on_progress,
+        make_stage_progress(89, "Decoding planner raw domains"),
+    )?;
     let world_props = get_world_save_data_props(&gvas.properties)
         .ok_or_else(|| "missing worldSaveData CustomStruct".to_string())?;
 
-    let (assignments, assignment_stats) = parse_base_assignments(world_props, raw_file_ref)?;
+    let BaseCampExtract {
+        bases,
+        assignments,
+        container_slots,
+        stats: assignment_stats,
+    } = parse_bases_and_assignments(world_props, raw_file_ref)?;
     metrics.basecamp_count = assignment_stats.basecamp_count;
     metrics.container_count = assignment_stats.container_count;
-    let required_assignment_instance_ids: HashSet<String> = assignments
-        .iter()
-        .map(|assignment| assignment.pal_instance_id.clone())
-        .collect();
+    let selector = CharacterSelector::new(
+        policy,
+        &container_slots,
+        &bases,
+        &assignments,
+        player_containers,
+        guild_storage_container_ids(world_props),
+    );
+    let guilds = parse_guilds(world_props, raw_file_ref);
+    metrics.guild_count = guilds.len();
 
     let mut players = Vec::<ExtractedPlayer>::new();
     let mut pals = Vec::<ExtractedPal>::new();
@@ -176,7 +317,7 @@ where
         world_props,
         &expanded_hints,
         gvas.header.get_custom_versions(),
-        &required_assignment_instance_ids,
+        &selector,
         raw_file_ref,
         &mut players,
         &mut pals,
@@ -192,11 +333,21 @@ where
             players,
             pals,
             assignments,
+            bases,
+            guilds,
+            in_game_day: extract_in_game_day(world_props),
         },
         metrics,
     })
 }
 
+/// Parses a decoded GVAS payload with the mirrored hints, resolving missing
+/// hints the same way Level.sav normalization does.
+pub(crate) fn parse_gvas_with_hints(gvas_bytes: &[u8]) -> Result<gvas::GvasFile, String> {
+    parse_with_auto_hints(gvas_bytes, &mut |_| ControlFlow::Continue(()))
+        .map(|outcome| outcome.gvas)
+}
+
 #[derive(Debug, Clone)]
 struct HintParseOutcome {
     gvas: gvas::GvasFile,
@@ -208,8 +359,8 @@ struct HintParseOutcome {
 
 fn parse_with_auto_hints(
     gvas_bytes: &[u8],
-    on_progress: &mut impl FnMut(NormalizationProgress),
-) -> Result<HintParseOutcome, GvasError> {
+    on_progress: &mut impl FnMut(NormalizationProgress) -> ControlFlow<()>,
+) -> Result<HintParseOutcome, String> {
     const MAX_FALLBACK_PASSES: u32 = 64;
     let mirrored_hints = merged_hints_with_cache();
     let mut hints = mirrored_hints.clone();
@@ -231,17 +382,21 @@ fn parse_with_auto_hints(
             }
             Err(GvasError::Deserialize(DeserializeError::MissingHint(kind, path, _))) => {
                 if hint_pass_count >= MAX_FALLBACK_PASSES {
-                    return Err(GvasError::Deserialize(DeserializeError::InvalidProperty(
-                        format!("exceeded fallback hint resolution limit ({MAX_FALLBACK_PASSES})")
+                    return Err(format_gvas_parse_error(GvasError::Deserialize(
+                        DeserializeError::InvalidProperty(
+                            format!(
+                                "exceeded fallback hint resolution limit ({MAX_FALLBACK_PASSES})"
+                            )
                             .into(),
-                        0,
+                            0,
+                        ),
                     )));
                 }
 
                 let path_string = path.to_string();
                 if hints.contains_key(&path_string) {
-                    return Err(GvasError::Deserialize(DeserializeError::MissingHint(
-                        kind, path, 0,
+                    return Err(format_gvas_parse_error(GvasError::Deserialize(
+                        DeserializeError::MissingHint(kind, path, 0),
                     )));
                 }
 
@@ -255,23 +410,30 @@ fn parse_with_auto_hints(
 
                 hint_pass_count += 1;
                 let pct = 78 + ((hint_pass_count as i32 * 11) / MAX_FALLBACK_PASSES as i32);
-                on_progress(make_stage_progress(
-                    pct.clamp(78, 89),
-                    format!(
-                        "Fallback hint resolution pass {}/{}, hints {}, missing {} (raw: {})",
-                        hint_pass_count,
-                        MAX_FALLBACK_PASSES,
-                        hints.len(),
-                        simplified,
-                        path
+                report_progress(
+                    on_progress,
+                    make_stage_progress(
+                        pct.clamp(78, 89),
+                        format!(
+                            "Fallback hint resolution pass {}/{}, hints {}, missing {} (raw: {})",
+                            hint_pass_count,
+                            MAX_FALLBACK_PASSES,
+                            hints.len(),
+                            simplified,
+                            path
+                        ),
                     ),
-                ));
+                )?;
             }
-            Err(error) => return Err(error),
+            Err(error) => return Err(format_gvas_parse_error(error)),
         }
     }
 }
 
+fn format_gvas_parse_error(error: GvasError) -> String {
+    format!("gvas parse failed: {}", error)
+}
+
 fn simplify_hint_path(path: &str) -> String {
     path.split('.')
         .filter(|segment| {
@@ -291,6 +453,131 @@ fn get_world_save_data_props<'a>(
     as_custom_struct(world_prop)
 }
 
+/// Whole in-game days elapsed, from `GameTimeSaveData.GameDateTimeTicks`
+/// (`FDateTime` ticks, 100ns each).
+fn extract_in_game_day(
+    world_props: &gvas::types::map::HashableIndexMap<String, Vec<Property>>,
+) -> Option<i32> {
+    const TICKS_PER_DAY: i64 = 864_000_000_000;
+    let game_time = as_custom_struct(get_first_prop(world_props, "GameTimeSaveData")?)?;
+    let ticks = get_i64(get_first_prop(game_time, "GameDateTimeTicks"))?;
+    i32::try_from(ticks / TICKS_PER_DAY).ok()
+}
+
+/// Guild records from `GroupSaveDataMap`. NPC groups (`Neutral`,
+/// `Organization`) are skipped. `IndependentGuild` is a solo player's guild
+/// and yields that player as its only member and admin.
+fn parse_guilds(
+    world_props: &gvas::types::map::HashableIndexMap<String, Vec<Property>>,
+    raw_file_ref: Uuid,
+) -> Vec<ExtractedGuild> {
+    const TICKS_PER_SECOND: i64 = 10_000_000;
+    let real_time_ticks = get_first_prop(world_props, "GameTimeSaveData")
+        .and_then(as_custom_struct)
+        .and_then(|game_time| get_i64(get_first_prop(game_time, "RealDateTimeTicks")));
+
+    let Some(Property::MapProperty(MapProperty::Properties { value, .. })) =
+        get_first_prop(world_props, "GroupSaveDataMap")
+    else {
+        return Vec::new();
+    };
+
+    let mut guilds = Vec::<ExtractedGuild>::new();
+    for (_group_key, group_value) in value {
+        let Some(group_struct) = as_custom_struct(group_value) else {
+            continue;
+        };
+        let Some(group_type) = get_string(get_first_prop(group_struct, "GroupType")) else {
+            continue;
+        };
+        if !matches!(
+            group_type.as_str(),
+            "EPalGroupType::Guild" | "EPalGroupType::IndependentGuild"
+        ) {
+            continue;
+        }
+        let Some(group_raw) = get_array_bytes(get_first_prop(group_struct, "RawData")) else {
+            continue;
+        };
+        let Ok(group_data) = decode_group(&group_raw, &group_type) else {
+            continue;
+        };
+        let Some(group_id) = group_data
+            .get("group_id")
+            .and_then(Value::as_str)
+            .map(ToString::to_string)
+        else {
+            continue;
+        };
+
+        let admin_player_uid = group_data
+            .get("admin_player_uid")
+            .or_else(|| group_data.get("player_uid"))
+            .and_then(Value::as_str)
+            .map(ToString::to_string);
+        let member_values: Vec<&Value> = match group_data.get("players") {
+            Some(Value::Array(players)) => players.iter().collect(),
+            _ => std::iter::once(&group_data)
+                .filter(|group| group.get("player_uid").is_some())
+                .collect(),
+        };
+        let members = member_values
+            .into_iter()
+            .filter_map(|member| {
+                let player_uid = member.get("player_uid")?.as_str()?.to_string();
+                let player_info = member.get("player_info");
+                let last_online_ticks = player_info
+                    .and_then(|info| info.get("last_online_real_time"))
+                    .and_then(Value::as_i64);
+                Some(ExtractedGuildMember {
+                    is_admin: admin_player_uid.as_deref() == Some(player_uid.as_str()),
+                    player_uid,
+                    player_name: player_info
+                        .and_then(|info| info.get("player_name"))
+                        .and_then(Value::as_str)
+                        .map(ToString::to_string),
+                    last_online_ticks,
+                    offline_seconds: real_time_ticks
+                        .zip(last_online_ticks)
+                        .map(|(now, last)| (now - last).max(0) / TICKS_PER_SECOND),
+                })
+            })
+            .collect();
+
+        guilds.push(ExtractedGuild {
+            raw_entity_path: format!("worldSaveData.GroupSaveDataMap[{}]", group_id),
+            group_id,
+            group_type: group_type
+                .strip_prefix("EPalGroupType::")
+                .unwrap_or(&group_type)
+                .to_string(),
+            guild_name: group_data
+                .get("guild_name")
+                .and_then(Value::as_str)
+                .map(ToString::to_string),
+            admin_player_uid,
+            base_camp_level: group_data
+                .get("base_camp_level")
+                .and_then(Value::as_i64)
+                .and_then(|level| i32::try_from(level).ok()),
+            base_ids: group_data
+                .get("base_ids")
+                .and_then(Value::as_array)
+                .map(|ids| {
+                    ids.iter()
+                        .filter_map(Value::as_str)
+                        .map(ToString::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            members,
+            raw_file_ref,
+        });
+    }
+
+    guilds
+}
+
 #[derive(Debug, Clone, Default)]
 struct CharacterParseStats {
     total_entries: usize,
@@ -302,14 +589,14 @@ fn parse_character_map<F>(
     world_props: &gvas::types::map::HashableIndexMap<String, Vec<Property>>,
     hints: &HashMap<String, String>,
     custom_versions: &gvas::types::map::HashableIndexMap<gvas::types::Guid, u32>,
-    required_assignment_instance_ids: &HashSet<String>,
+    selector: &CharacterSelector,
     raw_file_ref: Uuid,
     players: &mut Vec<ExtractedPlayer>,
     pals: &mut Vec<ExtractedPal>,
     on_progress: &mut F,
 ) -> Result<CharacterParseStats, String>
 where
-    F: FnMut(NormalizationProgress),
+    F: FnMut(NormalizationProgress) -> ControlFlow<()>,
 {
     let character_map_props = world_props
         .get("CharacterSaveParameterMap")
@@ -334,25 +621,28 @@ where
             90 + ((processed as i32 * 8) / total_entries as i32)
         }
         .clamp(90, 98);
-        on_progress(NormalizationProgress {
-            progress_pct_hint: Some(pct),
-            message: format!(
-                "Extracting planner entities from Level.sav ({}/{}, selected {}, players {}, pals {})",
-                processed, total_entries, selected, player_count, pal_count
-            ),
-            total_character_entries: total_entries,
-            processed_character_entries: processed,
-            selected_character_entries: selected,
-            player_count,
-            pal_count,
-        });
+        report_progress(
+            on_progress,
+            NormalizationProgress {
+                progress_pct_hint: Some(pct),
+                message: format!(
+                    "Extracting planner entities from Level.sav ({}/{}, selected {}, players {}, pals {})",
+                    processed, total_entries, selected, player_count, pal_count
+                ),
+                total_character_entries: total_entries,
+                processed_character_entries: processed,
+                selected_character_entries: selected,
+                player_count,
+                pal_count,
+            },
+        )
     };
     emit_progress(
         processed_entries,
         selected_entries,
         players.len(),
         pals.len(),
-    );
+    )?;
 
     for (entry_key, entry_value) in map_entries {
         processed_entries += 1;
@@ -365,7 +655,7 @@ where
                     selected_entries,
                     players.len(),
                     pals.len(),
-                );
+                )?;
             }
             continue;
         };
@@ -382,24 +672,22 @@ where
                     selected_entries,
                     players.len(),
                     pals.len(),
-                );
+                )?;
             }
             continue;
         };
-        let is_zero_player_uid = player_uid == "00000000000000000000000000000000";
-        if is_zero_player_uid && !required_assignment_instance_ids.contains(&instance_id) {
+        let is_zero_player_uid = player_uid == ZERO_GUID;
+        if is_zero_player_uid && !selector.should_decode(&instance_id) {
             if should_emit_character_progress(processed_entries, total_entries) {
                 emit_progress(
                     processed_entries,
                     selected_entries,
                     players.len(),
                     pals.len(),
-                );
+                )?;
             }
             continue;
         }
-        selected_entries += 1;
-
         let mut cursor = Cursor::new(raw_data.as_slice());
         let object_props = parse_property_stream(
             &mut cursor,
@@ -417,7 +705,7 @@ where
                     selected_entries,
                     players.len(),
                     pals.len(),
-                );
+                )?;
             }
             continue;
         }
@@ -434,7 +722,7 @@ where
                     selected_entries,
                     players.len(),
                     pals.len(),
-                );
+                )?;
             }
             continue;
         };
@@ -445,12 +733,14 @@ where
         let raw_entity_path = format!("worldSaveData.CharacterSaveParameterMap[{}]", instance_id);
 
         if is_player {
+            selected_entries += 1;
             players.push(ExtractedPlayer {
                 player_uid: player_uid.clone(),
                 player_instance_id: Some(instance_id.clone()),
                 player_name: nickname,
                 guild_id: group_id,
                 level,
+                selection_reason: SELECTION_PLAYER.to_string(),
                 raw_file_ref,
                 raw_entity_path,
             });
@@ -460,7 +750,7 @@ where
                     selected_entries,
                     players.len(),
                     pals.len(),
-                );
+                )?;
             }
             continue;
         }
@@ -469,12 +759,59 @@ where
         let species_id = get_string(get_first_prop(save_parameter_props, "CharacterID"));
         let gender = get_string(get_first_prop(save_parameter_props, "Gender"));
         let exp = get_i64(get_first_prop(save_parameter_props, "Exp"));
+        let rank = get_i32(get_first_prop(save_parameter_props, "Rank"));
+        let rank_hp = get_i32(get_first_prop(save_parameter_props, "Rank_HP"));
+        let rank_attack = get_i32(get_first_prop(save_parameter_props, "Rank_Attack"));
+        let rank_defense = get_i32(get_first_prop(save_parameter_props, "Rank_Defence"));
+        let rank_craftspeed = get_i32(get_first_prop(save_parameter_props, "Rank_CraftSpeed"));
+        let talent_hp = get_i32(get_first_prop(save_parameter_props, "Talent_HP"));
+        let talent_melee = get_i32(get_first_prop(save_parameter_props, "Talent_Melee"));
+        let talent_shot = get_i32(get_first_prop(save_parameter_props, "Talent_Shot"));
+        let talent_defense = get_i32(get_first_prop(save_parameter_props, "Talent_Defense"));
+        let is_lucky = get_bool(get_first_prop(save_parameter_props, "IsRarePal")).unwrap_or(false);
+        let work_suitability_add_ranks = get_work_suitability_ranks(get_first_prop(
+            save_parameter_props,
+            "GotWorkSuitabilityAddRankList",
+        ));
         let passive_skill_ids =
             get_string_array(get_first_prop(save_parameter_props, "PassiveSkillList"));
         let mastered_waza_ids =
             get_string_array(get_first_prop(save_parameter_props, "MasteredWaza"));
         let equip_waza_ids = get_string_array(get_first_prop(save_parameter_props, "EquipWaza"));
+        let slot_id = get_first_prop(save_parameter_props, "SlotID")
+            .and_then(as_custom_struct)
+            .and_then(|slot_props| {
+                Some((
+                    container_id(get_first_prop(slot_props, "ContainerId"))?,
+                    get_i32(get_first_prop(slot_props, "SlotIndex"))?,
+                ))
+            });
+        let has_old_owners = matches!(
+            get_first_prop(save_parameter_props, "OldOwnerPlayerUIds"),
+            Some(Property::ArrayProperty(ArrayProperty::Structs { structs, .. })) if !structs.is_empty()
+        );
+        let has_owner = owner_uid.as_deref().is_some_and(|uid| uid != ZERO_GUID);
+        let (location_kind, container_id, slot_index) =
+            selector.locate(&instance_id, slot_id, has_owner, has_old_owners);
+        let Some(selection_reason) = selector.pal_selection_reason(
+            &instance_id,
+            is_zero_player_uid,
+            has_owner,
+            has_old_owners,
+            location_kind,
+        ) else {
+            if should_emit_character_progress(processed_entries, total_entries) {
+                emit_progress(
+                    processed_entries,
+                    selected_entries,
+                    players.len(),
+                    pals.len(),
+                )?;
+            }
+            continue;
+        };
 
+        selected_entries += 1;
         pals.push(ExtractedPal {
             pal_instance_id: instance_id,
             owner_player_uid: owner_uid,
@@ -483,9 +820,24 @@ where
             gender,
             level,
             exp,
+            rank,
+            rank_hp,
+            rank_attack,
+            rank_defense,
+            rank_craftspeed,
+            talent_hp,
+            talent_melee,
+            talent_shot,
+            talent_defense,
+            is_lucky,
+            work_suitability_add_ranks,
             passive_skill_ids,
             mastered_waza_ids,
             equip_waza_ids,
+            location_kind: location_kind.to_string(),
+            container_id,
+            slot_index,
+            selection_reason: selection_reason.to_string(),
             raw_file_ref,
             raw_entity_path,
         });
@@ -496,7 +848,7 @@ where
                 selected_entries,
                 players.len(),
                 pals.len(),
-            );
+            )?;
         }
     }
 
@@ -506,7 +858,7 @@ where
             selected_entries,
             players.len(),
             pals.len(),
-        );
+        )?;
     }
 
     Ok(CharacterParseStats {
@@ -516,6 +868,16 @@ where
     })
 }
 
+fn report_progress(
+    on_progress: &mut impl FnMut(NormalizationProgress) -> ControlFlow<()>,
+    progress: NormalizationProgress,
+) -> Result<(), String> {
+    match on_progress(progress) {
+        ControlFlow::Continue(()) => Ok(()),
+        ControlFlow::Break(()) => Err(NORMALIZATION_CANCELLED.to_string()),
+    }
+}
+
 fn make_stage_progress(
     progress_pct_hint: i32,
     message: impl Into<String>,
@@ -541,11 +903,21 @@ struct AssignmentParseStats {
     container_count: usize,
 }
 
-fn parse_base_assignments(
+struct BaseCampExtract {
+    bases: Vec<ExtractedBase>,
+    assignments: Vec<ExtractedAssignment>,
+    /// Occupied `(slot_index, pal_instance_id)` pairs per character container.
+    container_slots: HashMap<String, Vec<(i32, String)>>,
+    stats: AssignmentParseStats,
+}
+
+/// Base camps from `BaseCampSaveData` and the pals in each base's worker
+/// container. `worker_count` counts the non-empty worker container slots.
+fn parse_bases_and_assignments(
     world_props: &gvas::types::map::HashableIndexMap<String, Vec<Property>>,
     raw_file_ref: Uuid,
-) -> Result<(Vec<ExtractedAssignment>, AssignmentParseStats), String> {
-    let mut base_to_container = HashMap::<String, String>::new();
+) -> Result<BaseCampExtract, String> {
+    let mut bases = Vec::<ExtractedBase>::new();
     let mut container_slots = HashMap::<String, Vec<(i32, String)>>::new();
     let mut stats = AssignmentParseStats::default();
 
@@ -575,7 +947,7 @@ fn parse_base_assignments(
                 continue;
             };
 
-            let container_id = get_first_prop(base_struct, "WorkerDirector")
+            let worker_data = get_first_prop(base_struct, "WorkerDirector")
                 .and_then(as_custom_struct)
                 .and_then(|worker| get_array_bytes(get_first_prop(worker, "RawData")))
                 .and_then(|worker_raw| {
@@ -584,17 +956,50 @@ fn parse_base_assignments(
                         &worker_raw,
                     )
                     .ok()
-                    .and_then(|(_, worker_data)| {
-                        worker_data
-                            .get("container_id")
-                            .and_then(Value::as_str)
-                            .map(ToString::to_string)
-                    })
+                    .map(|(_, worker_data)| worker_data)
                 });
+            let transform = base_data.get("transform").cloned();
+            let location = |axis: &str| {
+                transform
+                    .as_ref()
+                    .and_then(|transform| transform.get("translation"))
+                    .and_then(|translation| translation.get(axis))
+                    .and_then(Value::as_f64)
+            };
 
-            if let Some(container_id) = container_id {
-                base_to_container.insert(base_id, container_id);
-            }
+            bases.push(ExtractedBase {
+                raw_entity_path: format!("worldSaveData.BaseCampSaveData[{}]", base_id),
+                base_id,
+                base_name: base_data
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .map(ToString::to_string),
+                state: base_data
+                    .get("state")
+                    .and_then(Value::as_i64)
+                    .and_then(|state| i32::try_from(state).ok()),
+                area_range: base_data.get("area_range").and_then(Value::as_f64),
+                group_id: base_data
+                    .get("group_id_belong_to")
+                    .and_then(Value::as_str)
+                    .map(ToString::to_string),
+                worker_container_id: worker_data
+                    .as_ref()
+                    .and_then(|worker| worker.get("container_id"))
+                    .and_then(Value::as_str)
+                    .map(ToString::to_string),
+                worker_order_type: worker_data
+                    .as_ref()
+                    .and_then(|worker| worker.get("current_order_type"))
+                    .and_then(Value::as_i64)
+                    .and_then(|order| i32::try_from(order).ok()),
+                location_x: location("x"),
+                location_y: location("y"),
+                location_z: location("z"),
+                transform,
+                worker_count: 0,
+                raw_file_ref,
+            });
         }
     }
 
@@ -642,7 +1047,7 @@ fn parse_base_assignments(
                 else {
                     continue;
                 };
-                if instance_id == "00000000000000000000000000000000" {
+                if instance_id == ZERO_GUID {
                     continue;
                 }
                 container_slots
@@ -654,26 +1059,184 @@ fn parse_base_assignments(
     }
 
     let mut assignments = Vec::<ExtractedAssignment>::new();
-    for (base_id, container_id) in base_to_container {
-        if let Some(slots) = container_slots.get(&container_id) {
-            for (slot_index, pal_instance_id) in slots {
-                assignments.push(ExtractedAssignment {
-                    base_id: base_id.clone(),
-                    pal_instance_id: pal_instance_id.clone(),
-                    assignment_kind: Some("base_slot".to_string()),
-                    assignment_target: Some(slot_index.to_string()),
-                    priority: Some(*slot_index),
-                    raw_file_ref,
-                    raw_entity_path: format!(
-                        "worldSaveData.CharacterContainerSaveData[{}].Slots[{}]",
-                        container_id, slot_index
-                    ),
-                });
+    for base in &mut bases {
+        let Some(container_id) = &base.worker_container_id else {
+            continue;
+        };
+        let Some(slots) = container_slots.get(container_id) else {
+            continue;
+        };
+        base.worker_count = i32::try_from(slots.len()).unwrap_or(i32::MAX);
+        for (slot_index, pal_instance_id) in slots {
+            assignments.push(ExtractedAssignment {
+                base_id: base.base_id.clone(),
+                pal_instance_id: pal_instance_id.clone(),
+                assignment_kind: Some("base_slot".to_string()),
+                assignment_target: Some(slot_index.to_string()),
+                priority: Some(*slot_index),
+                raw_file_ref,
+                raw_entity_path: format!(
+                    "worldSaveData.CharacterContainerSaveData[{}].Slots[{}]",
+                    container_id, slot_index
+                ),
+            });
+        }
+    }
+
+    Ok(BaseCampExtract {
+        bases,
+        assignments,
+        container_slots,
+        stats,
+    })
+}
+
+/// Character containers referenced from `GuildExtraSaveDataMap`, i.e. the
+/// pal storage shared by a guild.
+fn guild_storage_container_ids(
+    world_props: &gvas::types::map::HashableIndexMap<String, Vec<Property>>,
+) -> HashSet<String> {
+    let Some(Property::MapProperty(MapProperty::Properties { value, .. })) =
+        get_first_prop(world_props, "GuildExtraSaveDataMap")
+    else {
+        return HashSet::new();
+    };
+
+    value
+        .values()
+        .filter_map(as_custom_struct)
+        .flat_map(|guild_props| guild_props.iter())
+        .filter(|(name, _)| name.ends_with("ContainerId"))
+        .filter_map(|(_, values)| container_id(values.first()))
+        .collect()
+}
+
+const LOCATION_PARTY: &str = "party";
+const LOCATION_PALBOX: &str = "palbox";
+const LOCATION_BASE_WORKER: &str = "base_worker";
+const LOCATION_GUILD_STORAGE: &str = "guild_storage";
+const LOCATION_UNKNOWN: &str = "unknown";
+
+/// Applies the selection policy to characters and resolves where a pal lives
+/// from the container holding it.
+struct CharacterSelector {
+    policy: CharacterSelectionPolicy,
+    assigned_instance_ids: HashSet<String>,
+    slot_by_instance: HashMap<String, (String, i32)>,
+    container_kinds: HashMap<String, &'static str>,
+}
+
+impl CharacterSelector {
+    fn new(
+        policy: CharacterSelectionPolicy,
+        container_slots: &HashMap<String, Vec<(i32, String)>>,
+        bases: &[ExtractedBase],
+        assignments: &[ExtractedAssignment],
+        player_containers: &[PlayerContainers],
+        guild_storage_ids: HashSet<String>,
+    ) -> Self {
+        let slot_by_instance = container_slots
+            .iter()
+            .flat_map(|(container_id, slots)| {
+                slots.iter().map(|(slot_index, instance_id)| {
+                    (instance_id.clone(), (container_id.clone(), *slot_index))
+                })
+            })
+            .collect();
+
+        let mut container_kinds = HashMap::<String, &'static str>::new();
+        for container_id in guild_storage_ids {
+            container_kinds.insert(container_id, LOCATION_GUILD_STORAGE);
+        }
+        for base in bases {
+            if let Some(container_id) = &base.worker_container_id {
+                container_kinds.insert(container_id.clone(), LOCATION_BASE_WORKER);
             }
         }
+        for player in player_containers {
+            if let Some(container_id) = &player.palbox_container_id {
+                container_kinds.insert(container_id.clone(), LOCATION_PALBOX);
+            }
+            if let Some(container_id) = &player.party_container_id {
+                container_kinds.insert(container_id.clone(), LOCATION_PARTY);
+            }
+        }
+
+        Self {
+            policy,
+            assigned_instance_ids: assignments
+                .iter()
+                .map(|assignment| assignment.pal_instance_id.clone())
+                .collect(),
+            slot_by_instance,
+            container_kinds,
+        }
+    }
+
+    /// Zero-`PlayerUId` entries are only worth decoding under the planner
+    /// policy when a base worker slot references them.
+    fn should_decode(&self, instance_id: &str) -> bool {
+        self.policy != CharacterSelectionPolicy::Planner
+            || self.assigned_instance_ids.contains(instance_id)
+    }
+
+    /// Why a decoded pal is kept, or `None` when the policy drops it.
+    fn pal_selection_reason(
+        &self,
+        instance_id: &str,
+        is_zero_player_uid: bool,
+        has_owner: bool,
+        has_old_owners: bool,
+        location_kind: &str,
+    ) -> Option<&'static str> {
+        let reason = if self.assigned_instance_ids.contains(instance_id) {
+            SELECTION_BASE_ASSIGNMENT
+        } else if !is_zero_player_uid {
+            SELECTION_PLAYER_KEY
+        } else if has_owner {
+            SELECTION_OWNED
+        } else if has_old_owners {
+            SELECTION_PREVIOUSLY_OWNED
+        } else if location_kind != LOCATION_UNKNOWN {
+            SELECTION_CONTAINER
+        } else {
+            SELECTION_UNOWNED
+        };
+
+        let selected = match self.policy {
+            CharacterSelectionPolicy::Planner => {
+                matches!(reason, SELECTION_BASE_ASSIGNMENT | SELECTION_PLAYER_KEY)
+            }
+            CharacterSelectionPolicy::Owned => reason != SELECTION_UNOWNED,
+            CharacterSelectionPolicy::All => true,
+        };
+        selected.then_some(reason)
     }
 
-    Ok((assignments, stats))
+    /// Prefers the container slot that lists the pal, then the pal's own
+    /// `SlotID`. A pal in no known container with no owner but a previous
+    /// owner is a base worker, the same rule the reference save editors use.
+    fn locate(
+        &self,
+        instance_id: &str,
+        slot_id: Option<(String, i32)>,
+        has_owner: bool,
+        has_old_owners: bool,
+    ) -> (&'static str, Option<String>, Option<i32>) {
+        let slot = self.slot_by_instance.get(instance_id).cloned().or(slot_id);
+        let kind = slot
+            .as_ref()
+            .and_then(|(container_id, _)| self.container_kinds.get(container_id).copied());
+        let kind = match kind {
+            Some(kind) => kind,
+            None if !has_owner && has_old_owners => LOCATION_BASE_WORKER,
+            None => LOCATION_UNKNOWN,
+        };
+        match slot {
+            Some((container_id, slot_index)) => (kind, Some(container_id), Some(slot_index)),
+            None => (kind, None, None),
+        }
+    }
 }
 
 fn parse_property_stream(
@@ -713,14 +1276,14 @@ fn parse_property_stream(
     Ok(properties)
 }
 
-fn get_first_prop<'a>(
+pub(crate) fn get_first_prop<'a>(
     properties: &'a gvas::types::map::HashableIndexMap<String, Vec<Property>>,
     key: &str,
 ) -> Option<&'a Property> {
     properties.get(key).and_then(|values| values.first())
 }
 
-fn as_custom_struct(
+pub(crate) fn as_custom_struct(
     property: &Property,
 ) -> Option<&gvas::types::map::HashableIndexMap<String, Vec<Property>>> {
     match property {
@@ -742,7 +1305,7 @@ fn get_array_bytes(property: Option<&Property>) -> Option<Vec<u8>> {
     }
 }
 
-fn get_guid_uid(property: Option<&Property>) -> Option<String> {
+pub(crate) fn get_guid_uid(property: Option<&Property>) -> Option<String> {
     let property = property?;
     match property {
         Property::StructProperty(value) => match &value.value {
@@ -763,7 +1326,7 @@ fn get_bool(property: Option<&Property>) -> Option<bool> {
     }
 }
 
-fn get_i32(property: Option<&Property>) -> Option<i32> {
+pub(crate) fn get_i32(property: Option<&Property>) -> Option<i32> {
     match property {
         Some(Property::IntProperty(value)) => Some(value.value),
         Some(Property::ByteProperty(value)) => match value.value {
@@ -781,7 +1344,7 @@ fn get_i64(property: Option<&Property>) -> Option<i64> {
     }
 }
 
-fn get_string(property: Option<&Property>) -> Option<String> {
+pub(crate) fn get_string(property: Option<&Property>) -> Option<String> {
     match property {
         Some(Property::StrProperty(value)) => value.value.clone(),
         Some(Property::NameProperty(value)) => value.value.clone(),
@@ -812,7 +1375,28 @@ fn get_string_array(property: Option<&Property>) -> Vec<String> {
     }
 }
 
-fn normalize_guid(value: &str) -> String {
+/// `PalWorkSuitabilityInfo` entries (`WorkSuitability`, `Rank`); repeated
+/// work types add up.
+fn get_work_suitability_ranks(property: Option<&Property>) -> BTreeMap<String, i64> {
+    let mut ranks = BTreeMap::new();
+    let Some(Property::ArrayProperty(ArrayProperty::Structs { structs, .. })) = property else {
+        return ranks;
+    };
+    for entry in structs {
+        let StructPropertyValue::CustomStruct(entry_props) = entry else {
+            continue;
+        };
+        let Some(work) = get_string(get_first_prop(entry_props, "WorkSuitability")) else {
+            continue;
+        };
+        let rank = get_i32(get_first_prop(entry_props, "Rank")).unwrap_or_default();
+        let work = work.rsplit("::").next().unwrap_or(&work).to_string();
+        *ranks.entry(work).or_default() += i64::from(rank);
+    }
+    ranks
+}
+
+pub fn normalize_guid(value: &str) -> String {
     if value == "0" {
         "00000000000000000000000000000000".to_string()
     } else {
@@ -820,6 +1404,12 @@ fn normalize_guid(value: &str) -> String {
     }
 }
 
+impl ExtractedGuild {
+    pub fn base_ids_json(&self) -> Value {
+        Value::Array(self.base_ids.iter().cloned().map(Value::String).collect())
+    }
+}
+
 impl ExtractedPal {
     pub fn passive_skill_ids_json(&self) -> Value {
         Value::Array(
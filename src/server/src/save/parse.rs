@@ -1,7 +1,9 @@
 use crate::save::detect::SaveVariantInfo;
+use flate2::Compression;
 use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
 use oozextract::Extractor;
-use std::io::Read;
+use std::io::{Read, Write};
 
 #[derive(Debug, Clone)]
 pub struct GvasInspectResult {
@@ -57,6 +59,27 @@ pub fn decode_to_gvas(bytes: &[u8], variant: &SaveVariantInfo) -> Result<Vec<u8>
     }
 }
 
+/// Wraps a GVAS payload as a `PlZ` (`0x32`, double zlib) save file, the
+/// recompression target for every file the exporter changes.
+pub fn encode_plz(gvas_bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let uncompressed_size = u32::try_from(gvas_bytes.len())
+        .map_err(|_| "GVAS payload exceeds PlZ size limit".to_string())?;
+    let first_pass =
+        zlib_compress(gvas_bytes).map_err(|error| format!("zlib encode failed: {}", error))?;
+    let payload = zlib_compress(&first_pass)
+        .map_err(|error| format!("zlib second-pass encode failed: {}", error))?;
+    let compressed_size = u32::try_from(payload.len())
+        .map_err(|_| "compressed payload exceeds PlZ size limit".to_string())?;
+
+    let mut output = Vec::with_capacity(payload.len() + 12);
+    output.extend_from_slice(&uncompressed_size.to_le_bytes());
+    output.extend_from_slice(&compressed_size.to_le_bytes());
+    output.extend_from_slice(b"PlZ");
+    output.push(0x32);
+    output.extend_from_slice(&payload);
+    Ok(output)
+}
+
 fn decode_plz(payload: &[u8], variant: &SaveVariantInfo) -> Result<Vec<u8>, String> {
     let first_pass =
         zlib_decompress(payload).map_err(|error| format!("zlib decode failed: {}", error))?;
@@ -119,6 +142,12 @@ fn zlib_decompress(payload: &[u8]) -> std::io::Result<Vec<u8>> {
     Ok(output)
 }
 
+fn zlib_compress(payload: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(payload)?;
+    encoder.finish()
+}
+
 fn hex_magic(bytes: &[u8]) -> String {
     bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
 }
@@ -1 +1,697 @@
-pub fn patch_pipeline_placeholder() {}
+use crate::catalog::GameCatalog;
+use crate::save::normalize::normalize_guid;
+use crate::save::rules::{
+    MAX_CONDENSER_RANK, MAX_PASSIVES, MAX_WORK_SUITABILITY_LEVEL, manual_work_levels,
+    species_work_levels,
+};
+use crate::save::world_option::{UpdateWorldOptionPayload, WorldSettings, validate_world_settings};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Operation types of the patch contract.
+pub const PATCH_OP_TYPES: [&str; 11] = [
+    "update_player_field",
+    "update_pal_field",
+    "replace_pal_passive_list",
+    "replace_pal_mastered_waza_list",
+    "replace_pal_equipped_waza_list",
+    "replace_pal_work_suitability_map",
+    "upsert_base_assignment",
+    "delete_base_assignment",
+    "create_base",
+    "delete_base",
+    "update_world_option",
+];
+/// Save `Rank` of a pal with no condenser stars.
+pub const MIN_CONDENSER_RANK: i64 = 1;
+pub const MAX_TALENT: i64 = 100;
+pub const MAX_EQUIPPED_WAZA: usize = 3;
+
+const PAL_INT_FIELDS: [&str; 11] = [
+    "level",
+    "exp",
+    "rank",
+    "rank_hp",
+    "rank_attack",
+    "rank_defense",
+    "rank_craftspeed",
+    "talent_hp",
+    "talent_melee",
+    "talent_shot",
+    "talent_defense",
+];
+const PAL_TEXT_FIELDS: [&str; 2] = ["gender", "nickname"];
+const PLAYER_INT_FIELDS: [&str; 1] = ["level"];
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PatchOperation {
+    pub sequence: i64,
+    pub op_type: String,
+    pub target_kind: String,
+    pub target_id: String,
+    #[serde(default)]
+    pub payload_json: Value,
+}
+
+/// One rule violation. `code` is stable and meant for programs; `message`
+/// is for people.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssue {
+    pub code: &'static str,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn new(code: &'static str, field: Option<&str>, message: String) -> Self {
+        Self {
+            code,
+            field: field.map(str::to_string),
+            message,
+        }
+    }
+}
+
+/// Editable pal fields as stored in `planner_pals`.
+#[derive(Debug, Clone, Default)]
+pub struct PalState {
+    pub species_id: Option<String>,
+    pub nickname: Option<String>,
+    pub gender: Option<String>,
+    pub level: Option<i64>,
+    pub exp: Option<i64>,
+    pub rank: Option<i64>,
+    pub rank_hp: Option<i64>,
+    pub rank_attack: Option<i64>,
+    pub rank_defense: Option<i64>,
+    pub rank_craftspeed: Option<i64>,
+    pub talent_hp: Option<i64>,
+    pub talent_melee: Option<i64>,
+    pub talent_shot: Option<i64>,
+    pub talent_defense: Option<i64>,
+    pub passive_skill_ids: Vec<String>,
+    pub mastered_waza_ids: Vec<String>,
+    pub equip_waza_ids: Vec<String>,
+    /// Effective levels, derived from the species levels,
+    /// `work_suitability_add_ranks`, and `rank`.
+    pub work_suitability_ranks: BTreeMap<String, i64>,
+    /// Levels added by work suitability manuals (`GotWorkSuitabilityAddRankList`).
+    pub work_suitability_add_ranks: BTreeMap<String, i64>,
+}
+
+impl PalState {
+    fn int_field_mut(&mut self, field: &str) -> Option<&mut Option<i64>> {
+        Some(match field {
+            "level" => &mut self.level,
+            "exp" => &mut self.exp,
+            "rank" => &mut self.rank,
+            "rank_hp" => &mut self.rank_hp,
+            "rank_attack" => &mut self.rank_attack,
+            "rank_defense" => &mut self.rank_defense,
+            "rank_craftspeed" => &mut self.rank_craftspeed,
+            "talent_hp" => &mut self.talent_hp,
+            "talent_melee" => &mut self.talent_melee,
+            "talent_shot" => &mut self.talent_shot,
+            "talent_defense" => &mut self.talent_defense,
+            _ => return None,
+        })
+    }
+
+    fn int_field(&self, field: &str) -> Option<i64> {
+        match field {
+            "level" => self.level,
+            "exp" => self.exp,
+            "rank" => self.rank,
+            "rank_hp" => self.rank_hp,
+            "rank_attack" => self.rank_attack,
+            "rank_defense" => self.rank_defense,
+            "rank_craftspeed" => self.rank_craftspeed,
+            "talent_hp" => self.talent_hp,
+            "talent_melee" => self.talent_melee,
+            "talent_shot" => self.talent_shot,
+            "talent_defense" => self.talent_defense,
+            _ => None,
+        }
+    }
+}
+
+/// Editable player fields as stored in `planner_players`.
+#[derive(Debug, Clone, Default)]
+pub struct PlayerState {
+    pub level: Option<i64>,
+}
+
+/// Current state of every entity the operations target, keyed by normalized
+/// GUID. Operations are applied to it in order, so later operations are
+/// checked against the result of earlier ones.
+#[derive(Default)]
+pub struct PatchTargets {
+    pub pals: HashMap<String, PalState>,
+    pub players: HashMap<String, PlayerState>,
+    pub bases: HashSet<String>,
+    pub world_settings: WorldSettings,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationValidation {
+    pub sequence: i64,
+    pub op_type: String,
+    pub target_kind: String,
+    pub target_id: String,
+    pub validated: bool,
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// Checks operations in sequence order against the catalog and `targets`.
+/// An operation with issues is not applied, so it does not affect the
+/// checks of later operations.
+pub fn validate_operations(
+    catalog: &GameCatalog,
+    operations: &[PatchOperation],
+    targets: &mut PatchTargets,
+) -> Vec<OperationValidation> {
+    let mut previous_sequence = 0;
+    operations
+        .iter()
+        .map(|operation| {
+            let mut issues = Vec::new();
+            if operation.sequence <= previous_sequence
+                || (previous_sequence == 0 && operation.sequence != 1)
+            {
+                issues.push(ValidationIssue::new(
+                    "sequence_not_increasing",
+                    None,
+                    format!(
+                        "sequence {} must be {} or more, starting at 1",
+                        operation.sequence,
+                        previous_sequence + 1
+                    ),
+                ));
+            }
+            previous_sequence = previous_sequence.max(operation.sequence);
+            issues.extend(validate_operation(catalog, operation, targets));
+            OperationValidation {
+                sequence: operation.sequence,
+                op_type: operation.op_type.clone(),
+                target_kind: operation.target_kind.clone(),
+                target_id: operation.target_id.clone(),
+                validated: issues.is_empty(),
+                issues,
+            }
+        })
+        .collect()
+}
+
+fn validate_operation(
+    catalog: &GameCatalog,
+    operation: &PatchOperation,
+    targets: &mut PatchTargets,
+) -> Vec<ValidationIssue> {
+    if !PATCH_OP_TYPES.contains(&operation.op_type.as_str()) {
+        return vec![ValidationIssue::new(
+            "unknown_op_type",
+            None,
+            format!("unknown operation type {}", operation.op_type),
+        )];
+    }
+    let expected_kind = match operation.op_type.as_str() {
+        "update_player_field" => "player",
+        "update_world_option" => "world_option",
+        "upsert_base_assignment" | "delete_base_assignment" | "create_base" | "delete_base" => {
+            "base"
+        }
+        _ => "pal",
+    };
+    if operation.target_kind != expected_kind {
+        return vec![ValidationIssue::new(
+            "invalid_target_kind",
+            None,
+            format!(
+                "{} targets {}, not {}",
+                operation.op_type, expected_kind, operation.target_kind
+            ),
+        )];
+    }
+
+    let target_id = normalize_guid(&operation.target_id);
+    match expected_kind {
+        "pal" => {
+            let Some(pal) = targets.pals.get(&target_id) else {
+                return vec![unknown_target("pal", &operation.target_id)];
+            };
+            let mut edited = pal.clone();
+            let fields = match apply_pal_operation(&mut edited, operation) {
+                Ok(fields) => fields,
+                Err(issue) => return vec![issue],
+            };
+            let issues = validate_pal_fields(catalog, pal, &edited, &fields);
+            if issues.is_empty() {
+                sync_work_suitability(catalog, &mut edited, &fields);
+                targets.pals.insert(target_id, edited);
+            }
+            issues
+        }
+        "player" => {
+            let Some(player) = targets.players.get_mut(&target_id) else {
+                return vec![unknown_target("player", &operation.target_id)];
+            };
+            let (field, value) = match field_update(operation, &PLAYER_INT_FIELDS) {
+                Ok(update) => update,
+                Err(issue) => return vec![issue],
+            };
+            let Some(value) = value.as_i64() else {
+                return vec![invalid_value(&field, "an integer")];
+            };
+            let issues: Vec<ValidationIssue> =
+                check_level(catalog, Some(value)).into_iter().collect();
+            if issues.is_empty() {
+                player.level = Some(value);
+            }
+            issues
+        }
+        "base" => validate_base_operation(operation, &target_id, targets),
+        _ => {
+            let Ok(payload) =
+                serde_json::from_value::<UpdateWorldOptionPayload>(operation.payload_json.clone())
+            else {
+                return vec![invalid_payload(
+                    "payload must have a settings object or option_settings_ini text",
+                )];
+            };
+            let settings = match payload.changes() {
+                Ok(settings) => settings,
+                Err(error) => return vec![invalid_payload(&error)],
+            };
+            match validate_world_settings(&settings, &targets.world_settings) {
+                Ok(validated) => {
+                    targets.world_settings.extend(validated);
+                    Vec::new()
+                }
+                Err(error) => vec![ValidationIssue::new(
+                    "invalid_world_setting",
+                    Some("settings"),
+                    error,
+                )],
+            }
+        }
+    }
+}
+
+fn validate_base_operation(
+    operation: &PatchOperation,
+    base_id: &str,
+    targets: &mut PatchTargets,
+) -> Vec<ValidationIssue> {
+    let base_exists = targets.bases.contains(base_id);
+    match operation.op_type.as_str() {
+        "create_base" if base_exists => vec![ValidationIssue::new(
+            "target_exists",
+            None,
+            format!("base {} already exists", base_id),
+        )],
+        "create_base" => {
+            targets.bases.insert(base_id.to_string());
+            Vec::new()
+        }
+        _ if !base_exists => vec![unknown_target("base", base_id)],
+        "delete_base" => {
+            targets.bases.remove(base_id);
+            Vec::new()
+        }
+        _ => match operation
+            .payload_json
+            .get("pal_instance_id")
+            .and_then(Value::as_str)
+        {
+            None => vec![invalid_payload(
+                "payload must have a pal_instance_id string",
+            )],
+            Some(pal_instance_id)
+                if !targets.pals.contains_key(&normalize_guid(pal_instance_id)) =>
+            {
+                vec![unknown_target("pal", pal_instance_id)]
+            }
+            Some(_) => Vec::new(),
+        },
+    }
+}
+
+/// Keeps effective and manual work suitability levels consistent after an
+/// edit: a condenser rank change recomputes the effective levels from the
+/// manual ones, and a replaced level map is carried back into manual levels so
+/// a later rank change keeps it. Pals of unknown species keep their stored maps.
+fn sync_work_suitability(catalog: &GameCatalog, pal: &mut PalState, fields: &[&str]) {
+    let Some(species) = pal
+        .species_id
+        .as_deref()
+        .and_then(|species_id| catalog.species(species_id))
+    else {
+        return;
+    };
+    let rank = pal.rank.and_then(|rank| i32::try_from(rank).ok());
+    if fields.contains(&"work_suitability_ranks") {
+        pal.work_suitability_add_ranks = manual_work_levels(
+            &species.pal.work_suitability,
+            &pal.work_suitability_ranks,
+            rank,
+        );
+    }
+    if fields.contains(&"rank") {
+        pal.work_suitability_ranks = species_work_levels(
+            &species.pal.work_suitability,
+            &pal.work_suitability_add_ranks,
+            rank,
+        );
+    }
+}
+
+/// Applies a pal operation's payload and returns the fields it set.
+fn apply_pal_operation(
+    pal: &mut PalState,
+    operation: &PatchOperation,
+) -> Result<Vec<&'static str>, ValidationIssue> {
+    let payload = &operation.payload_json;
+    match operation.op_type.as_str() {
+        "update_pal_field" => {
+            let fields: Vec<&str> = PAL_INT_FIELDS
+                .iter()
+                .chain(&PAL_TEXT_FIELDS)
+                .copied()
+                .collect();
+            let (field, value) = field_update(operation, &fields)?;
+            let field = fields
+                .into_iter()
+                .find(|known| *known == field)
+                .unwrap_or_default();
+            if let Some(slot) = pal.int_field_mut(field) {
+                *slot = Some(
+                    value
+                        .as_i64()
+                        .ok_or_else(|| invalid_value(field, "an integer"))?,
+                );
+            } else {
+                let text = value
+                    .as_str()
+                    .ok_or_else(|| invalid_value(field, "a string"))?
+                    .to_string();
+                match field {
+                    "gender" => pal.gender = Some(text),
+                    _ => pal.nickname = Some(text),
+                }
+            }
+            Ok(vec![field])
+        }
+        "replace_pal_passive_list" => {
+            pal.passive_skill_ids = string_list(payload, "passive_skill_ids")?;
+            Ok(vec!["passive_skill_ids"])
+        }
+        "replace_pal_mastered_waza_list" => {
+            pal.mastered_waza_ids = string_list(payload, "waza_ids")?;
+            Ok(vec!["mastered_waza_ids", "equip_waza_ids"])
+        }
+        "replace_pal_equipped_waza_list" => {
+            pal.equip_waza_ids = string_list(payload, "waza_ids")?;
+            Ok(vec!["equip_waza_ids"])
+        }
+        _ => {
+            pal.work_suitability_ranks = payload
+                .get("work_suitability_ranks")
+                .and_then(|ranks| serde_json::from_value(ranks.clone()).ok())
+                .ok_or_else(|| {
+                    invalid_payload("payload must have a work_suitability_ranks object of integers")
+                })?;
+            Ok(vec!["work_suitability_ranks"])
+        }
+    }
+}
+
+fn field_update<'a>(
+    operation: &'a PatchOperation,
+    fields: &[&str],
+) -> Result<(String, &'a Value), ValidationIssue> {
+    let field = operation
+        .payload_json
+        .get("field")
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid_payload("payload must have a field string"))?;
+    if !fields.contains(&field) {
+        return Err(ValidationIssue::new(
+            "unknown_field",
+            Some(field),
+            format!("{} cannot set {}", operation.op_type, field),
+        ));
+    }
+    let value = operation
+        .payload_json
+        .get("value")
+        .ok_or_else(|| invalid_payload("payload must have a value"))?;
+    Ok((field.to_string(), value))
+}
+
+fn string_list(payload: &Value, key: &str) -> Result<Vec<String>, ValidationIssue> {
+    payload
+        .get(key)
+        .and_then(|list| serde_json::from_value(list.clone()).ok())
+        .ok_or_else(|| invalid_payload(&format!("payload must have a {} string array", key)))
+}
+
+/// Runs the rules that involve `fields` against the whole of `pal`, the result
+/// of editing `previous`, so cross-field rules see the other fields' current
+/// values. Passives already on `previous` came from the save, so they are
+/// accepted even when the catalog cannot resolve them.
+pub fn validate_pal_fields(
+    catalog: &GameCatalog,
+    previous: &PalState,
+    pal: &PalState,
+    fields: &[&str],
+) -> Vec<ValidationIssue> {
+    let species = pal
+        .species_id
+        .as_deref()
+        .and_then(|species_id| catalog.species(species_id));
+    let mut issues = Vec::new();
+    for field in fields {
+        match *field {
+            "level" => issues.extend(check_level(catalog, pal.level)),
+            "exp" if pal.exp.is_some_and(|exp| exp < 0) => {
+                issues.push(out_of_range("exp_out_of_range", field, "0 or more"));
+            }
+            "rank" => {
+                let max_rank = i64::from(MAX_CONDENSER_RANK);
+                if pal
+                    .rank
+                    .is_some_and(|rank| !(MIN_CONDENSER_RANK..=max_rank).contains(&rank))
+                {
+                    issues.push(out_of_range(
+                        "rank_out_of_range",
+                        field,
+                        &format!(
+                            "{}-{} (0-{} condenser stars)",
+                            MIN_CONDENSER_RANK,
+                            max_rank,
+                            max_rank - MIN_CONDENSER_RANK
+                        ),
+                    ));
+                }
+            }
+            "rank_hp" | "rank_attack" | "rank_defense" | "rank_craftspeed"
+                if pal.int_field(field).is_some_and(|rank| rank < 0) =>
+            {
+                issues.push(out_of_range("soul_rank_out_of_range", field, "0 or more"));
+            }
+            "talent_hp" | "talent_melee" | "talent_shot" | "talent_defense"
+                if pal
+                    .int_field(field)
+                    .is_some_and(|talent| !(0..=MAX_TALENT).contains(&talent)) =>
+            {
+                issues.push(out_of_range(
+                    "talent_out_of_range",
+                    field,
+                    &format!("0-{}", MAX_TALENT),
+                ));
+            }
+            "gender" => {
+                let Some(gender) = pal.gender.as_deref() else {
+                    continue;
+                };
+                let male_probability = species.as_ref().map(|species| species.pal.male_probability);
+                match enum_value(gender) {
+                    "Male" if male_probability == Some(0.0) => issues.push(gender_issue(gender)),
+                    "Female" if male_probability == Some(100.0) => {
+                        issues.push(gender_issue(gender))
+                    }
+                    "Male" | "Female" => {}
+                    _ => issues.push(ValidationIssue::new(
+                        "invalid_gender",
+                        Some(field),
+                        format!("gender {} is not Male or Female", gender),
+                    )),
+                }
+            }
+            "passive_skill_ids" => {
+                if pal.passive_skill_ids.len() > MAX_PASSIVES {
+                    issues.push(ValidationIssue::new(
+                        "too_many_passives",
+                        Some(field),
+                        format!("a pal has at most {} passives", MAX_PASSIVES),
+                    ));
+                }
+                let mut seen = HashSet::new();
+                for passive_id in &pal.passive_skill_ids {
+                    if !seen.insert(passive_id.to_ascii_lowercase()) {
+                        issues.push(ValidationIssue::new(
+                            "duplicate_passive",
+                            Some(field),
+                            format!("passive {} is listed twice", passive_id),
+                        ));
+                    } else if catalog.passive(passive_id).is_none()
+                        && !previous
+                            .passive_skill_ids
+                            .iter()
+                            .any(|existing| existing.eq_ignore_ascii_case(passive_id))
+                    {
+                        issues.push(ValidationIssue::new(
+                            "unknown_passive",
+                            Some(field),
+                            format!("passive {} is not in the passive catalog", passive_id),
+                        ));
+                    }
+                }
+            }
+            "mastered_waza_ids" => {
+                let mut seen = HashSet::new();
+                for waza_id in &pal.mastered_waza_ids {
+                    let waza = enum_value(waza_id);
+                    if !seen.insert(waza) {
+                        issues.push(duplicate_waza(field, waza));
+                    } else if catalog.active_skill(waza).is_none() {
+                        issues.push(unknown_waza(field, waza));
+                    } else if let Some(species) = &species {
+                        let learned = species
+                            .pal
+                            .learnset
+                            .iter()
+                            .any(|skill| skill.waza_id == waza);
+                        if !learned && !catalog.is_skill_fruit_waza(waza) {
+                            issues.push(ValidationIssue::new(
+                                "waza_not_learnable",
+                                Some(field),
+                                format!(
+                                    "{} cannot learn {}; it is not in its learnset or a skill fruit",
+                                    species.pal.species_id, waza
+                                ),
+                            ));
+                        }
+                    }
+                }
+            }
+            "equip_waza_ids" => {
+                if pal.equip_waza_ids.len() > MAX_EQUIPPED_WAZA {
+                    issues.push(ValidationIssue::new(
+                        "too_many_equipped_waza",
+                        Some(field),
+                        format!("a pal equips at most {} skills", MAX_EQUIPPED_WAZA),
+                    ));
+                }
+                let mastered: HashSet<&str> = pal
+                    .mastered_waza_ids
+                    .iter()
+                    .map(|waza_id| enum_value(waza_id))
+                    .collect();
+                let mut seen = HashSet::new();
+                for waza_id in &pal.equip_waza_ids {
+                    let waza = enum_value(waza_id);
+                    if !seen.insert(waza) {
+                        issues.push(duplicate_waza(field, waza));
+                    } else if catalog.active_skill(waza).is_none() {
+                        issues.push(unknown_waza(field, waza));
+                    } else if !mastered.contains(waza) {
+                        issues.push(ValidationIssue::new(
+                            "equipped_waza_not_mastered",
+                            Some(field),
+                            format!("equipped skill {} is not in mastered_waza_ids", waza),
+                        ));
+                    }
+                }
+            }
+            "work_suitability_ranks" => {
+                for (work, level) in &pal.work_suitability_ranks {
+                    if !(0..=MAX_WORK_SUITABILITY_LEVEL).contains(level) {
+                        issues.push(ValidationIssue::new(
+                            "work_suitability_out_of_range",
+                            Some(field),
+                            format!(
+                                "{} level {} must be 0-{}",
+                                work, level, MAX_WORK_SUITABILITY_LEVEL
+                            ),
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    issues
+}
+
+fn check_level(catalog: &GameCatalog, level: Option<i64>) -> Option<ValidationIssue> {
+    let level_cap = catalog.tech_level_cap();
+    level
+        .filter(|level| !(1..=level_cap).contains(level))
+        .map(|_| out_of_range("level_out_of_range", "level", &format!("1-{}", level_cap)))
+}
+
+/// Save enum values carry their type (`EPalGenderType::Male`); payloads may
+/// omit it.
+fn enum_value(value: &str) -> &str {
+    value.rsplit("::").next().unwrap_or(value)
+}
+
+fn unknown_target(kind: &str, id: &str) -> ValidationIssue {
+    ValidationIssue::new(
+        "unknown_target",
+        None,
+        format!("{} {} not found in this import", kind, id),
+    )
+}
+
+fn invalid_payload(message: &str) -> ValidationIssue {
+    ValidationIssue::new("invalid_payload", None, message.to_string())
+}
+
+fn invalid_value(field: &str, expected: &str) -> ValidationIssue {
+    ValidationIssue::new(
+        "invalid_payload",
+        Some(field),
+        format!("{} must be {}", field, expected),
+    )
+}
+
+fn out_of_range(code: &'static str, field: &str, range: &str) -> ValidationIssue {
+    ValidationIssue::new(code, Some(field), format!("{} must be {}", field, range))
+}
+
+fn gender_issue(gender: &str) -> ValidationIssue {
+    ValidationIssue::new(
+        "invalid_gender",
+        Some("gender"),
+        format!("this species is never {}", enum_value(gender)),
+    )
+}
+
+fn duplicate_waza(field: &str, waza: &str) -> ValidationIssue {
+    ValidationIssue::new(
+        "duplicate_waza",
+        Some(field),
+        format!("skill {} is listed twice", waza),
+    )
+}
+
+fn unknown_waza(field: &str, waza: &str) -> ValidationIssue {
+    ValidationIssue::new(
+        "unknown_waza",
+        Some(field),
+        format!("skill {} is not in the active skill catalog", waza),
+    )
+}
@@ -0,0 +1,45 @@
+use crate::save::detect::detect_save_variant;
+use crate::save::normalize::{as_custom_struct, get_guid_uid, parse_gvas_with_hints};
+use crate::save::parse::decode_to_gvas;
+use gvas::properties::Property;
+
+/// Character container ids a player owns, read from `Players/<uid>.sav`.
+/// Pals in these containers are located by joining them with
+/// `CharacterContainerSaveData` in `Level.sav`.
+#[derive(Debug, Clone, Default)]
+pub struct PlayerContainers {
+    pub party_container_id: Option<String>,
+    pub palbox_container_id: Option<String>,
+}
+
+pub fn decode_player_containers(bytes: &[u8]) -> Result<PlayerContainers, String> {
+    let variant = detect_save_variant(bytes);
+    let gvas_bytes =
+        decode_to_gvas(bytes, &variant).map_err(|error| format!("decode failed: {}", error))?;
+    let gvas = parse_gvas_with_hints(&gvas_bytes)?;
+
+    let save_data = gvas
+        .properties
+        .get("SaveData")
+        .and_then(as_custom_struct)
+        .ok_or_else(|| "missing SaveData CustomStruct".to_string())?;
+
+    Ok(PlayerContainers {
+        party_container_id: container_id(
+            save_data
+                .get("OtomoCharacterContainerId")
+                .and_then(|values| values.first()),
+        ),
+        palbox_container_id: container_id(
+            save_data
+                .get("PalStorageContainerId")
+                .and_then(|values| values.first()),
+        ),
+    })
+}
+
+/// Reads the `ID` guid of a `PalContainerId` struct.
+pub(crate) fn container_id(property: Option<&Property>) -> Option<String> {
+    let container = as_custom_struct(property?)?;
+    get_guid_uid(container.get("ID").and_then(|values| values.first()))
+}
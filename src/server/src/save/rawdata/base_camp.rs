@@ -1,6 +1,6 @@
 use crate::save::rawdata::{
-    decode_f32, decode_fstring, decode_guid, decode_u8, passthrough_encode, read_bytes,
-    read_remaining, to_hex,
+    decode_f32, decode_fstring, decode_guid, decode_transform, decode_u8, passthrough_encode,
+    read_bytes, read_remaining, to_hex,
 };
 use serde_json::{Value, json};
 use std::io::Cursor;
@@ -22,6 +22,7 @@ pub fn decode(bytes: &[u8]) -> Result<Value, String> {
         "id": id,
         "name": name,
         "state": state,
+        "transform": decode_transform(&transform)?,
         "transform_hex": to_hex(&transform),
         "area_range": area_range,
         "group_id_belong_to": group_id_belong_to,
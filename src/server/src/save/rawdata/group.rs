@@ -1,5 +1,9 @@
-use crate::save::rawdata::{passthrough_decode, passthrough_encode};
-use serde_json::Value;
+use crate::save::rawdata::{
+    decode_fstring, decode_guid, decode_i32, decode_i64, decode_u8, passthrough_decode,
+    passthrough_encode, read_remaining, to_hex,
+};
+use serde_json::{Map, Value, json};
+use std::io::Cursor;
 
 pub fn decode(bytes: &[u8]) -> Result<Value, String> {
     Ok(passthrough_decode(bytes))
@@ -8,3 +12,110 @@ pub fn decode(bytes: &[u8]) -> Result<Value, String> {
 pub fn encode(value: &Value) -> Result<Vec<u8>, String> {
     passthrough_encode(value)
 }
+
+/// Decodes one `GroupSaveDataMap` value's RawData. The layout depends on the
+/// sibling `GroupType` enum (`EPalGroupType::Guild`, ...).
+pub fn decode_group(bytes: &[u8], group_type: &str) -> Result<Value, String> {
+    let is_guild = group_type == "EPalGroupType::Guild";
+    let is_independent_guild = group_type == "EPalGroupType::IndependentGuild";
+    let is_organization =
+        is_guild || is_independent_guild || group_type == "EPalGroupType::Organization";
+
+    let mut cursor = Cursor::new(bytes);
+    let mut group = Map::new();
+    group.insert("codec_status".to_string(), json!("decoded"));
+    group.insert("group_type".to_string(), json!(group_type));
+    group.insert("group_id".to_string(), json!(decode_guid(&mut cursor)?));
+    group.insert(
+        "group_name".to_string(),
+        json!(decode_fstring(&mut cursor)?),
+    );
+
+    let handle_count = decode_count(&mut cursor)?;
+    let mut handles = Vec::with_capacity(handle_count.min(1024));
+    for _ in 0..handle_count {
+        let guid = decode_guid(&mut cursor)?;
+        let instance_id = decode_guid(&mut cursor)?;
+        handles.push(json!({ "guid": guid, "instance_id": instance_id }));
+    }
+    group.insert(
+        "individual_character_handle_ids".to_string(),
+        Value::Array(handles),
+    );
+
+    if is_organization {
+        group.insert("org_type".to_string(), json!(decode_u8(&mut cursor)?));
+        group.insert("base_ids".to_string(), json!(decode_guids(&mut cursor)?));
+    }
+
+    if is_guild || is_independent_guild {
+        group.insert(
+            "base_camp_level".to_string(),
+            json!(decode_i32(&mut cursor)?),
+        );
+        group.insert(
+            "map_object_instance_ids_base_camp_points".to_string(),
+            json!(decode_guids(&mut cursor)?),
+        );
+        group.insert(
+            "guild_name".to_string(),
+            json!(decode_fstring(&mut cursor)?),
+        );
+        group.insert(
+            "last_guild_name_modifier_player_uid".to_string(),
+            json!(decode_guid(&mut cursor)?),
+        );
+    }
+
+    if is_independent_guild {
+        group.insert("player_uid".to_string(), json!(decode_guid(&mut cursor)?));
+        group.insert(
+            "guild_name_2".to_string(),
+            json!(decode_fstring(&mut cursor)?),
+        );
+        group.insert("player_info".to_string(), decode_player_info(&mut cursor)?);
+    }
+
+    if is_guild {
+        group.insert(
+            "admin_player_uid".to_string(),
+            json!(decode_guid(&mut cursor)?),
+        );
+        let player_count = decode_count(&mut cursor)?;
+        let mut players = Vec::with_capacity(player_count.min(1024));
+        for _ in 0..player_count {
+            let player_uid = decode_guid(&mut cursor)?;
+            let player_info = decode_player_info(&mut cursor)?;
+            players.push(json!({ "player_uid": player_uid, "player_info": player_info }));
+        }
+        group.insert("players".to_string(), Value::Array(players));
+    }
+
+    let unknown_tail = read_remaining(&mut cursor);
+    group.insert("unknown_tail_hex".to_string(), json!(to_hex(&unknown_tail)));
+    group.insert("original_bytes_hex".to_string(), json!(to_hex(bytes)));
+    Ok(Value::Object(group))
+}
+
+fn decode_player_info(cursor: &mut Cursor<&[u8]>) -> Result<Value, String> {
+    let last_online_real_time = decode_i64(cursor)?;
+    let player_name = decode_fstring(cursor)?;
+    Ok(json!({
+        "last_online_real_time": last_online_real_time,
+        "player_name": player_name,
+    }))
+}
+
+fn decode_guids(cursor: &mut Cursor<&[u8]>) -> Result<Vec<String>, String> {
+    let count = decode_count(cursor)?;
+    let mut guids = Vec::with_capacity(count.min(1024));
+    for _ in 0..count {
+        guids.push(decode_guid(cursor)?);
+    }
+    Ok(guids)
+}
+
+fn decode_count(cursor: &mut Cursor<&[u8]>) -> Result<usize, String> {
+    let count = decode_i32(cursor)?;
+    usize::try_from(count).map_err(|_| format!("invalid array length: {count}"))
+}
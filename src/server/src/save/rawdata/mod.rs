@@ -38,12 +38,46 @@ pub fn decode_u8(cursor: &mut Cursor<&[u8]>) -> Result<u8, String> {
         .map_err(|error| format!("failed to read u8: {error}"))
 }
 
+pub fn decode_i32(cursor: &mut Cursor<&[u8]>) -> Result<i32, String> {
+    cursor
+        .read_i32::<LittleEndian>()
+        .map_err(|error| format!("failed to read i32: {error}"))
+}
+
+pub fn decode_i64(cursor: &mut Cursor<&[u8]>) -> Result<i64, String> {
+    cursor
+        .read_i64::<LittleEndian>()
+        .map_err(|error| format!("failed to read i64: {error}"))
+}
+
 pub fn decode_f32(cursor: &mut Cursor<&[u8]>) -> Result<f32, String> {
     cursor
         .read_f32::<LittleEndian>()
         .map_err(|error| format!("failed to read f32: {error}"))
 }
 
+pub fn decode_f64(cursor: &mut Cursor<&[u8]>) -> Result<f64, String> {
+    cursor
+        .read_f64::<LittleEndian>()
+        .map_err(|error| format!("failed to read f64: {error}"))
+}
+
+/// Decodes an 80-byte `FTransform` (double-precision rotation quaternion,
+/// translation, and scale).
+pub fn decode_transform(bytes: &[u8]) -> Result<Value, String> {
+    let mut cursor = Cursor::new(bytes);
+    let mut components = [0f64; 10];
+    for component in &mut components {
+        *component = decode_f64(&mut cursor)?;
+    }
+    let [qx, qy, qz, qw, x, y, z, sx, sy, sz] = components;
+    Ok(json!({
+        "rotation": { "x": qx, "y": qy, "z": qz, "w": qw },
+        "translation": { "x": x, "y": y, "z": z },
+        "scale": { "x": sx, "y": sy, "z": sz },
+    }))
+}
+
 pub fn read_bytes(cursor: &mut Cursor<&[u8]>, len: usize) -> Result<Vec<u8>, String> {
     let start = cursor.position() as usize;
     let end = start.saturating_add(len);
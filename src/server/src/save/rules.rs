@@ -0,0 +1,72 @@
+//! Game rules shared by normalization, patch validation, and the planner
+//! engines.
+
+use crate::catalog::GameCatalog;
+use std::collections::BTreeMap;
+
+/// Passive skills a pal can carry.
+pub const MAX_PASSIVES: usize = 4;
+/// `Rank` of a fully condensed pal (four stars).
+pub const MAX_CONDENSER_RANK: i32 = 5;
+/// Highest work suitability level, including manual and condenser bonuses.
+pub const MAX_WORK_SUITABILITY_LEVEL: i64 = 5;
+
+/// A pal's work suitability levels: the species levels plus levels added by
+/// work suitability manuals, one higher for a fully condensed pal, capped at
+/// `MAX_WORK_SUITABILITY_LEVEL`. Manuals only raise work types the species
+/// already has.
+pub fn species_work_levels(
+    species_levels: &BTreeMap<String, i64>,
+    added_levels: &BTreeMap<String, i64>,
+    rank: Option<i32>,
+) -> BTreeMap<String, i64> {
+    let condenser_bonus = condenser_work_bonus(rank);
+    species_levels
+        .iter()
+        .filter(|(_, level)| **level > 0)
+        .map(|(work, level)| {
+            let added = added_levels.get(work).copied().unwrap_or(0);
+            (
+                work.clone(),
+                (level + added + condenser_bonus).min(MAX_WORK_SUITABILITY_LEVEL),
+            )
+        })
+        .collect()
+}
+
+/// The manual levels that give `levels` under `species_work_levels`. Work
+/// types the species lacks are dropped, since manuals cannot add them; a level
+/// at the cap keeps only the manual levels needed to reach it.
+pub fn manual_work_levels(
+    species_levels: &BTreeMap<String, i64>,
+    levels: &BTreeMap<String, i64>,
+    rank: Option<i32>,
+) -> BTreeMap<String, i64> {
+    let condenser_bonus = condenser_work_bonus(rank);
+    species_levels
+        .iter()
+        .filter(|(_, level)| **level > 0)
+        .filter_map(|(work, species_level)| {
+            let added = levels.get(work)? - species_level - condenser_bonus;
+            (added > 0).then(|| (work.clone(), added))
+        })
+        .collect()
+}
+
+/// `species_work_levels` for a save `CharacterID`; empty when the species is
+/// not in the catalog.
+pub fn pal_work_levels(
+    catalog: &GameCatalog,
+    species_id: Option<&str>,
+    added_levels: &BTreeMap<String, i64>,
+    rank: Option<i32>,
+) -> BTreeMap<String, i64> {
+    species_id
+        .and_then(|species_id| catalog.species(species_id))
+        .map(|species| species_work_levels(&species.pal.work_suitability, added_levels, rank))
+        .unwrap_or_default()
+}
+
+fn condenser_work_bonus(rank: Option<i32>) -> i64 {
+    i64::from(rank.unwrap_or(1) >= MAX_CONDENSER_RANK)
+}
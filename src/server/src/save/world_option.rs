@@ -0,0 +1,957 @@
+use crate::save::detect::detect_save_variant;
+use crate::save::normalize::{as_custom_struct, parse_gvas_with_hints};
+use crate::save::parse::{decode_to_gvas, encode_plz};
+use gvas::engine_version::FEngineVersion;
+use gvas::game_version::DeserializedGameVersion;
+use gvas::properties::Property;
+use gvas::properties::array_property::ArrayProperty;
+use gvas::properties::enum_property::EnumProperty;
+use gvas::properties::int_property::{
+    BoolProperty, BytePropertyValue, DoubleProperty, FloatProperty, Int64Property, IntProperty,
+};
+use gvas::properties::str_property::StrProperty;
+use gvas::properties::struct_property::{StructProperty, StructPropertyValue};
+use gvas::types::Guid;
+use gvas::types::map::HashableIndexMap;
+use gvas::{GvasFile, GvasHeader};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+/// Section header the dedicated server expects above the `OptionSettings` line.
+const INI_SECTION: &str = "[/Script/Pal.PalGameWorldSettings]";
+const WORLD_OPTION_SAVE_GAME_CLASS: &str = "/Script/Pal.PalWorldOptionSaveGame";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldSettingKind {
+    Bool,
+    Int,
+    Float,
+    Text,
+    TextList,
+    Enum(&'static str),
+    EnumList(&'static str),
+}
+
+/// One `OptionWorldData.Settings` field. `default` is the value as written in
+/// `DefaultPalWorldSettings.ini`; `bounds` are the documented limits.
+#[derive(Debug, Clone, Copy)]
+pub struct WorldSettingSpec {
+    pub name: &'static str,
+    pub kind: WorldSettingKind,
+    pub default: &'static str,
+    pub bounds: Option<(f64, f64)>,
+}
+
+const fn spec(
+    name: &'static str,
+    kind: WorldSettingKind,
+    default: &'static str,
+) -> WorldSettingSpec {
+    WorldSettingSpec {
+        name,
+        kind,
+        default,
+        bounds: None,
+    }
+}
+
+const fn bounded(
+    name: &'static str,
+    kind: WorldSettingKind,
+    default: &'static str,
+    min: f64,
+    max: f64,
+) -> WorldSettingSpec {
+    WorldSettingSpec {
+        name,
+        kind,
+        default,
+        bounds: Some((min, max)),
+    }
+}
+
+use WorldSettingKind::{Bool, Enum, EnumList, Float, Int, Text, TextList};
+
+/// Settings in `DefaultPalWorldSettings.ini` order.
+pub static WORLD_SETTINGS: &[WorldSettingSpec] = &[
+    spec("Difficulty", Enum("EPalOptionWorldDifficulty"), "None"),
+    spec("RandomizerType", Enum("EPalRandomizerType"), "None"),
+    spec("RandomizerSeed", Text, "\"\""),
+    spec("bIsRandomizerPalLevelRandom", Bool, "False"),
+    spec("DayTimeSpeedRate", Float, "1.000000"),
+    spec("NightTimeSpeedRate", Float, "1.000000"),
+    spec("ExpRate", Float, "1.000000"),
+    spec("PalCaptureRate", Float, "1.000000"),
+    spec("PalSpawnNumRate", Float, "1.000000"),
+    spec("PalDamageRateAttack", Float, "1.000000"),
+    spec("PalDamageRateDefense", Float, "1.000000"),
+    spec("PlayerDamageRateAttack", Float, "1.000000"),
+    spec("PlayerDamageRateDefense", Float, "1.000000"),
+    spec("PlayerStomachDecreaceRate", Float, "1.000000"),
+    spec("PlayerStaminaDecreaceRate", Float, "1.000000"),
+    spec("PlayerAutoHPRegeneRate", Float, "1.000000"),
+    spec("PlayerAutoHpRegeneRateInSleep", Float, "1.000000"),
+    spec("PalStomachDecreaceRate", Float, "1.000000"),
+    spec("PalStaminaDecreaceRate", Float, "1.000000"),
+    spec("PalAutoHPRegeneRate", Float, "1.000000"),
+    spec("PalAutoHpRegeneRateInSleep", Float, "1.000000"),
+    spec("BuildObjectHpRate", Float, "1.000000"),
+    spec("BuildObjectDamageRate", Float, "1.000000"),
+    spec("BuildObjectDeteriorationDamageRate", Float, "1.000000"),
+    spec("CollectionDropRate", Float, "1.000000"),
+    spec("CollectionObjectHpRate", Float, "1.000000"),
+    spec("CollectionObjectRespawnSpeedRate", Float, "1.000000"),
+    spec("EnemyDropItemRate", Float, "1.000000"),
+    spec("DeathPenalty", Enum("EPalOptionWorldDeathPenalty"), "All"),
+    spec("bEnablePlayerToPlayerDamage", Bool, "False"),
+    spec("bEnableFriendlyFire", Bool, "False"),
+    spec("bEnableInvaderEnemy", Bool, "True"),
+    spec("bActiveUNKO", Bool, "False"),
+    spec("bEnableAimAssistPad", Bool, "True"),
+    spec("bEnableAimAssistKeyboard", Bool, "False"),
+    spec("DropItemMaxNum", Int, "3000"),
+    spec("DropItemMaxNum_UNKO", Int, "100"),
+    spec("BaseCampMaxNum", Int, "128"),
+    bounded("BaseCampWorkerMaxNum", Int, "15", 1.0, 50.0),
+    spec("DropItemAliveMaxHours", Float, "1.000000"),
+    spec("bAutoResetGuildNoOnlinePlayers", Bool, "False"),
+    spec("AutoResetGuildTimeNoOnlinePlayers", Float, "72.000000"),
+    spec("GuildPlayerMaxNum", Int, "20"),
+    bounded("BaseCampMaxNumInGuild", Int, "4", 1.0, 10.0),
+    spec("PalEggDefaultHatchingTime", Float, "72.000000"),
+    spec("WorkSpeedRate", Float, "1.000000"),
+    spec("AutoSaveSpan", Float, "30.000000"),
+    spec("bIsMultiplay", Bool, "False"),
+    spec("bIsPvP", Bool, "False"),
+    spec("bHardcore", Bool, "False"),
+    spec("bPalLost", Bool, "False"),
+    spec("bCharacterRecreateInHardcore", Bool, "False"),
+    spec("bCanPickupOtherGuildDeathPenaltyDrop", Bool, "False"),
+    spec("bEnableNonLoginPenalty", Bool, "True"),
+    spec("bEnableFastTravel", Bool, "True"),
+    spec("bEnableFastTravelOnlyBaseCamp", Bool, "False"),
+    spec("bIsStartLocationSelectByMap", Bool, "True"),
+    spec("bExistPlayerAfterLogout", Bool, "False"),
+    spec("bEnableDefenseOtherGuildPlayer", Bool, "False"),
+    spec("bInvisibleOtherGuildBaseCampAreaFX", Bool, "False"),
+    spec("bBuildAreaLimit", Bool, "False"),
+    spec("ItemWeightRate", Float, "1.000000"),
+    spec("CoopPlayerMaxNum", Int, "4"),
+    spec("ServerPlayerMaxNum", Int, "32"),
+    spec("ServerName", Text, "\"Default Palworld Server\""),
+    spec("ServerDescription", Text, "\"\""),
+    spec("AdminPassword", Text, "\"\""),
+    spec("ServerPassword", Text, "\"\""),
+    spec("bAllowClientMod", Bool, "True"),
+    bounded("PublicPort", Int, "8211", 1.0, 65535.0),
+    spec("PublicIP", Text, "\"\""),
+    spec("RCONEnabled", Bool, "False"),
+    bounded("RCONPort", Int, "25575", 1.0, 65535.0),
+    spec("Region", Text, "\"\""),
+    spec("bUseAuth", Bool, "True"),
+    spec(
+        "BanListURL",
+        Text,
+        "\"https://api.palworldgame.com/api/banlist.txt\"",
+    ),
+    spec("RESTAPIEnabled", Bool, "False"),
+    bounded("RESTAPIPort", Int, "8212", 1.0, 65535.0),
+    spec("bShowPlayerList", Bool, "False"),
+    spec("ChatPostLimitPerMinute", Int, "10"),
+    spec(
+        "CrossplayPlatforms",
+        EnumList("EPalAllowConnectPlatform"),
+        "(Steam,Xbox,PS5,Mac)",
+    ),
+    spec("bIsUseBackupSaveData", Bool, "True"),
+    spec("LogFormatType", Enum("EPalLogFormatType"), "Text"),
+    spec("bIsShowJoinLeftMessage", Bool, "True"),
+    spec("SupplyDropSpan", Int, "180"),
+    spec("EnablePredatorBossPal", Bool, "True"),
+    spec("MaxBuildingLimitNum", Int, "0"),
+    bounded(
+        "ServerReplicatePawnCullDistance",
+        Float,
+        "15000.000000",
+        5000.0,
+        15000.0,
+    ),
+    spec("bAllowGlobalPalboxExport", Bool, "True"),
+    spec("bAllowGlobalPalboxImport", Bool, "False"),
+    spec("EquipmentDurabilityDamageRate", Float, "1.000000"),
+    spec("ItemContainerForceMarkDirtyInterval", Float, "1.000000"),
+    spec("ItemCorruptionMultiplier", Float, "1.000000"),
+    spec("DenyTechnologyList", TextList, "()"),
+    spec("GuildRejoinCooldownMinutes", Int, "0"),
+    spec("BlockRespawnTime", Float, "5.000000"),
+    spec("RespawnPenaltyDurationThreshold", Float, "0.000000"),
+    spec("RespawnPenaltyTimeScale", Float, "2.000000"),
+    spec("bDisplayPvPItemNumOnWorldMap_BaseCamp", Bool, "False"),
+    spec("bDisplayPvPItemNumOnWorldMap_Player", Bool, "False"),
+    spec(
+        "AdditionalDropItemWhenPlayerKillingInPvPMode",
+        Text,
+        "\"PlayerDropItem\"",
+    ),
+    spec("AdditionalDropItemNumWhenPlayerKillingInPvPMode", Int, "1"),
+    spec(
+        "bAdditionalDropItemWhenPlayerKillingInPvPMode",
+        Bool,
+        "False",
+    ),
+    spec("bAllowEnhanceStat_Health", Bool, "True"),
+    spec("bAllowEnhanceStat_Attack", Bool, "True"),
+    spec("bAllowEnhanceStat_Stamina", Bool, "True"),
+    spec("bAllowEnhanceStat_Weight", Bool, "True"),
+    spec("bAllowEnhanceStat_WorkSpeed", Bool, "True"),
+];
+
+/// A decoded setting value. Enum values are stored without their
+/// `EPalType::` prefix, matching the INI spelling.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WorldSettingValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    List(Vec<String>),
+}
+
+pub type WorldSettings = BTreeMap<String, WorldSettingValue>;
+
+/// Payload of the `update_world_option` patch operation. Settings come
+/// either as a `settings` object or as the text of a `PalWorldSettings.ini`,
+/// not both.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UpdateWorldOptionPayload {
+    #[serde(default)]
+    pub settings: WorldSettings,
+    pub option_settings_ini: Option<String>,
+}
+
+impl UpdateWorldOptionPayload {
+    /// The requested settings, before validation against the catalog.
+    pub fn changes(self) -> Result<WorldSettings, String> {
+        match self.option_settings_ini {
+            Some(_) if !self.settings.is_empty() => {
+                Err("provide either settings or option_settings_ini, not both".to_string())
+            }
+            Some(text) => parse_option_settings_ini(&text),
+            None => Ok(self.settings),
+        }
+    }
+}
+
+pub fn world_setting_spec(name: &str) -> Option<&'static WorldSettingSpec> {
+    WORLD_SETTINGS.iter().find(|spec| spec.name == name)
+}
+
+/// Reads `OptionWorldData.Settings` from a `WorldOption.sav`. Properties of
+/// types the planner does not model are left out.
+pub fn decode_world_option(bytes: &[u8]) -> Result<WorldSettings, String> {
+    let gvas = parse_save_gvas(bytes)?;
+    let settings = settings_struct(&gvas.properties)?;
+
+    let mut decoded = WorldSettings::new();
+    for (name, values) in settings.iter() {
+        if let Some(value) = values.first().and_then(property_to_value) {
+            decoded.insert(name.clone(), value);
+        }
+    }
+    Ok(decoded)
+}
+
+/// Applies validated `changes` to the original `WorldOption.sav` and
+/// re-encodes it as `PlZ`. Fields not named in `changes` keep their original
+/// property type and value.
+pub fn rebuild_world_option(bytes: &[u8], changes: &WorldSettings) -> Result<Vec<u8>, String> {
+    let mut gvas = parse_save_gvas(bytes)?;
+    let settings = settings_struct_mut(&mut gvas.properties)?;
+
+    for (name, value) in changes {
+        match settings.get_mut(name).and_then(|values| values.first_mut()) {
+            Some(property) => apply_value(name, property, value)?,
+            None => {
+                settings.insert(name.clone(), vec![new_property(name, value)?]);
+            }
+        }
+    }
+
+    let mut writer = Cursor::new(Vec::new());
+    gvas.write(&mut writer)
+        .map_err(|error| format!("gvas write failed: {}", error))?;
+    encode_plz(&writer.into_inner())
+}
+
+/// Coerces user-supplied values to each setting's kind and checks bounds.
+/// Settings outside the catalog are accepted only when `existing` already
+/// holds a value of the same shape.
+pub fn validate_world_settings(
+    changes: &WorldSettings,
+    existing: &WorldSettings,
+) -> Result<WorldSettings, String> {
+    let mut validated = WorldSettings::new();
+    for (name, value) in changes {
+        let coerced = match world_setting_spec(name) {
+            Some(spec) => coerce_value(spec, value)?,
+            None => match existing.get(name) {
+                Some(current) if same_shape(current, value) => value.clone(),
+                Some(_) => {
+                    return Err(format!("world setting {} has the wrong value type", name));
+                }
+                None => return Err(format!("unknown world setting: {}", name)),
+            },
+        };
+        validated.insert(name.clone(), coerced);
+    }
+    Ok(validated)
+}
+
+/// Parses the `OptionSettings=(...)` line of a `PalWorldSettings.ini`.
+pub fn parse_option_settings_ini(text: &str) -> Result<WorldSettings, String> {
+    let body = text
+        .lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix("OptionSettings="))
+        .ok_or_else(|| "missing OptionSettings= line".to_string())?;
+    let inner = body
+        .strip_prefix('(')
+        .and_then(|value| value.strip_suffix(')'))
+        .ok_or_else(|| "OptionSettings value must be wrapped in parentheses".to_string())?;
+
+    let mut settings = WorldSettings::new();
+    for field in split_top_level(inner)? {
+        let field = field.trim();
+        if field.is_empty() {
+            continue;
+        }
+        let (name, literal) = field
+            .split_once('=')
+            .ok_or_else(|| format!("OptionSettings entry is missing '=': {}", field))?;
+        let name = name.trim();
+        let value = parse_ini_literal(literal.trim())?;
+        let value = match world_setting_spec(name) {
+            Some(spec) => coerce_value(spec, &value)?,
+            None => value,
+        };
+        settings.insert(name.to_string(), value);
+    }
+    Ok(settings)
+}
+
+/// Formats settings as a `PalWorldSettings.ini` body: every catalog field in
+/// default-file order, using the default for fields the save does not set,
+/// then any other fields by name.
+pub fn format_option_settings_ini(settings: &WorldSettings) -> String {
+    let mut fields = Vec::with_capacity(WORLD_SETTINGS.len());
+    for spec in WORLD_SETTINGS {
+        let literal = match settings.get(spec.name) {
+            Some(value) => format_ini_literal(Some(spec.kind), value),
+            None => spec.default.to_string(),
+        };
+        fields.push(format!("{}={}", spec.name, literal));
+    }
+    for (name, value) in settings {
+        if world_setting_spec(name).is_none() {
+            fields.push(format!("{}={}", name, format_ini_literal(None, value)));
+        }
+    }
+
+    format!("{}\nOptionSettings=({})\n", INI_SECTION, fields.join(","))
+}
+
+/// Catalog settings at their `DefaultPalWorldSettings.ini` values.
+pub fn default_world_settings() -> Result<WorldSettings, String> {
+    let mut settings = WorldSettings::new();
+    for spec in WORLD_SETTINGS {
+        let value = coerce_value(spec, &parse_ini_literal(spec.default)?)?;
+        settings.insert(spec.name.to_string(), value);
+    }
+    Ok(settings)
+}
+
+/// How an export's `WorldOption.sav` was produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldOptionSource {
+    Rebuilt,
+    Synthesized,
+}
+
+impl WorldOptionSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Rebuilt => "rebuilt",
+            Self::Synthesized => "synthesized",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum WorldOptionBuildError {
+    /// `changes` failed validation.
+    Invalid(String),
+    /// The stored file could not be decoded or the result encoded.
+    Unprocessable(String),
+}
+
+/// The `WorldOption.sav` an export ships: `original` rebuilt with `changes`,
+/// or, when the import has none, one synthesized from defaults plus
+/// `changes` with `level_meta` as the header template. `changes` are
+/// validated here against the settings they apply to.
+pub fn build_export_world_option(
+    original: Option<&[u8]>,
+    level_meta: Option<&[u8]>,
+    changes: &WorldSettings,
+) -> Result<(WorldOptionSource, Vec<u8>), WorldOptionBuildError> {
+    match original {
+        Some(bytes) => {
+            let existing =
+                decode_world_option(bytes).map_err(WorldOptionBuildError::Unprocessable)?;
+            let changes = validate_world_settings(changes, &existing)
+                .map_err(WorldOptionBuildError::Invalid)?;
+            rebuild_world_option(bytes, &changes)
+                .map(|rebuilt| (WorldOptionSource::Rebuilt, rebuilt))
+                .map_err(WorldOptionBuildError::Unprocessable)
+        }
+        None => {
+            let changes = validate_world_settings(changes, &WorldSettings::new())
+                .map_err(WorldOptionBuildError::Invalid)?;
+            synthesize_world_option(&changes, level_meta)
+                .map(|synthesized| (WorldOptionSource::Synthesized, synthesized))
+                .map_err(WorldOptionBuildError::Unprocessable)
+        }
+    }
+}
+
+/// Builds a `WorldOption.sav` for a world that has none: every catalog
+/// setting at its default, overlaid with `settings`, encoded as `PlZ`. The
+/// GVAS header and top-level `Version` are borrowed from `template` (another
+/// save from the same world, normally `LevelMeta.sav`) so the file matches
+/// the world's engine and custom versions; without a readable template a
+/// UE 5.1 header is used.
+pub fn synthesize_world_option(
+    settings: &WorldSettings,
+    template: Option<&[u8]>,
+) -> Result<Vec<u8>, String> {
+    let template = template.and_then(|bytes| parse_save_gvas(bytes).ok());
+    let (header, deserialized_game_version, version) = match template {
+        Some(template) => (
+            template.header,
+            template.deserialized_game_version,
+            template.properties.get("Version").cloned(),
+        ),
+        None => (default_header(), DeserializedGameVersion::Default, None),
+    };
+    let header = with_save_game_class(header, WORLD_OPTION_SAVE_GAME_CLASS);
+
+    if let Some(name) = settings
+        .keys()
+        .find(|name| world_setting_spec(name).is_none())
+    {
+        return Err(format!("unknown world setting: {}", name));
+    }
+    let mut values = default_world_settings()?;
+    values.extend(settings.clone());
+    let mut fields: HashableIndexMap<String, Vec<Property>> = HashableIndexMap::new();
+    for spec in WORLD_SETTINGS {
+        if let Some(value) = values.get(spec.name) {
+            fields.insert(spec.name.to_string(), vec![new_property(spec.name, value)?]);
+        }
+    }
+
+    let mut option_world_data: HashableIndexMap<String, Vec<Property>> = HashableIndexMap::new();
+    option_world_data.insert(
+        "Settings".to_string(),
+        vec![Property::StructProperty(StructProperty::new(
+            Guid::from(0u128),
+            "PalOptionWorldSettings".to_string(),
+            StructPropertyValue::CustomStruct(fields),
+        ))],
+    );
+
+    let mut properties: HashableIndexMap<String, Property> = HashableIndexMap::new();
+    if let Some(version) = version {
+        properties.insert("Version".to_string(), version);
+    }
+    properties.insert(
+        "OptionWorldData".to_string(),
+        Property::StructProperty(StructProperty::new(
+            Guid::from(0u128),
+            "PalOptionWorldSaveData".to_string(),
+            StructPropertyValue::CustomStruct(option_world_data),
+        )),
+    );
+
+    let gvas = GvasFile {
+        deserialized_game_version,
+        header,
+        properties,
+    };
+    let mut writer = Cursor::new(Vec::new());
+    gvas.write(&mut writer)
+        .map_err(|error| format!("gvas write failed: {}", error))?;
+    encode_plz(&writer.into_inner())
+}
+
+fn default_header() -> GvasHeader {
+    GvasHeader::Version3 {
+        package_file_version: 522,
+        package_file_version_ue5: 1009,
+        engine_version: FEngineVersion {
+            major: 5,
+            minor: 1,
+            patch: 1,
+            change_list: 0,
+            branch: "++UE5+Release-5.1".to_string(),
+        },
+        custom_version_format: 3,
+        custom_versions: HashableIndexMap::new(),
+        save_game_class_name: WORLD_OPTION_SAVE_GAME_CLASS.to_string(),
+    }
+}
+
+fn with_save_game_class(header: GvasHeader, class_name: &str) -> GvasHeader {
+    match header {
+        GvasHeader::Version2 {
+            package_file_version,
+            engine_version,
+            custom_version_format,
+            custom_versions,
+            ..
+        } => GvasHeader::Version2 {
+            package_file_version,
+            engine_version,
+            custom_version_format,
+            custom_versions,
+            save_game_class_name: class_name.to_string(),
+        },
+        GvasHeader::Version3 {
+            package_file_version,
+            package_file_version_ue5,
+            engine_version,
+            custom_version_format,
+            custom_versions,
+            ..
+        } => GvasHeader::Version3 {
+            package_file_version,
+            package_file_version_ue5,
+            engine_version,
+            custom_version_format,
+            custom_versions,
+            save_game_class_name: class_name.to_string(),
+        },
+    }
+}
+
+fn parse_save_gvas(bytes: &[u8]) -> Result<gvas::GvasFile, String> {
+    let variant = detect_save_variant(bytes);
+    let gvas_bytes =
+        decode_to_gvas(bytes, &variant).map_err(|error| format!("decode failed: {}", error))?;
+    parse_gvas_with_hints(&gvas_bytes)
+}
+
+fn settings_struct(
+    properties: &HashableIndexMap<String, Property>,
+) -> Result<&HashableIndexMap<String, Vec<Property>>, String> {
+    let option_world_data = properties
+        .get("OptionWorldData")
+        .and_then(as_custom_struct)
+        .ok_or_else(|| "missing OptionWorldData CustomStruct".to_string())?;
+    option_world_data
+        .get("Settings")
+        .and_then(|values| values.first())
+        .and_then(as_custom_struct)
+        .ok_or_else(|| "missing OptionWorldData.Settings CustomStruct".to_string())
+}
+
+fn settings_struct_mut(
+    properties: &mut HashableIndexMap<String, Property>,
+) -> Result<&mut HashableIndexMap<String, Vec<Property>>, String> {
+    let option_world_data = properties
+        .get_mut("OptionWorldData")
+        .and_then(custom_struct_mut)
+        .ok_or_else(|| "missing OptionWorldData CustomStruct".to_string())?;
+    option_world_data
+        .get_mut("Settings")
+        .and_then(|values| values.first_mut())
+        .and_then(custom_struct_mut)
+        .ok_or_else(|| "missing OptionWorldData.Settings CustomStruct".to_string())
+}
+
+fn custom_struct_mut(
+    property: &mut Property,
+) -> Option<&mut HashableIndexMap<String, Vec<Property>>> {
+    match property {
+        Property::StructProperty(value) => match &mut value.value {
+            StructPropertyValue::CustomStruct(properties) => Some(properties),
+            _ => None,
+        },
+        Property::StructPropertyValue(StructPropertyValue::CustomStruct(properties)) => {
+            Some(properties)
+        }
+        _ => None,
+    }
+}
+
+fn property_to_value(property: &Property) -> Option<WorldSettingValue> {
+    let value = match property {
+        Property::BoolProperty(value) => WorldSettingValue::Bool(value.value),
+        Property::IntProperty(value) => WorldSettingValue::Int(i64::from(value.value)),
+        Property::Int64Property(value) => WorldSettingValue::Int(value.value),
+        Property::FloatProperty(value) => WorldSettingValue::Float(f64::from(value.value.0)),
+        Property::DoubleProperty(value) => WorldSettingValue::Float(value.value.0),
+        Property::StrProperty(value) => {
+            WorldSettingValue::Text(value.value.clone().unwrap_or_default())
+        }
+        Property::NameProperty(value) => {
+            WorldSettingValue::Text(value.value.clone().unwrap_or_default())
+        }
+        Property::EnumProperty(value) => {
+            WorldSettingValue::Text(strip_enum_prefix(&value.value).to_string())
+        }
+        Property::ByteProperty(value) => match &value.value {
+            BytePropertyValue::Namespaced(name) => {
+                WorldSettingValue::Text(strip_enum_prefix(name).to_string())
+            }
+            BytePropertyValue::Byte(byte) => WorldSettingValue::Int(i64::from(*byte)),
+        },
+        Property::ArrayProperty(ArrayProperty::Enums { enums }) => WorldSettingValue::List(
+            enums
+                .iter()
+                .map(|value| strip_enum_prefix(value).to_string())
+                .collect(),
+        ),
+        Property::ArrayProperty(ArrayProperty::Strings { strings }) => {
+            WorldSettingValue::List(strings.iter().flatten().cloned().collect())
+        }
+        Property::ArrayProperty(ArrayProperty::Names { names }) => {
+            WorldSettingValue::List(names.iter().flatten().cloned().collect())
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn apply_value(
+    name: &str,
+    property: &mut Property,
+    value: &WorldSettingValue,
+) -> Result<(), String> {
+    let mismatch = || format!("world setting {} cannot hold {:?}", name, value);
+    match (property, value) {
+        (Property::BoolProperty(property), WorldSettingValue::Bool(value)) => {
+            property.value = *value;
+        }
+        (Property::IntProperty(property), WorldSettingValue::Int(value)) => {
+            *property = IntProperty::new(
+                i32::try_from(*value)
+                    .map_err(|_| format!("world setting {} is out of i32 range", name))?,
+            );
+        }
+        (Property::Int64Property(property), WorldSettingValue::Int(value)) => {
+            *property = Int64Property::new(*value);
+        }
+        (Property::FloatProperty(property), WorldSettingValue::Float(value)) => {
+            *property = FloatProperty::new(*value as f32);
+        }
+        (Property::FloatProperty(property), WorldSettingValue::Int(value)) => {
+            *property = FloatProperty::new(*value as f32);
+        }
+        (Property::DoubleProperty(property), WorldSettingValue::Float(value)) => {
+            *property = DoubleProperty::new(*value);
+        }
+        (Property::DoubleProperty(property), WorldSettingValue::Int(value)) => {
+            *property = DoubleProperty::new(*value as f64);
+        }
+        (Property::StrProperty(property), WorldSettingValue::Text(value)) => {
+            property.value = Some(value.clone());
+        }
+        (Property::NameProperty(property), WorldSettingValue::Text(value)) => {
+            property.value = Some(value.clone());
+        }
+        (Property::EnumProperty(property), WorldSettingValue::Text(value)) => {
+            let prefix = enum_prefix(&property.value)
+                .map(str::to_string)
+                .or_else(|| property.enum_type.clone());
+            property.value = with_enum_prefix(prefix.as_deref(), value);
+        }
+        (Property::ByteProperty(property), WorldSettingValue::Text(value)) => {
+            let BytePropertyValue::Namespaced(current) = &property.value else {
+                return Err(mismatch());
+            };
+            let prefix = enum_prefix(current).map(str::to_string);
+            property.value =
+                BytePropertyValue::Namespaced(with_enum_prefix(prefix.as_deref(), value));
+        }
+        (
+            Property::ArrayProperty(ArrayProperty::Enums { enums }),
+            WorldSettingValue::List(values),
+        ) => {
+            let prefix = enums
+                .first()
+                .and_then(|current| enum_prefix(current))
+                .map(str::to_string)
+                .or_else(|| match world_setting_spec(name).map(|spec| spec.kind) {
+                    Some(WorldSettingKind::EnumList(enum_type)) => Some(enum_type.to_string()),
+                    _ => None,
+                });
+            *enums = values
+                .iter()
+                .map(|value| with_enum_prefix(prefix.as_deref(), value))
+                .collect();
+        }
+        (
+            Property::ArrayProperty(ArrayProperty::Strings { strings }),
+            WorldSettingValue::List(values),
+        ) => {
+            *strings = values.iter().cloned().map(Some).collect();
+        }
+        (
+            Property::ArrayProperty(ArrayProperty::Names { names }),
+            WorldSettingValue::List(values),
+        ) => {
+            *names = values.iter().cloned().map(Some).collect();
+        }
+        _ => return Err(mismatch()),
+    }
+    Ok(())
+}
+
+/// Builds a property for a catalog setting the source file does not contain.
+fn new_property(name: &str, value: &WorldSettingValue) -> Result<Property, String> {
+    let spec =
+        world_setting_spec(name).ok_or_else(|| format!("unknown world setting: {}", name))?;
+    let mismatch = || format!("world setting {} cannot hold {:?}", name, value);
+    let property = match (spec.kind, value) {
+        (WorldSettingKind::Bool, WorldSettingValue::Bool(value)) => {
+            Property::BoolProperty(BoolProperty::new(*value))
+        }
+        (WorldSettingKind::Int, WorldSettingValue::Int(value)) => {
+            Property::IntProperty(IntProperty::new(
+                i32::try_from(*value)
+                    .map_err(|_| format!("world setting {} is out of i32 range", name))?,
+            ))
+        }
+        (WorldSettingKind::Float, WorldSettingValue::Float(value)) => {
+            Property::FloatProperty(FloatProperty::new(*value as f32))
+        }
+        (WorldSettingKind::Text, WorldSettingValue::Text(value)) => {
+            Property::StrProperty(StrProperty::new(Some(value.clone())))
+        }
+        (WorldSettingKind::TextList, WorldSettingValue::List(values)) => {
+            Property::ArrayProperty(ArrayProperty::Strings {
+                strings: values.iter().cloned().map(Some).collect(),
+            })
+        }
+        (WorldSettingKind::Enum(enum_type), WorldSettingValue::Text(value)) => {
+            Property::EnumProperty(EnumProperty::new(
+                Some(enum_type.to_string()),
+                with_enum_prefix(Some(enum_type), value),
+            ))
+        }
+        (WorldSettingKind::EnumList(enum_type), WorldSettingValue::List(values)) => {
+            Property::ArrayProperty(ArrayProperty::Enums {
+                enums: values
+                    .iter()
+                    .map(|value| with_enum_prefix(Some(enum_type), value))
+                    .collect(),
+            })
+        }
+        _ => return Err(mismatch()),
+    };
+    Ok(property)
+}
+
+fn coerce_value(
+    spec: &WorldSettingSpec,
+    value: &WorldSettingValue,
+) -> Result<WorldSettingValue, String> {
+    let mismatch = || format!("world setting {} cannot hold {:?}", spec.name, value);
+    let coerced = match (spec.kind, value) {
+        (WorldSettingKind::Bool, WorldSettingValue::Bool(value)) => WorldSettingValue::Bool(*value),
+        (WorldSettingKind::Int, WorldSettingValue::Int(value)) => WorldSettingValue::Int(*value),
+        (WorldSettingKind::Int, WorldSettingValue::Float(value)) if value.fract() == 0.0 => {
+            WorldSettingValue::Int(*value as i64)
+        }
+        (WorldSettingKind::Float, WorldSettingValue::Float(value)) => {
+            WorldSettingValue::Float(*value)
+        }
+        (WorldSettingKind::Float, WorldSettingValue::Int(value)) => {
+            WorldSettingValue::Float(*value as f64)
+        }
+        (WorldSettingKind::Text, WorldSettingValue::Text(value)) => {
+            WorldSettingValue::Text(value.clone())
+        }
+        (WorldSettingKind::Text, WorldSettingValue::Int(value)) => {
+            WorldSettingValue::Text(value.to_string())
+        }
+        (WorldSettingKind::Enum(_), WorldSettingValue::Text(value)) => {
+            WorldSettingValue::Text(strip_enum_prefix(value).to_string())
+        }
+        (WorldSettingKind::TextList, WorldSettingValue::List(values)) => {
+            WorldSettingValue::List(values.clone())
+        }
+        (WorldSettingKind::EnumList(_), WorldSettingValue::List(values)) => {
+            WorldSettingValue::List(
+                values
+                    .iter()
+                    .map(|value| strip_enum_prefix(value).to_string())
+                    .collect(),
+            )
+        }
+        _ => return Err(mismatch()),
+    };
+
+    let numeric = match &coerced {
+        WorldSettingValue::Int(value) => Some(*value as f64),
+        WorldSettingValue::Float(value) => Some(*value),
+        _ => None,
+    };
+    if let Some(numeric) = numeric {
+        if !numeric.is_finite() || numeric < 0.0 {
+            return Err(format!(
+                "world setting {} must be a finite non-negative number",
+                spec.name
+            ));
+        }
+        if let Some((min, max)) = spec.bounds
+            && (numeric < min || numeric > max)
+        {
+            return Err(format!(
+                "world setting {} must be between {} and {}",
+                spec.name, min, max
+            ));
+        }
+    }
+    Ok(coerced)
+}
+
+fn same_shape(current: &WorldSettingValue, value: &WorldSettingValue) -> bool {
+    std::mem::discriminant(current) == std::mem::discriminant(value)
+        || matches!(
+            (current, value),
+            (WorldSettingValue::Float(_), WorldSettingValue::Int(_))
+        )
+}
+
+fn strip_enum_prefix(value: &str) -> &str {
+    value.rsplit_once("::").map_or(value, |(_, name)| name)
+}
+
+fn enum_prefix(value: &str) -> Option<&str> {
+    value.rsplit_once("::").map(|(prefix, _)| prefix)
+}
+
+fn with_enum_prefix(prefix: Option<&str>, value: &str) -> String {
+    let value = strip_enum_prefix(value);
+    match prefix {
+        Some(prefix) => format!("{}::{}", prefix, value),
+        None => value.to_string(),
+    }
+}
+
+/// Splits on commas outside quotes and parentheses.
+fn split_top_level(text: &str) -> Result<Vec<&str>, String> {
+    let mut fields = Vec::new();
+    let mut depth = 0usize;
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut start = 0usize;
+    for (index, value) in text.char_indices() {
+        if in_quotes {
+            match value {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_quotes = false,
+                _ => {}
+            }
+            continue;
+        }
+        match value {
+            '"' => in_quotes = true,
+            '(' => depth += 1,
+            ')' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| "unbalanced ')' in OptionSettings".to_string())?;
+            }
+            ',' if depth == 0 => {
+                fields.push(&text[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    if in_quotes || depth != 0 {
+        return Err("unterminated quote or parenthesis in OptionSettings".to_string());
+    }
+    fields.push(&text[start..]);
+    Ok(fields)
+}
+
+fn parse_ini_literal(literal: &str) -> Result<WorldSettingValue, String> {
+    if let Some(inner) = literal
+        .strip_prefix('(')
+        .and_then(|value| value.strip_suffix(')'))
+    {
+        let items = split_top_level(inner)?
+            .into_iter()
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(unquote)
+            .collect();
+        return Ok(WorldSettingValue::List(items));
+    }
+    if literal.starts_with('"') {
+        return Ok(WorldSettingValue::Text(unquote(literal)));
+    }
+    if literal.eq_ignore_ascii_case("true") {
+        return Ok(WorldSettingValue::Bool(true));
+    }
+    if literal.eq_ignore_ascii_case("false") {
+        return Ok(WorldSettingValue::Bool(false));
+    }
+    if let Ok(value) = literal.parse::<i64>() {
+        return Ok(WorldSettingValue::Int(value));
+    }
+    if let Ok(value) = literal.parse::<f64>() {
+        return Ok(WorldSettingValue::Float(value));
+    }
+    Ok(WorldSettingValue::Text(literal.to_string()))
+}
+
+fn unquote(literal: &str) -> String {
+    match literal
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        Some(inner) => inner.replace("\\\"", "\"").replace("\\\\", "\\"),
+        None => literal.to_string(),
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn format_ini_literal(kind: Option<WorldSettingKind>, value: &WorldSettingValue) -> String {
+    match value {
+        WorldSettingValue::Bool(true) => "True".to_string(),
+        WorldSettingValue::Bool(false) => "False".to_string(),
+        WorldSettingValue::Int(value) => value.to_string(),
+        WorldSettingValue::Float(value) => format!("{:.6}", value),
+        WorldSettingValue::Text(value) => match kind {
+            Some(WorldSettingKind::Enum(_)) => value.clone(),
+            _ => quote(value),
+        },
+        WorldSettingValue::List(values) => {
+            let items: Vec<String> = match kind {
+                Some(WorldSettingKind::EnumList(_)) => values.clone(),
+                _ => values.iter().map(|value| quote(value)).collect(),
+            };
+            format!("({})", items.join(","))
+        }
+    }
+}
@@ -1,11 +1,14 @@
 import { useEffect, useMemo, useRef, useState } from "react";
-import { DatabaseZap, RefreshCw, UploadCloud } from "lucide-react";
+import { Ban, DatabaseZap, RefreshCw, RotateCcw, UploadCloud } from "lucide-react";
 import { Button } from "@/components/ui/button";
 import {
+  cancelImportVersion,
   getImportVersion,
   getNormalized,
   importZip,
   listImportVersions,
+  listWorlds,
+  reprocessImportVersion,
   subscribeImportProgress,
 } from "@/lib/api";
 import type {
@@ -13,6 +16,7 @@ import type {
   ImportVersionDetailResponse,
   ImportVersionListItem,
   NormalizedResponse,
+  WorldSummary,
 } from "@/lib/types";
 
 interface PhaseProgress {
@@ -22,8 +26,19 @@ interface PhaseProgress {
   status: "uploading" | "processing" | "ready" | "failed";
 }
 
+function importVersionLabel(version: ImportVersionListItem): string {
+  if (!version.world_name) {
+    return version.source_file_name;
+  }
+  return version.in_game_day === null
+    ? version.world_name
+    : `${version.world_name}, day ${version.in_game_day}`;
+}
+
 function App() {
   const [versions, setVersions] = useState<ImportVersionListItem[]>([]);
+  const [worlds, setWorlds] = useState<WorldSummary[]>([]);
+  const [worldFilter, setWorldFilter] = useState<string>("");
   const [selectedId, setSelectedId] = useState<string | null>(null);
   const [detail, setDetail] = useState<ImportVersionDetailResponse | null>(null);
   const [normalized, setNormalized] = useState<NormalizedResponse | null>(null);
@@ -43,8 +58,12 @@ function App() {
     setLoading(true);
     setError(null);
     try {
-      const response = await listImportVersions();
+      const [response, worldsResponse] = await Promise.all([
+        listImportVersions(worldFilter || undefined),
+        listWorlds(),
+      ]);
       setVersions(response.versions);
+      setWorlds(worldsResponse.worlds);
       if (!selectedId && response.versions.length > 0) {
         setSelectedId(response.versions[0].id);
       }
@@ -77,6 +96,130 @@ function App() {
     }
   }
 
+  function watchImportProgress(importVersionId: string) {
+    closeProgressStreamRef.current?.();
+    closeProgressStreamRef.current = subscribeImportProgress(
+      importVersionId,
+      (event: ImportProgressEvent) => {
+        const mappedPct = event.status === "ready" ? 100 : event.progress_pct;
+
+        setPhaseProgress({
+          phase: event.progress_phase,
+          pct: mappedPct,
+          message: event.progress_message,
+          status:
+            event.status === "failed"
+              ? "failed"
+              : event.status === "ready"
+                ? "ready"
+                : "processing",
+        });
+
+        setVersions((previous) =>
+          previous.map((version) =>
+            version.id === event.import_version_id
+              ? {
+                  ...version,
+                  status: event.status,
+                  progress_phase: event.progress_phase,
+                  progress_pct: event.progress_pct,
+                  progress_message: event.progress_message,
+                  failed_error: event.failed_error,
+                  parse_metrics_json: event.parse_metrics_json,
+                  player_count: event.player_count,
+                  pal_count: event.pal_count,
+                  base_assignment_count: event.base_assignment_count,
+                  completed_at:
+                    event.status === "ready" || event.status === "failed"
+                      ? (event.completed_at ?? version.completed_at)
+                      : version.completed_at,
+                }
+              : version,
+          ),
+        );
+
+        if (selectedId === event.import_version_id) {
+          setDetail((previous) => {
+            if (!previous || previous.version.id !== event.import_version_id) {
+              return previous;
+            }
+            return {
+              ...previous,
+              version: {
+                ...previous.version,
+                status: event.status,
+                progress_phase: event.progress_phase,
+                progress_pct: event.progress_pct,
+                progress_message: event.progress_message,
+                failed_error: event.failed_error,
+                parse_metrics_json: event.parse_metrics_json,
+                player_count: event.player_count,
+                pal_count: event.pal_count,
+                base_assignment_count: event.base_assignment_count,
+                completed_at:
+                  event.status === "ready" || event.status === "failed"
+                    ? (event.completed_at ?? previous.version.completed_at)
+                    : previous.version.completed_at,
+              },
+            };
+          });
+        }
+
+        if (event.status === "ready" || event.status === "failed") {
+          closeProgressStreamRef.current?.();
+          closeProgressStreamRef.current = null;
+          void refreshVersions();
+          void loadDetail(event.import_version_id);
+          if (event.status === "failed") {
+            setError(event.failed_error ?? "import failed");
+          }
+          setUploading(false);
+        }
+      },
+      (message: string) => {
+        setError(message);
+        setUploading(false);
+        closeProgressStreamRef.current?.();
+        closeProgressStreamRef.current = null;
+      },
+    );
+  }
+
+  async function cancelSelectedImport() {
+    if (!selectedId) {
+      return;
+    }
+    setError(null);
+    try {
+      await cancelImportVersion(selectedId);
+      await refreshVersions();
+      await loadDetail(selectedId);
+    } catch (requestError) {
+      setError((requestError as Error).message);
+    }
+  }
+
+  async function reprocessSelectedImport() {
+    if (!selectedId) {
+      return;
+    }
+    setError(null);
+    try {
+      const event = await reprocessImportVersion(selectedId);
+      setUploading(true);
+      setPhaseProgress({
+        phase: event.progress_phase,
+        pct: event.progress_pct,
+        message: event.progress_message,
+        status: "processing",
+      });
+      await refreshVersions();
+      watchImportProgress(selectedId);
+    } catch (requestError) {
+      setError((requestError as Error).message);
+    }
+  }
+
   async function uploadSelectedZip() {
     if (!selectedFile) {
       return;
@@ -116,91 +259,7 @@ function App() {
       setSelectedId(response.import_version_id);
       setSelectedFile(null);
 
-      closeProgressStreamRef.current = subscribeImportProgress(
-        response.import_version_id,
-        (event: ImportProgressEvent) => {
-          const mappedPct = event.status === "ready" ? 100 : event.progress_pct;
-
-          setPhaseProgress({
-            phase: event.progress_phase,
-            pct: mappedPct,
-            message: event.progress_message,
-            status:
-              event.status === "failed"
-                ? "failed"
-                : event.status === "ready"
-                  ? "ready"
-                  : "processing",
-          });
-
-          setVersions((previous) =>
-            previous.map((version) =>
-              version.id === event.import_version_id
-                ? {
-                    ...version,
-                    status: event.status,
-                    progress_phase: event.progress_phase,
-                    progress_pct: event.progress_pct,
-                    progress_message: event.progress_message,
-                    failed_error: event.failed_error,
-                    parse_metrics_json: event.parse_metrics_json,
-                    player_count: event.player_count,
-                    pal_count: event.pal_count,
-                    base_assignment_count: event.base_assignment_count,
-                    completed_at:
-                      event.status === "ready" || event.status === "failed"
-                        ? (event.completed_at ?? version.completed_at)
-                        : version.completed_at,
-                  }
-                : version,
-            ),
-          );
-
-          if (selectedId === event.import_version_id) {
-            setDetail((previous) => {
-              if (!previous || previous.version.id !== event.import_version_id) {
-                return previous;
-              }
-              return {
-                ...previous,
-                version: {
-                  ...previous.version,
-                  status: event.status,
-                  progress_phase: event.progress_phase,
-                  progress_pct: event.progress_pct,
-                  progress_message: event.progress_message,
-                  failed_error: event.failed_error,
-                  parse_metrics_json: event.parse_metrics_json,
-                  player_count: event.player_count,
-                  pal_count: event.pal_count,
-                  base_assignment_count: event.base_assignment_count,
-                  completed_at:
-                    event.status === "ready" || event.status === "failed"
-                      ? (event.completed_at ?? previous.version.completed_at)
-                      : previous.version.completed_at,
-                },
-              };
-            });
-          }
-
-          if (event.status === "ready" || event.status === "failed") {
-            closeProgressStreamRef.current?.();
-            closeProgressStreamRef.current = null;
-            void refreshVersions();
-            void loadDetail(event.import_version_id);
-            if (event.status === "failed") {
-              setError(event.failed_error ?? "import failed");
-            }
-            setUploading(false);
-          }
-        },
-        (message: string) => {
-          setError(message);
-          setUploading(false);
-          closeProgressStreamRef.current?.();
-          closeProgressStreamRef.current = null;
-        },
-      );
+      watchImportProgress(response.import_version_id);
     } catch (requestError) {
       setError((requestError as Error).message);
       setPhaseProgress({
@@ -221,7 +280,7 @@ function App() {
 
   useEffect(() => {
     void refreshVersions();
-  }, []);
+  }, [worldFilter]);
 
   useEffect(() => {
     return () => {
@@ -257,7 +316,7 @@ function App() {
           <input
             className="rounded-md border bg-background px-2 py-1 text-sm"
             type="file"
-            accept=".zip,application/zip"
+            accept=".zip,.tar,.tar.gz,.tgz,.7z,application/zip,application/x-tar,application/gzip,application/x-7z-compressed"
             onChange={(event) => setSelectedFile(event.target.files?.[0] ?? null)}
           />
           <Button onClick={() => void uploadSelectedZip()} disabled={!selectedFile || uploading}>
@@ -299,6 +358,18 @@ function App() {
       <section className="grid gap-4 lg:grid-cols-[320px_1fr]">
         <aside className="rounded-lg border bg-card p-4">
           <h2 className="mb-3 text-sm font-semibold">Import Versions</h2>
+          <select
+            className="mb-3 w-full rounded-md border bg-background px-2 py-1 text-sm"
+            value={worldFilter}
+            onChange={(event) => setWorldFilter(event.target.value)}
+          >
+            <option value="">All worlds</option>
+            {worlds.map((world) => (
+              <option key={world.id} value={world.id}>
+                {world.display_name} ({world.import_count})
+              </option>
+            ))}
+          </select>
           <div className="space-y-2">
             {versions.length === 0 && (
               <p className="text-sm text-muted-foreground">No imports yet.</p>
@@ -314,7 +385,7 @@ function App() {
                 }`}
                 onClick={() => setSelectedId(version.id)}
               >
-                <div className="font-medium">{version.source_file_name}</div>
+                <div className="font-medium">{importVersionLabel(version)}</div>
                 <div className="text-xs text-muted-foreground">
                   status={version.status} phase={version.progress_phase} progress={version.progress_pct}% files={version.file_count} variants={version.variant_row_count}
                 </div>
@@ -333,10 +404,40 @@ function App() {
           {selectedVersion && detail && (
             <>
               <article className="rounded-lg border bg-card p-4">
-                <h2 className="mb-3 text-sm font-semibold">Import Summary</h2>
+                <div className="mb-3 flex items-center justify-between gap-2">
+                  <h2 className="text-sm font-semibold">Import Summary</h2>
+                  <div className="flex gap-2">
+                    <Button
+                      variant="outline"
+                      size="sm"
+                      onClick={() => void cancelSelectedImport()}
+                      disabled={detail.version.status !== "processing"}
+                    >
+                      <Ban className="mr-2 h-4 w-4" />
+                      Cancel
+                    </Button>
+                    <Button
+                      variant="outline"
+                      size="sm"
+                      onClick={() => void reprocessSelectedImport()}
+                      disabled={detail.version.status === "processing"}
+                    >
+                      <RotateCcw className="mr-2 h-4 w-4" />
+                      Reprocess
+                    </Button>
+                  </div>
+                </div>
                 <div className="grid gap-2 text-sm md:grid-cols-2">
                   <SummaryRow label="ID" value={detail.version.id} />
                   <SummaryRow label="Status" value={detail.version.status} />
+                  <SummaryRow
+                    label="Source"
+                    value={
+                      detail.version.source_path
+                        ? `${detail.version.source_kind} (${detail.version.source_path})`
+                        : detail.version.source_kind
+                    }
+                  />
                   <SummaryRow
                     label="Progress"
                     value={`${detail.version.progress_phase} (${detail.version.progress_pct}%)`}
@@ -399,12 +500,31 @@ function App() {
             <article className="rounded-lg border bg-card p-4">
               <h2 className="mb-3 text-sm font-semibold">Normalized Rows</h2>
               <p className="mb-3 text-xs text-muted-foreground">
-                players={normalized.players.length} pals={normalized.pals.length} assignments=
-                {normalized.base_assignments.length}
+                bases={normalized.bases.length} guilds={normalized.guilds.length} players={normalized.players.length} pals=
+                {normalized.pals.length} assignments={normalized.base_assignments.length}
               </p>
-              <div className="grid gap-3 md:grid-cols-3">
+              <div className="grid gap-3 md:grid-cols-5">
+                <SmallList
+                  title="Bases"
+                  items={normalized.bases.map(
+                    (row) => `${row.base_name ?? row.base_id} (${row.worker_count} workers)`,
+                  )}
+                />
+                <SmallList
+                  title="Guilds"
+                  items={normalized.guilds.map(
+                    (row) =>
+                      `${row.guild_name ?? row.group_id} (${row.members.length} members, ${row.base_ids.length} bases)`,
+                  )}
+                />
                 <SmallList title="Players" items={normalized.players.map((row) => row.player_uid)} />
-                <SmallList title="Pals" items={normalized.pals.map((row) => row.pal_instance_id)} />
+                <SmallList
+                  title="Pals"
+                  items={normalized.pals.map(
+                    (row) =>
+                      `${row.species?.display_name ?? row.species_id ?? "unknown"} ${row.pal_instance_id} (${row.location_kind}, ${row.selection_reason ?? "unrecorded"})`,
+                  )}
+                />
                 <SmallList
                   title="Assignments"
                   items={normalized.base_assignments.map((row) => `${row.base_id}:${row.pal_instance_id}`)}
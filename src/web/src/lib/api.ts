@@ -1,10 +1,32 @@
 import type {
+  BaseProductionResponse,
+  BreedingPair,
+  BreedingParentsResponse,
+  BreedingPathResponse,
+  CatalogPalsResponse,
+  CatalogPassivesResponse,
+  CatalogSkillsResponse,
+  CharacterSelectionPolicy,
   HealthResponse,
+  ImportDiffResponse,
   ImportProgressEvent,
   ImportVersionDetailResponse,
   ImportVersionListResponse,
   ImportZipResponse,
   NormalizedResponse,
+  PalHistoryResponse,
+  PalStatsResponse,
+  PassiveInheritanceRequest,
+  PassiveInheritanceResponse,
+  PlannedPatchOperation,
+  SolveRequest,
+  SolveResponse,
+  ValidatePatchResponse,
+  WorldDetailResponse,
+  WorldListResponse,
+  WorldOptionResponse,
+  WorldSettings,
+  WorldSummary,
 } from "@/lib/types";
 
 async function getJson<T>(path: string): Promise<T> {
@@ -23,8 +45,70 @@ export function getReady(): Promise<HealthResponse> {
   return getJson<HealthResponse>("/ready");
 }
 
-export function listImportVersions(): Promise<ImportVersionListResponse> {
-  return getJson<ImportVersionListResponse>("/v1/save/import-versions");
+export function listCatalogPals(): Promise<CatalogPalsResponse> {
+  return getJson<CatalogPalsResponse>("/v1/catalog/pals");
+}
+
+export function listCatalogPassives(): Promise<CatalogPassivesResponse> {
+  return getJson<CatalogPassivesResponse>("/v1/catalog/passives");
+}
+
+export function listCatalogSkills(): Promise<CatalogSkillsResponse> {
+  return getJson<CatalogSkillsResponse>("/v1/catalog/skills");
+}
+
+export function listImportVersions(worldId?: string): Promise<ImportVersionListResponse> {
+  const query = worldId ? `?world_id=${encodeURIComponent(worldId)}` : "";
+  return getJson<ImportVersionListResponse>(`/v1/save/import-versions${query}`);
+}
+
+export function getBaseProduction(importVersionId: string, baseId: string): Promise<BaseProductionResponse> {
+  return getJson<BaseProductionResponse>(
+    `/v1/save/import-versions/${importVersionId}/bases/${encodeURIComponent(baseId)}/production`,
+  );
+}
+
+export function getPalStats(importVersionId: string, ownerPlayerUid?: string): Promise<PalStatsResponse> {
+  const query = ownerPlayerUid ? `?owner_player_uid=${encodeURIComponent(ownerPlayerUid)}` : "";
+  return getJson<PalStatsResponse>(`/v1/save/import-versions/${importVersionId}/pal-stats${query}`);
+}
+
+export function getBreedingOffspring(parentA: string, parentB: string): Promise<BreedingPair> {
+  const query = `parent_a=${encodeURIComponent(parentA)}&parent_b=${encodeURIComponent(parentB)}`;
+  return getJson<BreedingPair>(`/v1/breeding/offspring?${query}`);
+}
+
+export function getBreedingParents(speciesId: string): Promise<BreedingParentsResponse> {
+  return getJson<BreedingParentsResponse>(`/v1/breeding/parents/${encodeURIComponent(speciesId)}`);
+}
+
+export function getPassiveInheritance(request: PassiveInheritanceRequest): Promise<PassiveInheritanceResponse> {
+  return sendJson<PassiveInheritanceResponse>("POST", "/v1/breeding/passive-inheritance", request);
+}
+
+export function getBreedingPath(
+  importVersionId: string,
+  playerUid: string,
+  target: string,
+): Promise<BreedingPathResponse> {
+  return getJson<BreedingPathResponse>(
+    `/v1/save/import-versions/${importVersionId}/players/${encodeURIComponent(playerUid)}/breeding-path?target=${encodeURIComponent(target)}`,
+  );
+}
+
+export function solvePlanner(request: SolveRequest): Promise<SolveResponse> {
+  return sendJson<SolveResponse>("POST", "/v1/planner/solve", request);
+}
+
+export function validatePatchOperations(
+  importVersionId: string,
+  operations: PlannedPatchOperation[],
+): Promise<ValidatePatchResponse> {
+  return sendJson<ValidatePatchResponse>(
+    "POST",
+    `/v1/save/import-versions/${importVersionId}/patch-operations/validate`,
+    { operations },
+  );
 }
 
 export function getImportVersion(id: string): Promise<ImportVersionDetailResponse> {
@@ -35,6 +119,77 @@ export function getNormalized(id: string): Promise<NormalizedResponse> {
   return getJson<NormalizedResponse>(`/v1/save/import-versions/${id}/normalized`);
 }
 
+export function diffImportVersions(fromId: string, toId: string): Promise<ImportDiffResponse> {
+  return getJson<ImportDiffResponse>(`/v1/save/import-versions/${fromId}/diff/${toId}`);
+}
+
+export function listWorlds(includeArchived = false): Promise<WorldListResponse> {
+  const query = includeArchived ? "?include_archived=true" : "";
+  return getJson<WorldListResponse>(`/v1/worlds${query}`);
+}
+
+export function getWorld(worldId: string): Promise<WorldDetailResponse> {
+  return getJson<WorldDetailResponse>(`/v1/worlds/${worldId}`);
+}
+
+export function createWorld(displayName: string, worldKey?: string): Promise<WorldSummary> {
+  return sendJson<WorldSummary>("POST", "/v1/worlds", {
+    display_name: displayName,
+    world_key: worldKey ?? null,
+  });
+}
+
+export function updateWorld(
+  worldId: string,
+  changes: { display_name?: string; archived?: boolean },
+): Promise<WorldSummary> {
+  return sendJson<WorldSummary>("PATCH", `/v1/worlds/${worldId}`, changes);
+}
+
+export function assignImportWorld(
+  importVersionId: string,
+  worldId: string,
+): Promise<{ import_version_id: string; world_id: string }> {
+  return sendJson("PUT", `/v1/save/import-versions/${importVersionId}/world`, {
+    world_id: worldId,
+  });
+}
+
+export function getPalHistory(worldId: string, palInstanceId: string): Promise<PalHistoryResponse> {
+  return getJson<PalHistoryResponse>(`/v1/worlds/${worldId}/pals/${palInstanceId}/history`);
+}
+
+export function getWorldOption(importVersionId: string): Promise<WorldOptionResponse> {
+  return getJson<WorldOptionResponse>(`/v1/save/import-versions/${importVersionId}/world-option`);
+}
+
+export async function rebuildWorldOption(
+  importVersionId: string,
+  changes: { settings?: WorldSettings; option_settings_ini?: string },
+): Promise<{ source: "rebuilt" | "synthesized"; file: Blob }> {
+  const path = `/v1/save/import-versions/${importVersionId}/world-option/rebuild`;
+  const response = await sendRequest(path, "POST", "application/json", JSON.stringify(changes));
+  const source =
+    response.headers.get("x-world-option-source") === "synthesized" ? "synthesized" : "rebuilt";
+  return { source, file: await response.blob() };
+}
+
+export async function worldSettingsFromIni(iniText: string): Promise<WorldSettings> {
+  const response = await sendRequest("/v1/world-options/from-ini", "POST", "text/plain", iniText);
+  const payload = (await response.json()) as { settings: WorldSettings };
+  return payload.settings;
+}
+
+export async function worldSettingsToIni(settings: WorldSettings): Promise<string> {
+  const response = await sendRequest(
+    "/v1/world-options/to-ini",
+    "POST",
+    "application/json",
+    JSON.stringify({ settings }),
+  );
+  return response.text();
+}
+
 export function importZip(
   file: File,
   onUploadProgress: (progressPct: number) => void,
@@ -74,6 +229,73 @@ export function importZip(
   });
 }
 
+export async function importDirectory(path: string): Promise<ImportZipResponse> {
+  const response = await fetch("/api/v1/save/import-directory", {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify({ path }),
+  });
+  if (!response.ok) {
+    throw new Error(`request failed (${response.status}): /v1/save/import-directory`);
+  }
+  return (await response.json()) as ImportZipResponse;
+}
+
+async function postJson<T>(path: string): Promise<T> {
+  const response = await fetch(`/api${path}`, { method: "POST" });
+  if (!response.ok) {
+    const payload = (await response.json().catch(() => null)) as { error?: string } | null;
+    throw new Error(payload?.error ?? `request failed (${response.status}): ${path}`);
+  }
+  return (await response.json()) as T;
+}
+
+async function sendJson<T>(method: string, path: string, body: unknown): Promise<T> {
+  const response = await fetch(`/api${path}`, {
+    method,
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify(body),
+  });
+  if (!response.ok) {
+    const payload = (await response.json().catch(() => null)) as { error?: string } | null;
+    throw new Error(payload?.error ?? `request failed (${response.status}): ${path}`);
+  }
+  return (await response.json()) as T;
+}
+
+async function sendRequest(
+  path: string,
+  method: string,
+  contentType: string,
+  body: string,
+): Promise<Response> {
+  const response = await fetch(`/api${path}`, {
+    method,
+    headers: { "Content-Type": contentType },
+    body,
+  });
+  if (!response.ok) {
+    const payload = (await response.json().catch(() => null)) as { error?: string } | null;
+    throw new Error(payload?.error ?? `request failed (${response.status}): ${path}`);
+  }
+  return response;
+}
+
+export function cancelImportVersion(id: string): Promise<ImportProgressEvent> {
+  return postJson<ImportProgressEvent>(`/v1/save/import-versions/${id}/cancel`);
+}
+
+export function reprocessImportVersion(
+  id: string,
+  selectionPolicy?: CharacterSelectionPolicy,
+): Promise<ImportProgressEvent> {
+  const path = `/v1/save/import-versions/${id}/reprocess`;
+  if (selectionPolicy === undefined) {
+    return postJson<ImportProgressEvent>(path);
+  }
+  return sendJson<ImportProgressEvent>("POST", path, { selection_policy: selectionPolicy });
+}
+
 export function subscribeImportProgress(
   importVersionId: string,
   onProgress: (event: ImportProgressEvent) => void,
@@ -10,18 +10,43 @@ export interface NormalizedSummary {
   base_assignment_count: number;
 }
 
+export type CharacterSelectionPolicy = "planner" | "owned" | "all";
+
+export type ArchiveFormat = "zip" | "tar" | "tar_gz" | "seven_zip" | "directory";
+
+export type ImportSourceKind = "upload" | "directory" | "watch";
+
 export interface ImportZipResponse {
   import_version_id: string;
+  world_id: string;
   world_root_path: string;
+  archive_format: ArchiveFormat;
   persisted_file_count: number;
   supported_file_count: number;
+  skipped_entries: SkippedZipEntry[];
   normalized_summary: NormalizedSummary;
 }
 
+export interface SkippedZipEntry {
+  path: string;
+  reason: string;
+}
+
 export interface ImportVersionListItem {
   id: string;
   source_file_name: string;
+  source_kind: ImportSourceKind;
+  source_path: string | null;
+  world_id: string | null;
+  source_export_version_id: string | null;
   world_root_path: string;
+  world_name: string | null;
+  host_player_name: string | null;
+  host_player_level: number | null;
+  in_game_day: number | null;
+  save_game_version: number | null;
+  engine_version: string | null;
+  selection_policy: CharacterSelectionPolicy | null;
   status: string;
   progress_phase: string;
   progress_pct: number;
@@ -75,17 +100,46 @@ export interface NormalizedPlayerRow {
   player_name: string | null;
   guild_id: string | null;
   level: number | null;
+  selection_reason: string | null;
   raw_file_ref: string | null;
   raw_entity_path: string;
 }
 
+export type PalLocationKind =
+  | "party"
+  | "palbox"
+  | "base_worker"
+  | "dimensional_storage"
+  | "guild_storage"
+  | "unknown";
+
+export type CharacterVariant = "alpha" | "tower_boss" | "raid_boss" | "predator";
+
+export interface PalSpeciesInfo {
+  species_id: string;
+  display_name: string;
+  paldeck_no: string | null;
+  elements: string[];
+  work_suitability: Record<string, number>;
+  food: number | null;
+  variant: CharacterVariant | null;
+  is_alpha: boolean;
+  is_boss: boolean;
+}
+
 export interface NormalizedPalRow {
   id: string;
   pal_instance_id: string;
   owner_player_uid: string | null;
   species_id: string | null;
+  species: PalSpeciesInfo | null;
   nickname: string | null;
   level: number | null;
+  is_lucky: boolean;
+  location_kind: PalLocationKind;
+  container_id: string | null;
+  slot_index: number | null;
+  selection_reason: string | null;
   raw_file_ref: string | null;
   raw_entity_path: string;
 }
@@ -101,11 +155,59 @@ export interface NormalizedAssignmentRow {
   raw_entity_path: string;
 }
 
+export interface BaseTransform {
+  rotation: { x: number; y: number; z: number; w: number };
+  translation: { x: number; y: number; z: number };
+  scale: { x: number; y: number; z: number };
+}
+
+export interface NormalizedBaseRow {
+  id: string;
+  base_id: string;
+  base_name: string | null;
+  state: number | null;
+  area_range: number | null;
+  group_id: string | null;
+  worker_container_id: string | null;
+  worker_order_type: number | null;
+  location_x: number | null;
+  location_y: number | null;
+  location_z: number | null;
+  transform: BaseTransform | null;
+  worker_count: number;
+  raw_file_ref: string | null;
+  raw_entity_path: string;
+}
+
+export interface NormalizedGuildMemberRow {
+  id: string;
+  player_uid: string;
+  player_name: string | null;
+  last_online_ticks: number | null;
+  offline_seconds: number | null;
+  is_admin: boolean;
+}
+
+export interface NormalizedGuildRow {
+  id: string;
+  group_id: string;
+  group_type: string;
+  guild_name: string | null;
+  admin_player_uid: string | null;
+  base_camp_level: number | null;
+  base_ids: string[];
+  members: NormalizedGuildMemberRow[];
+  raw_file_ref: string | null;
+  raw_entity_path: string;
+}
+
 export interface NormalizedResponse {
   import_version_id: string;
   players: NormalizedPlayerRow[];
   pals: NormalizedPalRow[];
   base_assignments: NormalizedAssignmentRow[];
+  bases: NormalizedBaseRow[];
+  guilds: NormalizedGuildRow[];
 }
 
 export interface ImportProgressEvent {
@@ -133,5 +235,408 @@ export interface ParseMetrics {
   character_map_decoded: number;
   basecamp_count: number;
   container_count: number;
+  guild_count: number;
   disabled_property_skips: number;
 }
+
+export interface FieldChange<T> {
+  from: T;
+  to: T;
+}
+
+export interface ListChange {
+  added: string[];
+  removed: string[];
+}
+
+export interface DiffPlayerSnapshot {
+  player_uid: string;
+  player_name: string | null;
+  guild_id: string | null;
+  level: number | null;
+}
+
+export interface DiffPalSnapshot {
+  pal_instance_id: string;
+  owner_player_uid: string | null;
+  species_id: string | null;
+  nickname: string | null;
+  level: number | null;
+  exp: number | null;
+  passive_skill_ids: string[];
+  base_ids: string[];
+}
+
+export interface GuildMembershipChange {
+  player_uid: string;
+  player_name: string | null;
+  guild_id: FieldChange<string | null>;
+}
+
+export interface PalChange {
+  pal_instance_id: string;
+  species_id: string | null;
+  nickname: string | null;
+  level?: FieldChange<number | null>;
+  exp?: FieldChange<number | null>;
+  passive_skill_ids?: ListChange;
+  owner_player_uid?: FieldChange<string | null>;
+  base_ids?: ListChange;
+}
+
+export interface ImportDiffResponse {
+  from_import_version_id: string;
+  to_import_version_id: string;
+  summary: {
+    players_added: number;
+    players_removed: number;
+    guild_changes: number;
+    pals_added: number;
+    pals_removed: number;
+    pals_changed: number;
+  };
+  players: {
+    added: DiffPlayerSnapshot[];
+    removed: DiffPlayerSnapshot[];
+    guild_changes: GuildMembershipChange[];
+  };
+  pals: {
+    added: DiffPalSnapshot[];
+    removed: DiffPalSnapshot[];
+    changed: PalChange[];
+  };
+}
+
+export interface PalHistoryEntry {
+  import_version_id: string;
+  imported_at: string;
+  species_id: string | null;
+  nickname: string | null;
+  owner_player_uid: string | null;
+  level: number | null;
+  exp: number | null;
+  rank: number | null;
+  passive_skill_ids: string[];
+}
+
+export interface PalHistoryResponse {
+  world_id: string;
+  pal_instance_id: string;
+  entries: PalHistoryEntry[];
+}
+
+export interface WorldSummary {
+  id: string;
+  world_key: string | null;
+  display_name: string;
+  archived_at: string | null;
+  created_at: string;
+  updated_at: string;
+  import_count: number;
+  latest_import_version_id: string | null;
+  latest_import_at: string | null;
+  export_count: number;
+}
+
+export interface WorldListResponse {
+  worlds: WorldSummary[];
+}
+
+export interface WorldExportLineageItem {
+  export_version_id: string;
+  import_version_id: string;
+  patchset_id: string | null;
+  status: string;
+  created_at: string;
+  reimported_as: string[];
+}
+
+export interface WorldDetailResponse {
+  world: WorldSummary;
+  exports: WorldExportLineageItem[];
+}
+
+export type WorldSettingValue = boolean | number | string | string[];
+
+export type WorldSettings = Record<string, WorldSettingValue>;
+
+export interface WorldOptionResponse {
+  import_version_id: string;
+  settings: WorldSettings;
+  option_settings_ini: string;
+}
+
+export interface DatasetSource {
+  file: string;
+  sha256: string;
+}
+
+export interface CatalogPalStats {
+  hp: number;
+  defense: number;
+  stamina: number;
+  melee_attack: number;
+  ranged_attack: number;
+  support: number;
+  craft_speed: number;
+}
+
+export interface CatalogPal {
+  species_id: string;
+  name: string;
+  paldeck_no: string | null;
+  description: string | null;
+  rarity: number;
+  size: string;
+  genus: string | null;
+  is_boss: boolean;
+  elements: string[];
+  stats: CatalogPalStats;
+  food: number | null;
+  exp_ratio: number | null;
+  price: number | null;
+  breeding_power: number;
+  ignore_breeding: boolean;
+  male_probability: number;
+  work_suitability: Record<string, number>;
+  partner_skill: { name: string | null; description: string | null; unlock_item: string | null } | null;
+  learnset: { waza_id: string; level: number }[];
+  drops: { item_id: string; drop_rate: number; min: number; max: number }[];
+}
+
+export interface CatalogPalsResponse {
+  schema_version: number;
+  sources: DatasetSource[];
+  pals: CatalogPal[];
+}
+
+export interface PassiveModifiers {
+  work_speed_pct: number;
+  max_hp_pct: number;
+  attack_pct: number;
+  defense_pct: number;
+  sanity_drain_pct: number;
+  hunger_rate_pct: number;
+}
+
+export interface CatalogPassiveSkill {
+  passive_id: string | null;
+  name: string;
+  pool: "pal" | "other";
+  rank: number;
+  weight: number | null;
+  tags: string[];
+  description: string | null;
+  effects: { stat: string; value: number; is_percent: boolean; target: string | null }[];
+  modifiers: PassiveModifiers;
+}
+
+export interface CatalogPassivesResponse {
+  schema_version: number;
+  sources: DatasetSource[];
+  passives: CatalogPassiveSkill[];
+}
+
+export interface CatalogActiveSkill {
+  waza_id: string;
+  name: string | null;
+  description: string | null;
+  element: string | null;
+  category: string;
+  power: number;
+  min_range: number;
+  max_range: number;
+  cool_time: number;
+  effects: { kind: string; value: number }[];
+}
+
+export interface CatalogSkillsResponse {
+  schema_version: number;
+  sources: DatasetSource[];
+  skills: CatalogActiveSkill[];
+}
+
+export interface WorkerContribution {
+  pal_instance_id: string;
+  species_id: string | null;
+  display_name: string | null;
+  passive_work_speed_pct: number;
+  unresolved_passive_ids: string[];
+  work_speed_multiplier: number;
+  work_suitability: Record<string, number>;
+  work_rates: Record<string, number>;
+}
+
+export interface WorkTypePower {
+  work_suitability: string;
+  worker_count: number;
+  work_rate: number;
+}
+
+export interface ProductionNode {
+  building_id: string;
+  name: string;
+  stations: number;
+  steps: { work_suitability: string; workload: number; work_rate: number; seconds: number | null }[];
+  growth_seconds: number | null;
+  cycle_seconds: number | null;
+  cycles_per_hour: number;
+  products: { item_id: string; count: number; per_hour: number }[];
+  missing_work: string[];
+}
+
+export interface BaseProductionResponse {
+  import_version_id: string;
+  base_id: string;
+  base_name: string | null;
+  reference_work_rates: number[];
+  workers: WorkerContribution[];
+  work_power: WorkTypePower[];
+  nodes: ProductionNode[];
+}
+
+export interface ProductionTarget {
+  item_id: string;
+  per_hour: number;
+}
+
+export interface SolveRequest {
+  import_version_id: string;
+  base_id: string;
+  targets: ProductionTarget[];
+  candidate_pal_instance_ids?: string[];
+  max_workers?: number;
+  emit_patchset?: boolean;
+}
+
+export interface PlannedWorker {
+  pal_instance_id: string;
+  species_id: string | null;
+  display_name: string | null;
+  building_id: string;
+  work_rates: Record<string, number>;
+}
+
+export interface PlannedStation {
+  building_id: string;
+  name: string;
+  pal_instance_ids: string[];
+  node: ProductionNode;
+}
+
+export interface TargetOutcome {
+  item_id: string;
+  building_id: string;
+  target_per_hour: number;
+  expected_per_hour: number;
+  shortfall_per_hour: number;
+  limiting_factor: "worker_cap" | "no_capable_pals" | "station_limit" | null;
+}
+
+export interface PlannedPatchOperation {
+  sequence: number;
+  op_type: string;
+  target_kind: string;
+  target_id: string;
+  payload_json: Record<string, unknown>;
+}
+
+export interface SolveResponse {
+  import_version_id: string;
+  base_id: string;
+  worker_cap: number;
+  worker_cap_source: "request" | "world_option" | "default";
+  workers: PlannedWorker[];
+  stations: PlannedStation[];
+  targets: TargetOutcome[];
+  patchset: { operations: PlannedPatchOperation[] } | null;
+}
+
+export interface BreedingPair {
+  parent_a: string;
+  parent_b: string;
+  children: string[];
+  gender_dependent: boolean;
+}
+
+export interface BreedingStep {
+  parent_a: string;
+  parent_b: string;
+  child: string;
+  gender_dependent: boolean;
+}
+
+export interface BreedingParentsResponse {
+  child: string;
+  pairs: BreedingStep[];
+}
+
+export interface PassiveInheritanceRequest {
+  parent_a_passive_ids: string[];
+  parent_b_passive_ids: string[];
+  desired_passive_ids: string[];
+}
+
+export interface PassiveInheritanceResponse {
+  inherited_count_pct: [number, number][];
+  random_count_pct: [number, number][];
+  parent_pool: string[];
+  desired_passive_ids: string[];
+  missing_from_parents: string[];
+  all_desired_pct: number;
+  exact_pct: number;
+}
+
+export interface BreedingPathResponse {
+  import_version_id: string;
+  player_uid: string;
+  target: string;
+  owned_species: string[];
+  reachable: boolean;
+  steps: BreedingStep[];
+}
+
+export interface PatchValidationIssue {
+  code: string;
+  field: string | null;
+  message: string;
+}
+
+export interface OperationValidation {
+  sequence: number;
+  op_type: string;
+  target_kind: string;
+  target_id: string;
+  validated: boolean;
+  issues: PatchValidationIssue[];
+}
+
+export interface ValidatePatchResponse {
+  import_version_id: string;
+  valid: boolean;
+  operations: OperationValidation[];
+}
+
+export interface PalStats {
+  pal_instance_id: string;
+  owner_player_uid: string | null;
+  species_id: string | null;
+  display_name: string | null;
+  nickname: string | null;
+  level: number | null;
+  passive_hp_pct: number;
+  passive_attack_pct: number;
+  passive_defense_pct: number;
+  unresolved_passive_ids: string[];
+  max_hp: number | null;
+  attack: number | null;
+  defense: number | null;
+  combat_power: number | null;
+}
+
+export interface PalStatsResponse {
+  import_version_id: string;
+  condenser_stat_pct: number;
+  soul_stat_pct: number;
+  pals: PalStats[];
+}